@@ -0,0 +1,132 @@
+use super::ImportSettings;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+pub const ARTIFACT_EXTENSION: &str = "artifact";
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArtifactKey(u64);
+
+impl ArtifactKey {
+    pub fn new(source: &[u8], settings: &ImportSettings, importer: &str, version: u32) -> Self {
+        let settings = ron::to_string(settings).unwrap_or_default();
+
+        let mut hash = FNV_OFFSET;
+        for bytes in [
+            source,
+            settings.as_bytes(),
+            importer.as_bytes(),
+            &version.to_le_bytes(),
+        ] {
+            hash = fnv(hash, &(bytes.len() as u64).to_le_bytes());
+            hash = fnv(hash, bytes);
+        }
+
+        Self(hash)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("{:016x}.{}", self.0, ARTIFACT_EXTENSION)
+    }
+}
+
+fn fnv(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+
+pub struct ArtifactCache {
+    dir: PathBuf,
+    hits: usize,
+    misses: usize,
+}
+
+impl ArtifactCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, key: ArtifactKey) -> PathBuf {
+        self.dir.join(key.file_name())
+    }
+
+    pub fn contains(&self, key: ArtifactKey) -> bool {
+        self.path(key).is_file()
+    }
+
+    pub fn read(&mut self, key: ArtifactKey) -> Option<Vec<u8>> {
+        match std::fs::read(self.path(key)) {
+            Ok(bytes) => {
+                self.hits += 1;
+                Some(bytes)
+            }
+            Err(_) => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn write(&self, key: ArtifactKey, bytes: &[u8]) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let path = self.path(key);
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, bytes)?;
+        std::fs::rename(temp, path)
+    }
+
+    pub fn clean(&mut self) -> io::Result<usize> {
+        self.hits = 0;
+        self.misses = 0;
+
+        let read_dir = match std::fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        let mut removed = 0;
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let artifact = path
+                .extension()
+                .is_some_and(|extension| extension == ARTIFACT_EXTENSION || extension == "tmp");
+            if path.is_file() && artifact {
+                std::fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyCycle {
+    pub path: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self
+            .path
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+
+        write!(f, "Dependency cycle: {}", path.join(" -> "))
+    }
+}
+
+pub struct DependencyGraph {
+    dependencies: HashMap<PathBuf, Vec<PathBuf>>,
+    dependents: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self {
+            dependencies: HashMap::new(),
+            dependents: HashMap::new(),
+        }
+    }
+
+    pub fn dependencies(&self, path: &Path) -> &[PathBuf] {
+        self.dependencies
+            .get(path)
+            .map(|paths| paths.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn dependents(&self, path: &Path) -> &[PathBuf] {
+        self.dependents
+            .get(path)
+            .map(|paths| paths.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn add(&mut self, dependent: &Path, dependency: &Path) -> Result<bool, DependencyCycle> {
+        if self.dependencies(dependent).iter().any(|p| p == dependency) {
+            return Ok(false);
+        }
+
+        if let Some(mut path) = self.find_path(dependency, dependent) {
+            path.insert(0, dependent.to_path_buf());
+            return Err(DependencyCycle { path });
+        }
+
+        self.dependencies
+            .entry(dependent.to_path_buf())
+            .or_default()
+            .push(dependency.to_path_buf());
+        self.dependents
+            .entry(dependency.to_path_buf())
+            .or_default()
+            .push(dependent.to_path_buf());
+
+        Ok(true)
+    }
+
+    pub fn clear(&mut self, dependent: &Path) {
+        for dependency in self.dependencies.remove(dependent).unwrap_or_default() {
+            if let Some(dependents) = self.dependents.get_mut(&dependency) {
+                dependents.retain(|path| path != dependent);
+                if dependents.is_empty() {
+                    self.dependents.remove(&dependency);
+                }
+            }
+        }
+    }
+
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        if let Some(dependencies) = self.dependencies.remove(from) {
+            for dependency in &dependencies {
+                Self::replace(self.dependents.get_mut(dependency), from, to);
+            }
+
+            self.dependencies.insert(to.to_path_buf(), dependencies);
+        }
+
+        if let Some(dependents) = self.dependents.remove(from) {
+            for dependent in &dependents {
+                Self::replace(self.dependencies.get_mut(dependent), from, to);
+            }
+
+            self.dependents.insert(to.to_path_buf(), dependents);
+        }
+    }
+
+    pub fn dependents_recursive(&self, path: &Path) -> Vec<PathBuf> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![path.to_path_buf()];
+        let mut result = Vec::new();
+
+        while let Some(current) = stack.pop() {
+            for dependent in self.dependents(&current) {
+                if visited.insert(dependent.clone()) {
+                    result.push(dependent.clone());
+                    stack.push(dependent.clone());
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn sort(&self, paths: &[PathBuf]) -> Result<Vec<PathBuf>, DependencyCycle> {
+        let set = paths.iter().collect::<HashSet<_>>();
+        let mut visited = HashSet::new();
+        let mut visiting = Vec::new();
+        let mut sorted = Vec::new();
+
+        for path in paths {
+            self.visit(path, &set, &mut visited, &mut visiting, &mut sorted)?;
+        }
+
+        Ok(sorted)
+    }
+
+    fn visit(
+        &self,
+        path: &PathBuf,
+        set: &HashSet<&PathBuf>,
+        visited: &mut HashSet<PathBuf>,
+        visiting: &mut Vec<PathBuf>,
+        sorted: &mut Vec<PathBuf>,
+    ) -> Result<(), DependencyCycle> {
+        if visited.contains(path) {
+            return Ok(());
+        }
+
+        if let Some(start) = visiting.iter().position(|p| p == path) {
+            let mut cycle = visiting[start..].to_vec();
+            cycle.push(path.clone());
+            return Err(DependencyCycle { path: cycle });
+        }
+
+        visiting.push(path.clone());
+        for dependency in self.dependencies(path) {
+            if set.contains(dependency) {
+                self.visit(dependency, set, visited, visiting, sorted)?;
+            }
+        }
+        visiting.pop();
+
+        visited.insert(path.clone());
+        sorted.push(path.clone());
+
+        Ok(())
+    }
+
+    fn find_path(&self, from: &Path, to: &Path) -> Option<Vec<PathBuf>> {
+        if from == to {
+            return Some(vec![from.to_path_buf()]);
+        }
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![vec![from.to_path_buf()]];
+
+        while let Some(path) = stack.pop() {
+            let current = path.last().unwrap();
+            for dependency in self.dependencies(current) {
+                let mut next = path.clone();
+                next.push(dependency.clone());
+
+                if dependency == to {
+                    return Some(next);
+                }
+
+                if visited.insert(dependency.clone()) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn replace(paths: Option<&mut Vec<PathBuf>>, from: &Path, to: &Path) {
+        for path in paths.into_iter().flatten() {
+            if path == from {
+                *path = to.to_path_buf();
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use super::{Asset, AssetId, AssetType};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, Weak,
+    },
+};
+
+struct StrongHandle {
+    id: AssetId,
+    asset_type: AssetType,
+    drops: Sender<(AssetType, AssetId)>,
+}
+
+impl Drop for StrongHandle {
+    fn drop(&mut self) {
+        let _ = self.drops.send((self.asset_type, self.id));
+    }
+}
+
+pub struct Handle<T: Asset> {
+    id: AssetId,
+    strong: Option<Arc<StrongHandle>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Asset> Handle<T> {
+    pub fn untracked(id: AssetId) -> Self {
+        Self {
+            id,
+            strong: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        self.strong.is_some()
+    }
+
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            id: self.id,
+            weak: self.strong.as_ref().map(Arc::downgrade),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            strong: self.strong.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T: Asset> Eq for Handle<T> {}
+
+impl<T: Asset> Hash for Handle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T: Asset> Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle<{}>({})", std::any::type_name::<T>(), self.id)
+    }
+}
+
+impl<T: Asset> From<&Handle<T>> for AssetId {
+    fn from(handle: &Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+pub struct WeakHandle<T: Asset> {
+    id: AssetId,
+    weak: Option<Weak<StrongHandle>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Asset> WeakHandle<T> {
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        match &self.weak {
+            Some(weak) => weak.upgrade().map(|strong| Handle {
+                id: self.id,
+                strong: Some(strong),
+                _marker: PhantomData,
+            }),
+            None => Some(Handle::untracked(self.id)),
+        }
+    }
+}
+
+impl<T: Asset> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            weak: self.weak.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WeakHandle<{}>({})", std::any::type_name::<T>(), self.id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetEventKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+pub struct AssetEvent<T: Asset> {
+    kind: AssetEventKind,
+    id: AssetId,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Asset> AssetEvent<T> {
+    pub fn new(kind: AssetEventKind, id: AssetId) -> Self {
+        Self {
+            kind,
+            id,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn kind(&self) -> AssetEventKind {
+        self.kind
+    }
+
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    pub fn is_created(&self) -> bool {
+        self.kind == AssetEventKind::Created
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.kind == AssetEventKind::Modified
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.kind == AssetEventKind::Removed
+    }
+}
+
+impl<T: Asset> Clone for AssetEvent<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.kind, self.id)
+    }
+}
+
+impl<T: Asset> Debug for AssetEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AssetEvent<{}>::{:?}({})",
+            std::any::type_name::<T>(),
+            self.kind,
+            self.id
+        )
+    }
+}
+
+struct Tracked {
+    handles: HashMap<(AssetType, AssetId), Weak<StrongHandle>>,
+    sender: Sender<(AssetType, AssetId)>,
+    receiver: Receiver<(AssetType, AssetId)>,
+}
+
+#[derive(Clone)]
+pub(super) struct HandleTracker {
+    tracked: Arc<Mutex<Tracked>>,
+}
+
+impl HandleTracker {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            tracked: Arc::new(Mutex::new(Tracked {
+                handles: HashMap::new(),
+                sender,
+                receiver,
+            })),
+        }
+    }
+
+    pub fn handle<T: Asset>(&self, id: AssetId) -> Handle<T> {
+        let asset_type = TypeId::of::<T>().into();
+        let mut tracked = self.tracked.lock().unwrap();
+        let strong = match tracked
+            .handles
+            .get(&(asset_type, id))
+            .and_then(|weak| weak.upgrade())
+        {
+            Some(strong) => strong,
+            None => {
+                let strong = Arc::new(StrongHandle {
+                    id,
+                    asset_type,
+                    drops: tracked.sender.clone(),
+                });
+                tracked
+                    .handles
+                    .insert((asset_type, id), Arc::downgrade(&strong));
+                strong
+            }
+        };
+
+        Handle {
+            id,
+            strong: Some(strong),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn strong_count(&self, asset_type: AssetType, id: AssetId) -> usize {
+        self.tracked
+            .lock()
+            .unwrap()
+            .handles
+            .get(&(asset_type, id))
+            .map_or(0, |weak| weak.strong_count())
+    }
+
+    pub fn forget(&self, asset_type: AssetType, id: AssetId) {
+        self.tracked
+            .lock()
+            .unwrap()
+            .handles
+            .remove(&(asset_type, id));
+    }
+
+    pub fn unused(&self) -> Vec<(AssetType, AssetId)> {
+        let mut tracked = self.tracked.lock().unwrap();
+        let dropped = tracked.receiver.try_iter().collect::<Vec<_>>();
+
+        let mut unused = Vec::new();
+        for (asset_type, id) in dropped {
+            let dead = tracked
+                .handles
+                .get(&(asset_type, id))
+                .is_some_and(|weak| weak.strong_count() == 0);
+
+            if dead {
+                tracked.handles.remove(&(asset_type, id));
+                unused.push((asset_type, id));
+            }
+        }
+
+        unused
+    }
+}
@@ -0,0 +1,347 @@
+use super::{AssetIo, AssetMetadata, DirectoryIo};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const ARCHIVE_MAGIC: &[u8; 4] = b"FPAK";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const HEADER_SIZE: u64 = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    pub offset: u64,
+    pub stored: u64,
+    pub len: u64,
+    pub compressed: bool,
+    pub modified: SystemTime,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PackStats {
+    pub files: usize,
+    pub bytes: u64,
+    pub stored: u64,
+}
+
+pub struct ArchiveIo {
+    mount: PathBuf,
+    entries: BTreeMap<PathBuf, ArchiveEntry>,
+    file: Mutex<File>,
+}
+
+impl ArchiveIo {
+    pub fn open(path: &Path, mount: impl Into<PathBuf>) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        file.read_exact(&mut header)?;
+        if &header[0..4] != ARCHIVE_MAGIC {
+            return Err(invalid(format!(
+                "{} is not an asset archive",
+                path.display()
+            )));
+        }
+
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        if version != ARCHIVE_VERSION {
+            return Err(invalid(format!(
+                "{} has unsupported archive version {}",
+                path.display(),
+                version
+            )));
+        }
+
+        let mount = mount.into();
+        let index_offset = u64::from_le_bytes(header[8..16].try_into().unwrap());
+        file.seek(SeekFrom::Start(index_offset))?;
+
+        let mut reader = BufReader::new(&mut file);
+        let count = read_u32(&mut reader)?;
+        let mut entries = BTreeMap::new();
+        for _ in 0..count {
+            let name_len = read_u32(&mut reader)? as usize;
+            let mut name = vec![0u8; name_len];
+            reader.read_exact(&mut name)?;
+            let name = String::from_utf8(name).map_err(|e| invalid(e.to_string()))?;
+
+            let entry = ArchiveEntry {
+                offset: read_u64(&mut reader)?,
+                stored: read_u64(&mut reader)?,
+                len: read_u64(&mut reader)?,
+                compressed: read_u8(&mut reader)? != 0,
+                modified: UNIX_EPOCH + Duration::from_secs(read_u64(&mut reader)?),
+            };
+
+            entries.insert(mount.join(name), entry);
+        }
+
+        Ok(Self {
+            mount,
+            entries,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn pack(source: &Path, output: &Path, compress: bool) -> io::Result<PackStats> {
+        let mut writer = BufWriter::new(File::create(output)?);
+        let output = output.canonicalize()?;
+        let files = DirectoryIo::new()
+            .list(source)?
+            .into_iter()
+            .filter(|path| path.canonicalize().ok().as_ref() != Some(&output))
+            .collect::<Vec<_>>();
+        let mut stats = PackStats::default();
+        let mut index = Vec::new();
+
+        writer.write_all(&[0u8; HEADER_SIZE as usize])?;
+        let mut offset = HEADER_SIZE;
+
+        for path in files {
+            let name = path
+                .strip_prefix(source)
+                .map_err(|e| invalid(e.to_string()))?
+                .to_str()
+                .ok_or_else(|| invalid(format!("{} is not valid utf-8", path.display())))?
+                .replace('\\', "/");
+
+            let bytes = std::fs::read(&path)?;
+            let modified = std::fs::metadata(&path)?
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            let deflated = match compress {
+                true => Some(deflate(&bytes)?).filter(|deflated| deflated.len() < bytes.len()),
+                false => None,
+            };
+            let stored = deflated.as_deref().unwrap_or(&bytes);
+            writer.write_all(stored)?;
+
+            index.push((
+                name,
+                ArchiveEntry {
+                    offset,
+                    stored: stored.len() as u64,
+                    len: bytes.len() as u64,
+                    compressed: deflated.is_some(),
+                    modified: UNIX_EPOCH + Duration::from_secs(modified),
+                },
+            ));
+
+            offset += stored.len() as u64;
+            stats.files += 1;
+            stats.bytes += bytes.len() as u64;
+            stats.stored += stored.len() as u64;
+        }
+
+        writer.write_all(&(index.len() as u32).to_le_bytes())?;
+        for (name, entry) in &index {
+            let modified = entry
+                .modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            writer.write_all(&(name.len() as u32).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.stored.to_le_bytes())?;
+            writer.write_all(&entry.len.to_le_bytes())?;
+            writer.write_all(&[entry.compressed as u8])?;
+            writer.write_all(&modified.to_le_bytes())?;
+        }
+
+        writer.seek(SeekFrom::Start(0))?;
+        writer.write_all(ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.flush()?;
+
+        Ok(stats)
+    }
+
+    pub fn mount(&self) -> &Path {
+        &self.mount
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&PathBuf, &ArchiveEntry)> {
+        self.entries.iter()
+    }
+
+    fn entry(&self, path: &Path) -> io::Result<&ArchiveEntry> {
+        self.entries.get(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} not found in archive", path.display()),
+            )
+        })
+    }
+}
+
+impl AssetIo for ArchiveIo {
+    fn list(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let listed = self
+            .entries
+            .keys()
+            .filter(|entry| entry.starts_with(path))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match listed.is_empty() {
+            true => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} not found in archive", path.display()),
+            )),
+            false => Ok(listed),
+        }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let entry = self.entry(path)?;
+
+        let mut stored = vec![0u8; entry.stored as usize];
+        {
+            let mut file = self.file.lock().unwrap();
+            file.seek(SeekFrom::Start(entry.offset))?;
+            file.read_exact(&mut stored)?;
+        }
+
+        if !entry.compressed {
+            return Ok(stored);
+        }
+
+        let mut bytes = Vec::with_capacity(entry.len as usize);
+        DeflateDecoder::new(stored.as_slice()).read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<AssetMetadata> {
+        let entry = self.entry(path)?;
+        Ok(AssetMetadata {
+            len: entry.len,
+            modified: entry.modified,
+        })
+    }
+}
+
+fn deflate(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut bytes = [0u8; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("frost-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(path.join("source/models")).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn text() -> Vec<u8> {
+        "compressible ".repeat(64).into_bytes()
+    }
+
+    fn round_trip(name: &str, compress: bool) {
+        let dir = TempDir::new(name);
+        let source = dir.0.join("source");
+        std::fs::write(source.join("readme.txt"), text()).unwrap();
+        std::fs::write(source.join("models/cube.bin"), [7u8, 1, 9]).unwrap();
+
+        let output = source.join("assets.pak");
+        let stats = ArchiveIo::pack(&source, &output, compress).unwrap();
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.bytes, text().len() as u64 + 3);
+        assert_eq!(stats.stored < stats.bytes, compress);
+
+        let archive = ArchiveIo::open(&output, "assets").unwrap();
+        let readme = Path::new("assets/readme.txt");
+        let cube = Path::new("assets/models/cube.bin");
+
+        assert_eq!(archive.list(Path::new("assets")).unwrap(), [cube, readme]);
+        assert_eq!(archive.list(Path::new("assets/models")).unwrap(), [cube]);
+        assert!(archive.list(Path::new("other")).is_err());
+
+        assert_eq!(archive.read(readme).unwrap(), text());
+        assert_eq!(archive.read(cube).unwrap(), [7, 1, 9]);
+        assert_eq!(archive.metadata(readme).unwrap().len, text().len() as u64);
+        assert_eq!(archive.metadata(cube).unwrap().len, 3);
+        assert_eq!(
+            archive
+                .read(Path::new("assets/missing.txt"))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let compressed = archive
+            .entries()
+            .map(|(path, entry)| (path.as_path(), entry.compressed))
+            .collect::<Vec<_>>();
+        assert_eq!(compressed, [(cube, false), (readme, compress)]);
+        assert!(archive.write(readme, b"nope").is_err());
+    }
+
+    #[test]
+    fn pack_round_trip() {
+        round_trip("pack", false);
+    }
+
+    #[test]
+    fn pack_round_trip_compressed() {
+        round_trip("pack-compressed", true);
+    }
+
+    #[test]
+    fn open_rejects_other_files() {
+        let dir = TempDir::new("not-an-archive");
+        let path = dir.0.join("assets.pak");
+        std::fs::write(&path, [0u8; 32]).unwrap();
+
+        let error = ArchiveIo::open(&path, "assets").err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,62 @@
+use super::{AssetIo, AssetMetadata};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+pub struct DirectoryIo;
+
+impl DirectoryIo {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AssetIo for DirectoryIo {
+    fn list(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut dirs = vec![path.to_path_buf()];
+
+        std::fs::metadata(path)?;
+        while let Some(dir) = dirs.pop() {
+            let Ok(read_dir) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => dirs.push(path),
+                    Ok(file_type) if file_type.is_file() => files.push(path),
+                    _ => {}
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<AssetMetadata> {
+        let metadata = std::fs::metadata(path)?;
+        if !metadata.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not a file", path.display()),
+            ));
+        }
+
+        Ok(AssetMetadata {
+            len: metadata.len(),
+            modified: metadata.modified()?,
+        })
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        std::fs::write(path, bytes)
+    }
+}
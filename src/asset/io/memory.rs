@@ -0,0 +1,132 @@
+use super::{AssetIo, AssetMetadata};
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::SystemTime,
+};
+
+pub struct MemoryIo {
+    files: RwLock<BTreeMap<PathBuf, (Vec<u8>, SystemTime)>>,
+}
+
+impl MemoryIo {
+    pub fn new() -> Self {
+        Self {
+            files: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn with_file(self, path: impl Into<PathBuf>, bytes: impl Into<Vec<u8>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: impl Into<Vec<u8>>) {
+        let mut files = self.files.write().unwrap();
+        files.insert(path.into(), (bytes.into(), SystemTime::now()));
+    }
+
+    pub fn remove(&self, path: &Path) -> Option<Vec<u8>> {
+        let mut files = self.files.write().unwrap();
+        files.remove(path).map(|(bytes, _)| bytes)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.read().unwrap().is_empty()
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found", path.display()),
+        )
+    }
+}
+
+impl AssetIo for MemoryIo {
+    fn list(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let files = self.files.read().unwrap();
+        let listed = files
+            .keys()
+            .filter(|file| file.starts_with(path))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match listed.is_empty() {
+            true => Err(MemoryIo::not_found(path)),
+            false => Ok(listed),
+        }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let files = self.files.read().unwrap();
+        files
+            .get(path)
+            .map(|(bytes, _)| bytes.clone())
+            .ok_or_else(|| MemoryIo::not_found(path))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<AssetMetadata> {
+        let files = self.files.read().unwrap();
+        files
+            .get(path)
+            .map(|(bytes, modified)| AssetMetadata {
+                len: bytes.len() as u64,
+                modified: *modified,
+            })
+            .ok_or_else(|| MemoryIo::not_found(path))
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        self.insert(path, bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_can_be_read_listed_and_replaced() {
+        let io = MemoryIo::new()
+            .with_file("assets/a.txt", "a")
+            .with_file("assets/models/b.obj", "b");
+
+        assert_eq!(io.len(), 2);
+        assert_eq!(io.read(Path::new("assets/a.txt")).unwrap(), b"a");
+        assert_eq!(
+            io.metadata(Path::new("assets/models/b.obj")).unwrap().len,
+            1
+        );
+        assert_eq!(
+            io.list(Path::new("assets/models")).unwrap(),
+            [PathBuf::from("assets/models/b.obj")]
+        );
+        assert!(io.exists(Path::new("assets/a.txt")));
+
+        io.write(Path::new("assets/a.txt"), b"replaced").unwrap();
+        assert_eq!(io.read(Path::new("assets/a.txt")).unwrap(), b"replaced");
+        assert_eq!(io.metadata(Path::new("assets/a.txt")).unwrap().len, 8);
+    }
+
+    #[test]
+    fn removed_files_are_not_found() {
+        let io = MemoryIo::new().with_file("assets/a.txt", "a");
+
+        assert_eq!(io.remove(Path::new("assets/a.txt")), Some(b"a".to_vec()));
+        assert!(io.is_empty());
+        assert!(!io.exists(Path::new("assets/a.txt")));
+        assert_eq!(
+            io.read(Path::new("assets/a.txt")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(io.list(Path::new("assets")).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+pub mod archive;
+pub mod directory;
+pub mod memory;
+
+pub use archive::*;
+pub use directory::*;
+pub use memory::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssetMetadata {
+    pub len: u64,
+    pub modified: SystemTime,
+}
+
+pub trait AssetIo: Send + Sync + 'static {
+    fn list(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    fn metadata(&self, path: &Path) -> io::Result<AssetMetadata>;
+
+    fn write(&self, path: &Path, _bytes: &[u8]) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} is read only", path.display()),
+        ))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    fn watch(&self, path: &Path) -> io::Result<HashMap<PathBuf, SystemTime>> {
+        let mut files = HashMap::new();
+        for file in self.list(path)? {
+            if let Ok(metadata) = self.metadata(&file) {
+                files.insert(file, metadata.modified);
+            }
+        }
+
+        Ok(files)
+    }
+}
@@ -0,0 +1,234 @@
+use super::{AssetId, AssetIo};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+pub const META_EXTENSION: &str = "meta";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl From<bool> for SettingValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for SettingValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for SettingValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for SettingValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for SettingValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ImportSettings {
+    values: BTreeMap<String, SettingValue>,
+}
+
+impl ImportSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<SettingValue>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub fn set(&mut self, key: &str, value: impl Into<SettingValue>) {
+        self.values.insert(key.to_string(), value.into());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SettingValue> {
+        self.values.get(key)
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.values.get(key)? {
+            SettingValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn int(&self, key: &str) -> Option<i64> {
+        match self.values.get(key)? {
+            SettingValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn float(&self, key: &str) -> Option<f64> {
+        match self.values.get(key)? {
+            SettingValue::Float(value) => Some(*value),
+            SettingValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.values.get(key)? {
+            SettingValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SettingValue)> {
+        self.values.iter()
+    }
+
+    pub fn matches(&self, required: &ImportSettings) -> bool {
+        required
+            .values
+            .iter()
+            .all(|(key, value)| self.values.get(key) == Some(value))
+    }
+
+    pub fn apply(&mut self, overrides: &ImportSettings) -> bool {
+        let changed = !self.matches(overrides);
+        for (key, value) in &overrides.values {
+            self.values.insert(key.clone(), value.clone());
+        }
+
+        changed
+    }
+
+    pub fn merge_defaults(&mut self, defaults: &ImportSettings) -> bool {
+        let mut changed = false;
+        for (key, value) in &defaults.values {
+            if !self.values.contains_key(key) {
+                self.values.insert(key.clone(), value.clone());
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AssetMeta {
+    pub uuid: String,
+    pub importer: String,
+    pub version: u32,
+    #[serde(default)]
+    pub settings: ImportSettings,
+}
+
+impl AssetMeta {
+    pub fn new(importer: &str, version: u32, settings: ImportSettings) -> Self {
+        Self {
+            uuid: uuid::Uuid::new_v4().to_string(),
+            importer: importer.to_string(),
+            version,
+            settings,
+        }
+    }
+
+    pub fn id(&self) -> AssetId {
+        match uuid::Uuid::parse_str(&self.uuid) {
+            Ok(uuid) => {
+                let (high, low) = uuid.as_u64_pair();
+                AssetId::new(high ^ low)
+            }
+            Err(_) => self.uuid.as_str().into(),
+        }
+    }
+
+    pub fn regenerate(&mut self) {
+        self.uuid = uuid::Uuid::new_v4().to_string();
+    }
+
+    pub fn path(source: &Path) -> PathBuf {
+        let mut path = source.as_os_str().to_owned();
+        path.push(".");
+        path.push(META_EXTENSION);
+        PathBuf::from(path)
+    }
+
+    pub fn source(meta: &Path) -> Option<PathBuf> {
+        match meta.extension() {
+            Some(extension) if extension == META_EXTENSION => Some(meta.with_extension("")),
+            _ => None,
+        }
+    }
+
+    pub fn resolve(
+        io: &dyn AssetIo,
+        source: &Path,
+        importer: &str,
+        version: u32,
+        defaults: &ImportSettings,
+    ) -> (AssetMeta, bool) {
+        let path = AssetMeta::path(source);
+        let (mut meta, mut dirty) = match AssetMeta::load(io, &path) {
+            Ok(meta) => (meta, false),
+            Err(e) => {
+                if io.exists(&path) {
+                    eprintln!("Failed to read {}: {}", path.display(), e);
+                }
+
+                (AssetMeta::new(importer, version, defaults.clone()), true)
+            }
+        };
+
+        if meta.importer != importer || meta.version != version {
+            meta.importer = importer.to_string();
+            meta.version = version;
+            dirty = true;
+        }
+
+        dirty |= meta.settings.merge_defaults(defaults);
+
+        (meta, dirty)
+    }
+
+    pub fn store(&self, io: &dyn AssetIo, source: &Path) {
+        let path = AssetMeta::path(source);
+        match self.save(io, &path) {
+            Err(e) if e.kind() != ErrorKind::Unsupported => {
+                eprintln!("Failed to write {}: {}", path.display(), e);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn load(io: &dyn AssetIo, path: &Path) -> std::io::Result<AssetMeta> {
+        let bytes = io.read(path)?;
+        let text = String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        ron::from_str(&text).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn save(&self, io: &dyn AssetIo, path: &Path) -> std::io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        io.write(path, text.as_bytes())
+    }
+}
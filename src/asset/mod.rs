@@ -1,64 +1,230 @@
 use crate::{
-    ecs::{Resource, ResourceManager},
+    ecs::{Resource, ResourceManager, World},
     shared::{ext::path::PathExt, ResourceId, ResourceType},
 };
 use std::{
     any::{Any, TypeId},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-pub trait Asset: 'static {}
+pub mod cache;
+pub mod dependency;
+pub mod error;
+pub mod handle;
+pub mod io;
+pub mod meta;
+pub mod plugin;
+pub mod server;
+pub mod watcher;
+
+pub use cache::*;
+pub use dependency::*;
+pub use error::*;
+pub use handle::*;
+pub use io::*;
+pub use meta::*;
+pub use server::*;
+pub use watcher::*;
+
+pub trait Asset: Send + 'static {}
 
 pub type AssetId = ResourceId;
 pub type AssetType = ResourceType;
+pub type SortedImporters<'a> = HashMap<&'a str, Vec<&'a ImporterEntry>>;
+type Placeholder = Arc<dyn Fn(&mut AssetDatabase, AssetId) + Send + Sync>;
+type Failure = (AssetType, AssetId, ImportError);
 
 pub struct AssetInfo {
     pub id: AssetId,
     pub name: String,
     pub path: PathBuf,
+    pub settings: ImportSettings,
 }
 
 pub struct ImportContext<'a> {
     info: AssetInfo,
-    resources: &'a ResourceManager,
     assets: &'a mut AssetDatabase,
+    importers: &'a SortedImporters<'a>,
+    importer: Option<(String, u32)>,
+    artifact: Option<ArtifactKey>,
 }
 
 impl<'a> ImportContext<'a> {
     pub fn new(
         info: AssetInfo,
-        resources: &'a ResourceManager,
         assets: &'a mut AssetDatabase,
+        importers: &'a SortedImporters<'a>,
     ) -> Self {
         Self {
             info,
-            resources,
             assets,
+            importers,
+            importer: None,
+            artifact: None,
         }
     }
 
+    pub fn with_importer(mut self, name: &str, version: u32) -> Self {
+        self.importer = Some((name.to_string(), version));
+        self
+    }
+
     pub fn info(&self) -> &AssetInfo {
         &self.info
     }
 
-    pub fn resources(&self) -> &ResourceManager {
-        self.resources
+    pub fn settings(&self) -> &ImportSettings {
+        &self.info.settings
+    }
+
+    pub fn sub_asset_id(&self, name: &str) -> AssetId {
+        AssetId::from(format!("{}#{}", self.info.id, name))
     }
 
     pub fn assets(&mut self) -> &mut AssetDatabase {
         self.assets
     }
+
+    pub fn artifact_key(&mut self) -> Option<ArtifactKey> {
+        if self.artifact.is_none() && self.assets.cache.is_some() {
+            let (name, version) = self.importer.as_ref()?;
+            let source = self.read().ok()?;
+            let key = ArtifactKey::new(&source, &self.info.settings, name, *version);
+            self.artifact = Some(key);
+        }
+
+        self.artifact
+    }
+
+    pub fn read_artifact(&mut self) -> Option<Vec<u8>> {
+        let key = self.artifact_key()?;
+        self.assets.cache.as_mut()?.read(key)
+    }
+
+    pub fn write_artifact(&mut self, bytes: &[u8]) {
+        let key = self.artifact_key();
+        let (Some(key), Some(cache)) = (key, &self.assets.cache) else {
+            return;
+        };
+
+        if let Err(e) = cache.write(key, bytes) {
+            eprintln!(
+                "Failed to write artifact for {}: {}",
+                self.info.path.display(),
+                e
+            );
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ImportError {
+        ImportError::new(message).with_path(&self.info.path)
+    }
+
+    pub fn error_at(&self, line: usize, message: impl Into<String>) -> ImportError {
+        self.error(message).with_line(line)
+    }
+
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        self.assets.io.read(&self.info.path)
+    }
+
+    pub fn read_path(&mut self, path: impl AsRef<Path>) -> std::io::Result<Vec<u8>> {
+        let path = self
+            .assets
+            .resolve_dependency(&self.info.path, path.as_ref());
+        if !self.assets.depend(&self.info.path, &path) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Dependency cycle through {}", path.display()),
+            ));
+        }
+
+        self.assets.io.read(&path)
+    }
+
+    pub fn load_dependency(&mut self, path: impl AsRef<Path>) -> Option<AssetId> {
+        self.load_dependency_with(path, &ImportSettings::new())
+    }
+
+    /// Loads a dependency whose import settings must include `settings`,
+    /// re-importing it when its meta file says otherwise.
+    pub fn load_dependency_with(
+        &mut self,
+        path: impl AsRef<Path>,
+        settings: &ImportSettings,
+    ) -> Option<AssetId> {
+        let path = self
+            .assets
+            .resolve_dependency(&self.info.path, path.as_ref());
+        if !self.assets.depend(&self.info.path, &path) {
+            return None;
+        }
+
+        let imported = self.assets.sources.contains_key(&path);
+        let stale = !imported || !self.assets.has_settings(&path, settings);
+        if stale && self.assets.io.exists(&path) {
+            if imported {
+                eprintln!(
+                    "Re-importing {} with the settings required by {}",
+                    path.display(),
+                    self.info.path.display()
+                );
+            }
+
+            self.assets
+                .import_file_with(&path, self.importers, settings);
+        }
+
+        let id = self.assets.id(&path);
+        if id.is_none() {
+            eprintln!(
+                "Failed to load dependency {} of {}",
+                path.display(),
+                self.info.path.display()
+            );
+        }
+
+        id
+    }
+
+    pub fn add_dependency(&mut self, id: AssetId) -> bool {
+        match self.assets.path(&id).map(Path::to_path_buf) {
+            Some(path) => self.assets.depend(&self.info.path, &path),
+            None => {
+                eprintln!(
+                    "Unknown dependency {:?} of {}",
+                    id,
+                    self.info.path.display()
+                );
+                false
+            }
+        }
+    }
+}
+
+pub trait AssetStore: Send + 'static {
+    fn remove_asset(&mut self, id: &AssetId) -> bool;
+    fn merge(&mut self, other: Box<dyn AssetStore>);
+    fn flush(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
 pub struct AssetStorage<T: Asset> {
     assets: HashMap<AssetId, T>,
+    pending: Vec<AssetEvent<T>>,
+    events: Vec<AssetEvent<T>>,
 }
 
 impl<T: Asset> AssetStorage<T> {
     pub fn new() -> Self {
         Self {
             assets: HashMap::new(),
+            pending: Vec::new(),
+            events: Vec::new(),
         }
     }
 
@@ -66,68 +232,245 @@ impl<T: Asset> AssetStorage<T> {
         self.assets.get(id)
     }
 
+    pub fn get_mut(&mut self, id: &AssetId) -> Option<AssetMut<'_, T>> {
+        let asset = self.assets.get_mut(id)?;
+        Some(AssetMut {
+            id: *id,
+            asset,
+            pending: &mut self.pending,
+            modified: false,
+        })
+    }
+
+    pub fn contains(&self, id: &AssetId) -> bool {
+        self.assets.contains_key(id)
+    }
+
     pub fn insert(&mut self, id: AssetId, asset: T) {
-        self.assets.insert(id, asset);
+        let kind = match self.assets.insert(id, asset) {
+            Some(_) => AssetEventKind::Modified,
+            None => AssetEventKind::Created,
+        };
+
+        self.pending.push(AssetEvent::new(kind, id));
     }
 
     pub fn remove(&mut self, id: &AssetId) -> Option<T> {
-        self.assets.remove(id)
+        let asset = self.assets.remove(id)?;
+        self.pending
+            .push(AssetEvent::new(AssetEventKind::Removed, *id));
+        Some(asset)
     }
 
     pub fn clear(&mut self) {
+        for id in self.assets.keys() {
+            self.pending
+                .push(AssetEvent::new(AssetEventKind::Removed, *id));
+        }
+
         self.assets.clear();
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&AssetId, &T)> {
         self.assets.iter()
     }
+
+    pub fn events(&self) -> &[AssetEvent<T>] {
+        &self.events
+    }
+}
+
+impl<T: Asset> AssetStore for AssetStorage<T> {
+    fn remove_asset(&mut self, id: &AssetId) -> bool {
+        self.remove(id).is_some()
+    }
+
+    fn merge(&mut self, other: Box<dyn AssetStore>) {
+        if let Ok(other) = other.into_any().downcast::<AssetStorage<T>>() {
+            for (id, asset) in other.assets {
+                self.insert(id, asset);
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        self.events = std::mem::take(&mut self.pending);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+pub struct AssetMut<'a, T: Asset> {
+    id: AssetId,
+    asset: &'a mut T,
+    pending: &'a mut Vec<AssetEvent<T>>,
+    modified: bool,
+}
+
+impl<'a, T: Asset> std::ops::Deref for AssetMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.asset
+    }
+}
+
+impl<'a, T: Asset> std::ops::DerefMut for AssetMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+        self.asset
+    }
+}
+
+impl<'a, T: Asset> Drop for AssetMut<'a, T> {
+    fn drop(&mut self) {
+        if self.modified {
+            self.pending
+                .push(AssetEvent::new(AssetEventKind::Modified, self.id));
+        }
+    }
 }
 
 pub struct AssetDatabase {
-    storages: HashMap<AssetType, Box<dyn Any>>,
+    storages: HashMap<AssetType, Box<dyn AssetStore>>,
+    handles: HandleTracker,
+    root: PathBuf,
+    sources: HashMap<PathBuf, Vec<(AssetType, AssetId)>>,
+    importing: Vec<PathBuf>,
+    dependencies: DependencyGraph,
+    cache: Option<ArtifactCache>,
+    io: Arc<dyn AssetIo>,
+    errors: Vec<ImportError>,
+    failures: Option<Vec<Failure>>,
+    placeholders: HashMap<AssetType, Placeholder>,
+    placeholder_ids: HashSet<(AssetType, AssetId)>,
+    metas: HashMap<PathBuf, AssetMeta>,
+    ids: HashMap<PathBuf, AssetId>,
+    paths: HashMap<AssetId, PathBuf>,
 }
 
 impl AssetDatabase {
     pub fn new() -> Self {
         Self {
             storages: HashMap::new(),
+            handles: HandleTracker::new(),
+            root: PathBuf::new(),
+            sources: HashMap::new(),
+            importing: Vec::new(),
+            dependencies: DependencyGraph::new(),
+            cache: None,
+            io: Arc::new(DirectoryIo::new()),
+            errors: Vec::new(),
+            failures: None,
+            placeholders: HashMap::new(),
+            placeholder_ids: HashSet::new(),
+            metas: HashMap::new(),
+            ids: HashMap::new(),
+            paths: HashMap::new(),
         }
     }
 
-    pub fn get<T: Asset>(&self, id: &AssetId) -> Option<&T> {
+    pub fn storage<T: Asset>(&self) -> Option<&AssetStorage<T>> {
         let asset_type = TypeId::of::<T>().into();
         self.storages
             .get(&asset_type)
-            .and_then(|storage| storage.downcast_ref::<AssetStorage<T>>())
-            .and_then(|storage| storage.get(id))
+            .and_then(|storage| storage.as_any().downcast_ref::<AssetStorage<T>>())
     }
 
-    pub fn insert<T: Asset>(&mut self, id: AssetId, asset: T) {
+    pub fn storage_mut<T: Asset>(&mut self) -> &mut AssetStorage<T> {
         let asset_type = TypeId::of::<T>().into();
-        let storage = self
-            .storages
+        self.storages
             .entry(asset_type)
-            .or_insert_with(|| Box::new(AssetStorage::<T>::new()));
-        storage
+            .or_insert_with(|| Box::new(AssetStorage::<T>::new()))
+            .as_any_mut()
             .downcast_mut::<AssetStorage<T>>()
             .unwrap()
-            .insert(id, asset);
+    }
+
+    pub fn get<T: Asset>(&self, id: &AssetId) -> Option<&T> {
+        self.storage::<T>().and_then(|storage| storage.get(id))
+    }
+
+    pub fn get_mut<T: Asset>(&mut self, id: &AssetId) -> Option<AssetMut<'_, T>> {
+        self.storage_mut::<T>().get_mut(id)
+    }
+
+    pub fn asset<T: Asset>(&self, handle: &Handle<T>) -> Option<&T> {
+        self.get(&handle.id())
+    }
+
+    pub fn contains<T: Asset>(&self, id: &AssetId) -> bool {
+        self.storage::<T>()
+            .is_some_and(|storage| storage.contains(id))
+    }
+
+    pub fn insert<T: Asset>(&mut self, id: AssetId, asset: T) {
+        self.storage_mut::<T>().insert(id, asset);
+        self.placeholder_ids.remove(&(TypeId::of::<T>().into(), id));
+
+        if let Some(path) = self.importing.last() {
+            let asset_type = TypeId::of::<T>().into();
+            let assets = self.sources.entry(path.clone()).or_default();
+            if !assets.contains(&(asset_type, id)) {
+                assets.push((asset_type, id));
+            }
+        }
+    }
+
+    pub fn add<T: Asset>(&mut self, id: AssetId, asset: T) -> Handle<T> {
+        self.insert(id, asset);
+        self.handles.handle(id)
+    }
+
+    pub fn handle<T: Asset>(&mut self, id: &AssetId) -> Option<Handle<T>> {
+        if self.contains::<T>(id) {
+            Some(self.handles.handle(*id))
+        } else {
+            None
+        }
+    }
+
+    pub fn track<T: Asset>(&self, id: AssetId) -> Handle<T> {
+        self.handles.handle(id)
+    }
+
+    pub fn strong_count<T: Asset>(&self, id: &AssetId) -> usize {
+        self.handles.strong_count(TypeId::of::<T>().into(), *id)
     }
 
     pub fn remove<T: Asset>(&mut self, id: &AssetId) -> Option<T> {
-        let asset_type = TypeId::of::<T>().into();
-        self.storages
-            .get_mut(&asset_type)
-            .and_then(|storage| storage.downcast_mut::<AssetStorage<T>>())
-            .and_then(|storage| storage.remove(id))
+        self.handles.forget(TypeId::of::<T>().into(), *id);
+        self.storage_mut::<T>().remove(id)
     }
 
     pub fn iter<T: Asset>(&self) -> Option<impl Iterator<Item = (&AssetId, &T)>> {
-        let asset_type = TypeId::of::<T>().into();
-        self.storages
-            .get(&asset_type)
-            .and_then(|storage| storage.downcast_ref::<AssetStorage<T>>())
-            .map(|storage| storage.iter())
+        self.storage::<T>().map(|storage| storage.iter())
+    }
+
+    pub fn events<T: Asset>(&self) -> &[AssetEvent<T>] {
+        self.storage::<T>()
+            .map(|storage| storage.events())
+            .unwrap_or(&[])
+    }
+
+    pub fn update(&mut self) {
+        for (asset_type, id) in self.handles.unused() {
+            self.remove_erased(asset_type, id);
+        }
+
+        for storage in self.storages.values_mut() {
+            storage.flush();
+        }
     }
 
     pub fn clear(&mut self) {
@@ -138,43 +481,468 @@ impl AssetDatabase {
 impl AssetDatabase {
     pub fn load(base_path: &Path, resources: &ResourceManager, importers: &ImporterRepo) {
         let mut db = resources.resource_mut::<AssetDatabase>();
-        let sorted_importers: HashMap<&str, Vec<&Box<dyn BaseImporter>>> = importers.sort();
+        let sorted_importers = importers.sort();
+
+        db.root = base_path.to_path_buf();
+
+        let paths = match db.io.list(base_path) {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("Failed to read assets in {}: {}", base_path.display(), e);
+                return;
+            }
+        };
+
+        for path in paths {
+            if !db.ids.contains_key(&path) {
+                db.import_file(&path, &sorted_importers);
+            }
+        }
 
-        AssetDatabase::load_inner(base_path, &mut db, resources, &sorted_importers);
+        if !db.errors.is_empty() {
+            eprintln!("{} asset imports failed", db.errors.len());
+        }
     }
 
-    fn load_inner(
-        base_path: &Path,
-        db: &mut AssetDatabase,
-        resources: &ResourceManager,
-        importers: &HashMap<&str, Vec<&Box<dyn BaseImporter>>>,
+    pub fn reload(changes: &[AssetChange], resources: &ResourceManager, importers: &ImporterRepo) {
+        let mut db = resources.resource_mut::<AssetDatabase>();
+        let sorted_importers = importers.sort();
+
+        let mut imports = Vec::new();
+        let mut removed = Vec::new();
+        for change in changes {
+            if let Some(source) = AssetMeta::source(change.path()) {
+                let edited = AssetMeta::load(db.io.as_ref(), change.path())
+                    .is_ok_and(|meta| db.metas.get(&source) != Some(&meta));
+                if db.io.exists(&source) && edited && !imports.contains(&source) {
+                    imports.push(source);
+                }
+
+                continue;
+            }
+
+            match change {
+                AssetChange::Added(path) | AssetChange::Modified(path) => {
+                    if !imports.contains(path) {
+                        imports.push(path.clone());
+                    }
+                }
+                AssetChange::Removed(path) => removed.push(path.clone()),
+            }
+        }
+
+        let mut dependents = Vec::new();
+        for path in &imports {
+            dependents.extend(db.dependencies.dependents_recursive(path));
+        }
+
+        for path in removed {
+            let moved = db.ids.get(&path).and_then(|id| {
+                imports.iter().find(|import| {
+                    AssetMeta::load(db.io.as_ref(), &AssetMeta::path(import))
+                        .is_ok_and(|meta| meta.id() == *id)
+                })
+            });
+
+            match moved {
+                Some(destination) => db.move_source(&path, &destination.clone()),
+                None => {
+                    dependents.extend(db.dependencies.dependents_recursive(&path));
+                    db.unload_source(&path);
+                }
+            }
+        }
+
+        for path in dependents {
+            if !imports.contains(&path) && db.io.exists(&path) {
+                imports.push(path);
+            }
+        }
+
+        let imports = match db.dependencies.sort(&imports) {
+            Ok(sorted) => sorted,
+            Err(cycle) => {
+                eprintln!("{}", cycle);
+                imports
+            }
+        };
+
+        for path in imports {
+            db.import_file(&path, &sorted_importers);
+        }
+    }
+
+    pub fn rebuild(resources: &ResourceManager, importers: &ImporterRepo) {
+        let changes = {
+            let mut db = resources.resource_mut::<AssetDatabase>();
+            if let Some(cache) = &mut db.cache {
+                if let Err(e) = cache.clean() {
+                    eprintln!("Failed to clean {}: {}", cache.dir().display(), e);
+                }
+            }
+
+            db.ids
+                .keys()
+                .map(|path| AssetChange::Modified(path.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        AssetDatabase::reload(&changes, resources, importers);
+    }
+
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
+    }
+
+    pub fn set_io(&mut self, io: Arc<dyn AssetIo>) {
+        self.io = io;
+    }
+
+    pub fn io(&self) -> &Arc<dyn AssetIo> {
+        &self.io
+    }
+
+    pub fn set_placeholder<T: Asset>(
+        &mut self,
+        placeholder: impl Fn() -> T + Send + Sync + 'static,
     ) {
-        let read_dir = std::fs::read_dir(base_path).expect("Failed to read path: {path}");
-
-        for entry in read_dir {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    let _ = AssetDatabase::load_inner(&path, db, resources, importers);
-                } else if path.is_file() {
-                    let ext = path.extension_str();
-                    if let Some(importers) = importers.get(ext) {
-                        for importer in importers {
-                            let id = path
-                                .trim_prefix(base_path.with_extension("").to_str().unwrap())
-                                .into();
-                            let info = AssetInfo {
-                                id,
-                                name: path.file_stem().unwrap().to_str().unwrap().to_string(),
-                                path: path.clone(),
-                            };
-                            let mut ctx = ImportContext::new(info, resources, db);
-                            importer.import(&mut ctx);
-                        }
+        let asset_type = TypeId::of::<T>().into();
+        self.placeholders.insert(
+            asset_type,
+            Arc::new(move |db: &mut AssetDatabase, id| db.insert(id, placeholder())),
+        );
+    }
+
+    pub fn is_placeholder<T: Asset>(&self, id: &AssetId) -> bool {
+        self.placeholder_ids
+            .contains(&(TypeId::of::<T>().into(), *id))
+    }
+
+    pub fn errors(&self) -> &[ImportError] {
+        &self.errors
+    }
+
+    pub fn take_errors(&mut self) -> Vec<ImportError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    pub fn set_cache(&mut self, cache: ArtifactCache) {
+        self.cache = Some(cache);
+    }
+
+    pub fn cache(&self) -> Option<&ArtifactCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn cache_mut(&mut self) -> Option<&mut ArtifactCache> {
+        self.cache.as_mut()
+    }
+
+    fn tracker(&self) -> &HandleTracker {
+        &self.handles
+    }
+
+    /// Creates an empty database that shares this database's handles, io and
+    /// known paths, for importing a file away from the main thread.
+    pub(super) fn staging(&self) -> AssetDatabase {
+        let mut staging = AssetDatabase::new();
+        staging.handles = self.handles.clone();
+        staging.root = self.root.clone();
+        staging.cache = self
+            .cache
+            .as_ref()
+            .map(|cache| ArtifactCache::new(cache.dir()));
+        staging.io = self.io.clone();
+        staging.failures = Some(Vec::new());
+        staging.ids = self.ids.clone();
+        staging.paths = self.paths.clone();
+        staging
+    }
+
+    pub(super) fn failures(&self) -> &[Failure] {
+        self.failures.as_deref().unwrap_or(&[])
+    }
+
+    /// Moves everything a staging database imported into this one, replacing
+    /// the previous assets of each imported source.
+    pub(super) fn merge(&mut self, mut staged: AssetDatabase) {
+        for (path, meta) in staged.metas.drain() {
+            self.index(&path, meta.id(), meta);
+        }
+
+        for (path, assets) in staged.sources.drain() {
+            self.dependencies.clear(&path);
+            for dependency in staged.dependencies.dependencies(&path) {
+                self.depend(&path, dependency);
+            }
+
+            self.errors.retain(|error| error.path != path);
+            for (asset_type, id) in &assets {
+                self.placeholder_ids.remove(&(*asset_type, *id));
+            }
+
+            let previous = self.sources.insert(path, assets.clone());
+            for (asset_type, id) in previous.unwrap_or_default() {
+                if !assets.contains(&(asset_type, id)) {
+                    self.remove_erased(asset_type, id);
+                }
+            }
+        }
+
+        for (asset_type, storage) in staged.storages.drain() {
+            match self.storages.get_mut(&asset_type) {
+                Some(existing) => existing.merge(storage),
+                None => {
+                    self.storages.insert(asset_type, storage);
+                }
+            }
+        }
+
+        for (asset_type, id, error) in staged.failures.take().unwrap_or_default() {
+            self.fail(asset_type, id, error);
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn id(&self, path: &Path) -> Option<AssetId> {
+        self.ids
+            .get(path)
+            .or_else(|| self.ids.get(&self.root.join(path)))
+            .copied()
+    }
+
+    pub fn path(&self, id: &AssetId) -> Option<&Path> {
+        self.paths.get(id).map(|path| path.as_path())
+    }
+
+    pub fn meta(&self, path: &Path) -> Option<&AssetMeta> {
+        self.metas
+            .get(path)
+            .or_else(|| self.metas.get(&self.root.join(path)))
+    }
+
+    pub fn get_by_path<T: Asset>(&self, path: &Path) -> Option<&T> {
+        self.id(path).and_then(|id| self.get(&id))
+    }
+
+    pub fn dependencies(&self, path: &Path) -> &[PathBuf] {
+        self.dependencies.dependencies(path)
+    }
+
+    pub fn dependents(&self, path: &Path) -> &[PathBuf] {
+        self.dependencies.dependents(path)
+    }
+
+    pub fn source_assets(&self, path: &Path) -> &[(AssetType, AssetId)] {
+        self.sources
+            .get(path)
+            .map(|assets| assets.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn has_settings(&self, path: &Path, settings: &ImportSettings) -> bool {
+        match self.meta(path) {
+            Some(meta) => meta.settings.matches(settings),
+            None => AssetMeta::load(self.io.as_ref(), &AssetMeta::path(path))
+                .is_ok_and(|meta| meta.settings.matches(settings)),
+        }
+    }
+
+    fn resolve_dependency(&self, source: &Path, path: &Path) -> PathBuf {
+        if path.is_absolute() || path.starts_with(&self.root) {
+            return path.normalize();
+        }
+
+        let sibling = source.parent().unwrap_or(Path::new("")).join(path);
+        if self.io.exists(&sibling) || !self.io.exists(&self.root.join(path)) {
+            sibling.normalize()
+        } else {
+            self.root.join(path).normalize()
+        }
+    }
+
+    fn depend(&mut self, dependent: &Path, dependency: &Path) -> bool {
+        match self.dependencies.add(dependent, dependency) {
+            Ok(_) => true,
+            Err(cycle) => {
+                eprintln!("{}", cycle);
+                false
+            }
+        }
+    }
+
+    pub(super) fn import_file(&mut self, path: &Path, importers: &SortedImporters) {
+        self.import_file_with(path, importers, &ImportSettings::new());
+    }
+
+    fn import_file_with(
+        &mut self,
+        path: &Path,
+        importers: &SortedImporters,
+        overrides: &ImportSettings,
+    ) {
+        let Some(file_importers) = importers.get(path.extension_str()) else {
+            return;
+        };
+
+        let meta = self.import_meta(path, file_importers[0].importer(), overrides);
+        let id = meta.id();
+        let settings = meta.settings.clone();
+        self.index(path, id, meta);
+
+        let previous = self.sources.remove(path).unwrap_or_default();
+        self.sources.insert(path.to_path_buf(), Vec::new());
+        self.dependencies.clear(path);
+        self.errors.retain(|error| error.path != path);
+        self.importing.push(path.to_path_buf());
+
+        let mut imported = Vec::new();
+        let mut failed = Vec::new();
+        for entry in file_importers {
+            let asset_type = entry.asset_type();
+            if imported.contains(&asset_type) {
+                continue;
+            }
+
+            let importer = entry.importer();
+            let info = AssetInfo {
+                id,
+                name: path.file_stem().unwrap().to_str().unwrap().to_string(),
+                path: path.to_path_buf(),
+                settings: settings.clone(),
+            };
+            let mut ctx = ImportContext::new(info, self, importers)
+                .with_importer(importer.name(), importer.version());
+
+            match importer.import(&mut ctx) {
+                Ok(()) => imported.push(asset_type),
+                Err(mut error) => {
+                    if error.path.as_os_str().is_empty() {
+                        error.path = path.to_path_buf();
                     }
+
+                    failed.push((asset_type, error));
                 }
             }
         }
+
+        self.importing.pop();
+
+        for (asset_type, error) in failed {
+            if !imported.contains(&asset_type) {
+                self.fail(asset_type, id, error);
+            }
+        }
+
+        let current = self.source_assets(path).to_vec();
+        for (asset_type, id) in previous {
+            if !current.contains(&(asset_type, id)) {
+                self.remove_erased(asset_type, id);
+            }
+        }
+    }
+
+    fn fail(&mut self, asset_type: AssetType, id: AssetId, error: ImportError) {
+        if let Some(failures) = &mut self.failures {
+            failures.push((asset_type, id, error));
+            return;
+        }
+
+        eprintln!("Failed to import {}", error);
+        self.errors.push(error);
+
+        if let Some(placeholder) = self.placeholders.get(&asset_type).cloned() {
+            placeholder(self, id);
+            self.placeholder_ids.insert((asset_type, id));
+        }
+    }
+
+    fn import_meta(
+        &self,
+        path: &Path,
+        importer: &dyn BaseImporter,
+        overrides: &ImportSettings,
+    ) -> AssetMeta {
+        let (mut meta, mut dirty) = AssetMeta::resolve(
+            self.io.as_ref(),
+            path,
+            importer.name(),
+            importer.version(),
+            &importer.settings(),
+        );
+        dirty |= meta.settings.apply(overrides);
+
+        let duplicate = self
+            .paths
+            .get(&meta.id())
+            .is_some_and(|other| other != path && self.io.exists(other));
+        if duplicate {
+            eprintln!(
+                "Duplicate asset id in {}, generating a new one",
+                AssetMeta::path(path).display()
+            );
+            meta.regenerate();
+            dirty = true;
+        }
+
+        if dirty {
+            meta.store(self.io.as_ref(), path);
+        }
+
+        meta
+    }
+
+    fn index(&mut self, path: &Path, id: AssetId, meta: AssetMeta) {
+        if let Some(old) = self.ids.insert(path.to_path_buf(), id) {
+            if old != id {
+                self.paths.remove(&old);
+            }
+        }
+
+        self.paths.insert(id, path.to_path_buf());
+        self.metas.insert(path.to_path_buf(), meta);
+    }
+
+    fn move_source(&mut self, from: &Path, to: &Path) {
+        self.dependencies.rename(from, to);
+
+        if let Some(assets) = self.sources.remove(from) {
+            self.sources.insert(to.to_path_buf(), assets);
+        }
+
+        if let Some(meta) = self.metas.remove(from) {
+            self.metas.insert(to.to_path_buf(), meta);
+        }
+
+        if let Some(id) = self.ids.remove(from) {
+            self.ids.insert(to.to_path_buf(), id);
+            self.paths.insert(id, to.to_path_buf());
+        }
+    }
+
+    fn unload_source(&mut self, path: &Path) {
+        self.dependencies.clear(path);
+        self.errors.retain(|error| error.path != path);
+
+        for (asset_type, id) in self.sources.remove(path).unwrap_or_default() {
+            self.remove_erased(asset_type, id);
+        }
+
+        self.metas.remove(path);
+        if let Some(id) = self.ids.remove(path) {
+            self.paths.remove(&id);
+        }
+    }
+
+    fn remove_erased(&mut self, asset_type: AssetType, id: AssetId) {
+        self.handles.forget(asset_type, id);
+        self.placeholder_ids.remove(&(asset_type, id));
+        if let Some(storage) = self.storages.get_mut(&asset_type) {
+            storage.remove_asset(&id);
+        }
     }
 }
 
@@ -188,42 +956,357 @@ impl Resource for AssetDatabase {
     }
 }
 
-pub trait BaseImporter: 'static {
-    fn import(&self, context: &mut ImportContext);
+pub fn update_assets(world: &World) {
+    world.resource_mut::<AssetDatabase>().update();
+}
+
+pub trait BaseImporter: Send + Sync + 'static {
+    fn import(&self, context: &mut ImportContext) -> Result<(), ImportError>;
     fn extensions(&self) -> &'static [&'static str] {
         &[]
     }
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+    fn version(&self) -> u32 {
+        0
+    }
+    fn settings(&self) -> ImportSettings {
+        ImportSettings::new()
+    }
 }
 
 pub trait AssetImporter<T: Asset>: BaseImporter {}
 
+#[derive(Clone)]
+pub struct ImporterEntry {
+    asset_type: AssetType,
+    priority: i32,
+    importer: Arc<dyn BaseImporter>,
+}
+
+impl ImporterEntry {
+    pub fn asset_type(&self) -> AssetType {
+        self.asset_type
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn importer(&self) -> &dyn BaseImporter {
+        self.importer.as_ref()
+    }
+}
+
+#[derive(Clone)]
 pub struct ImporterRepo {
-    importers: HashMap<AssetType, Box<dyn BaseImporter>>,
+    importers: Vec<ImporterEntry>,
 }
 
 impl ImporterRepo {
     pub fn new() -> Self {
         Self {
-            importers: HashMap::new(),
+            importers: Vec::new(),
         }
     }
 
     pub fn add_importer<T: Asset, U: AssetImporter<T>>(&mut self, importer: U) {
-        let asset_type = TypeId::of::<T>().into();
-        self.importers.insert(asset_type, Box::new(importer));
+        self.add_importer_with_priority::<T, U>(importer, 0);
+    }
+
+    pub fn add_importer_with_priority<T: Asset, U: AssetImporter<T>>(
+        &mut self,
+        importer: U,
+        priority: i32,
+    ) {
+        self.importers.push(ImporterEntry {
+            asset_type: TypeId::of::<T>().into(),
+            priority,
+            importer: Arc::new(importer),
+        });
     }
 
-    pub fn sort(&self) -> HashMap<&str, Vec<&Box<dyn BaseImporter>>> {
-        let mut sorted_importers: HashMap<&str, Vec<&Box<dyn BaseImporter>>> = HashMap::new();
-        for (_, importer) in &self.importers {
-            for extension in importer.extensions() {
-                sorted_importers
-                    .entry(extension)
-                    .or_insert_with(Vec::new)
-                    .push(importer);
+    pub fn sort(&self) -> SortedImporters<'_> {
+        let mut sorted_importers: SortedImporters = HashMap::new();
+        for entry in &self.importers {
+            for extension in entry.importer.extensions() {
+                sorted_importers.entry(extension).or_default().push(entry);
             }
         }
 
+        for importers in sorted_importers.values_mut() {
+            importers.sort_by_key(|entry| std::cmp::Reverse(entry.priority));
+        }
+
         sorted_importers
     }
 }
+
+impl Resource for ImporterRepo {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    struct Text(String);
+
+    impl Asset for Text {}
+
+    struct TextImporter {
+        decoded: Arc<AtomicUsize>,
+        cached: bool,
+    }
+
+    impl BaseImporter for TextImporter {
+        fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+            let artifact = match self.cached {
+                true => ctx.read_artifact(),
+                false => None,
+            };
+
+            let text = match artifact {
+                Some(bytes) => String::from_utf8(bytes).unwrap(),
+                None => {
+                    self.decoded.fetch_add(1, Ordering::SeqCst);
+                    let text = String::from_utf8(ctx.read()?).unwrap();
+                    let text = match ctx.settings().bool("lower") {
+                        Some(true) => text.to_lowercase(),
+                        _ => text.to_uppercase(),
+                    };
+                    if self.cached {
+                        ctx.write_artifact(text.as_bytes());
+                    }
+                    text
+                }
+            };
+
+            let id = ctx.info().id;
+            ctx.assets().insert(id, Text(text));
+            Ok(())
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            match self.cached {
+                true => &["txt"],
+                false => &["raw"],
+            }
+        }
+    }
+
+    impl AssetImporter<Text> for TextImporter {}
+
+    struct LinkImporter;
+
+    impl BaseImporter for LinkImporter {
+        fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+            let target = String::from_utf8(ctx.read()?).unwrap();
+            let (target, settings) = match target.trim().split_once(' ') {
+                Some((target, key)) => (target, ImportSettings::new().with(key, true)),
+                None => (target.trim(), ImportSettings::new()),
+            };
+            let id = ctx
+                .load_dependency_with(target, &settings)
+                .ok_or_else(|| ctx.error(format!("Missing {}", target)))?;
+            let text = ctx.assets().get::<Text>(&id).unwrap().0.clone();
+
+            let id = ctx.info().id;
+            ctx.assets().insert(id, Text(format!("-> {}", text)));
+            Ok(())
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            &["link"]
+        }
+    }
+
+    impl AssetImporter<Text> for LinkImporter {}
+
+    struct CountingIo {
+        inner: MemoryIo,
+        reads: Mutex<HashMap<PathBuf, usize>>,
+    }
+
+    impl CountingIo {
+        fn reads(&self, path: &str) -> usize {
+            let reads = self.reads.lock().unwrap();
+            reads.get(Path::new(path)).copied().unwrap_or_default()
+        }
+    }
+
+    impl AssetIo for CountingIo {
+        fn list(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+            self.inner.list(path)
+        }
+
+        fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+            *self
+                .reads
+                .lock()
+                .unwrap()
+                .entry(path.to_path_buf())
+                .or_default() += 1;
+            self.inner.read(path)
+        }
+
+        fn metadata(&self, path: &Path) -> std::io::Result<AssetMetadata> {
+            self.inner.metadata(path)
+        }
+
+        fn write(&self, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+            self.inner.write(path, bytes)
+        }
+    }
+
+    fn importers(decoded: &Arc<AtomicUsize>) -> ImporterRepo {
+        let mut importers = ImporterRepo::new();
+        for cached in [true, false] {
+            importers.add_importer::<Text, _>(TextImporter {
+                decoded: decoded.clone(),
+                cached,
+            });
+        }
+
+        importers
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("frost-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn database(io: Arc<dyn AssetIo>, cache: &Path) -> AssetDatabase {
+        let mut db = AssetDatabase::new();
+        db.set_root("assets");
+        db.set_io(io);
+        db.set_cache(ArtifactCache::new(cache));
+        db
+    }
+
+    fn text(db: &AssetDatabase, path: &str) -> Option<String> {
+        let id = db.id(Path::new(path))?;
+        db.get::<Text>(&id).map(|text| text.0.clone())
+    }
+
+    #[test]
+    fn artifacts_skip_decoding_on_the_next_launch() {
+        let dir = cache_dir("artifacts");
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let importers = importers(&decoded);
+        let io = Arc::new(MemoryIo::new().with_file("assets/a.txt", "hello"));
+
+        for launch in 0..2 {
+            let mut db = database(io.clone(), &dir);
+            db.import_file(Path::new("assets/a.txt"), &importers.sort());
+            assert_eq!(text(&db, "assets/a.txt").as_deref(), Some("HELLO"));
+            assert_eq!(db.cache().unwrap().hits(), launch);
+        }
+        assert_eq!(decoded.load(Ordering::SeqCst), 1);
+
+        io.insert("assets/a.txt", "changed");
+        let mut db = database(io.clone(), &dir);
+        db.import_file(Path::new("assets/a.txt"), &importers.sort());
+        assert_eq!(text(&db, "assets/a.txt").as_deref(), Some("CHANGED"));
+        assert_eq!(decoded.load(Ordering::SeqCst), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn importers_without_artifacts_read_the_source_once() {
+        let dir = cache_dir("uncached");
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let importers = importers(&decoded);
+        let io = Arc::new(CountingIo {
+            inner: MemoryIo::new().with_file("assets/b.raw", "plain"),
+            reads: Mutex::new(HashMap::new()),
+        });
+
+        let mut db = database(io.clone(), &dir);
+        db.import_file(Path::new("assets/b.raw"), &importers.sort());
+
+        assert_eq!(text(&db, "assets/b.raw").as_deref(), Some("PLAIN"));
+        assert_eq!(io.reads("assets/b.raw"), 1);
+        assert_eq!(db.cache().unwrap().misses(), 0);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn load_imports_everything_from_memory() {
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let mut importers = importers(&decoded);
+        importers.add_importer::<Text, _>(LinkImporter);
+
+        let io = Arc::new(
+            MemoryIo::new()
+                .with_file("assets/a.link", "text/b.txt")
+                .with_file("assets/broken.link", "missing.txt")
+                .with_file("assets/text/b.txt", "bee")
+                .with_file("assets/c.raw", "sea"),
+        );
+        let mut resources = ResourceManager::new();
+        let mut db = AssetDatabase::new();
+        db.set_io(io.clone());
+        resources.register(db);
+
+        AssetDatabase::load(Path::new("assets"), &resources, &importers);
+
+        let db = resources.resource::<AssetDatabase>();
+        assert_eq!(text(&db, "assets/a.link").as_deref(), Some("-> BEE"));
+        assert_eq!(text(&db, "assets/text/b.txt").as_deref(), Some("BEE"));
+        assert_eq!(text(&db, "assets/c.raw").as_deref(), Some("SEA"));
+        assert_eq!(text(&db, "assets/broken.link"), None);
+        assert_eq!(decoded.load(Ordering::SeqCst), 2);
+
+        let errors = db.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, Path::new("assets/broken.link"));
+
+        for path in ["assets/a.link", "assets/text/b.txt", "assets/c.raw"] {
+            let meta = AssetMeta::path(Path::new(path));
+            assert!(io.exists(&meta), "{}", meta.display());
+        }
+    }
+
+    #[test]
+    fn dependencies_are_reimported_with_required_settings() {
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let mut importers = importers(&decoded);
+        importers.add_importer::<Text, _>(LinkImporter);
+        let io = Arc::new(
+            MemoryIo::new()
+                .with_file("assets/b.raw", "Bee")
+                .with_file("assets/a.link", "b.raw lower"),
+        );
+
+        let mut db = AssetDatabase::new();
+        db.set_root("assets");
+        db.set_io(io.clone());
+        db.import_file(Path::new("assets/b.raw"), &importers.sort());
+        assert_eq!(text(&db, "assets/b.raw").as_deref(), Some("BEE"));
+
+        db.import_file(Path::new("assets/a.link"), &importers.sort());
+        assert_eq!(text(&db, "assets/a.link").as_deref(), Some("-> bee"));
+        assert_eq!(text(&db, "assets/b.raw").as_deref(), Some("bee"));
+        assert_eq!(decoded.load(Ordering::SeqCst), 2);
+
+        db.import_file(Path::new("assets/a.link"), &importers.sort());
+        assert_eq!(decoded.load(Ordering::SeqCst), 2);
+
+        let meta = AssetMeta::load(io.as_ref(), Path::new("assets/b.raw.meta")).unwrap();
+        assert_eq!(meta.settings.bool("lower"), Some(true));
+    }
+}
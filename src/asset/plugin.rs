@@ -0,0 +1,224 @@
+use super::{
+    finish_loads, reload_assets, update_assets, ArchiveIo, ArtifactCache, AssetDatabase, AssetIo,
+    AssetServer, AssetWatcher, ImporterRepo,
+};
+use crate::{
+    console::{Arg, Command, Console, ConsoleError},
+    game::{GameBuilder, Plugin},
+    schedule::ScenePhase,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+#[derive(Default)]
+pub struct AssetPlugin {
+    path: Option<PathBuf>,
+    watch: Option<Duration>,
+    io: Option<Arc<dyn AssetIo>>,
+}
+
+impl AssetPlugin {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            watch: None,
+            io: None,
+        }
+    }
+
+    pub fn with_watch(mut self, interval: Duration) -> Self {
+        self.watch = Some(interval);
+        self
+    }
+
+    pub fn with_io(mut self, io: impl AssetIo) -> Self {
+        self.io = Some(Arc::new(io));
+        self
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+}
+
+impl Plugin for AssetPlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        game.add_resource(AssetDatabase::new());
+    }
+
+    fn finish(&self, game: &mut GameBuilder) {
+        let placeholders = game.take_placeholders();
+        {
+            let mut db = game.resources().resource_mut::<AssetDatabase>();
+            for placeholder in placeholders {
+                placeholder(&mut db);
+            }
+        }
+
+        let config = &game.game_config().assets;
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => config.root.clone(),
+        };
+
+        let watch = match self.watch {
+            Some(interval) => Some(interval),
+            None if config.watch => Some(Duration::from_secs_f64(config.watch_interval)),
+            None => None,
+        };
+
+        let preload = config.preload;
+        let threads = config.loader_threads;
+
+        let io = match (&self.io, &config.archive) {
+            (Some(io), _) => Some(io.clone()),
+            (None, Some(archive)) => match ArchiveIo::open(archive, path.clone()) {
+                Ok(archive) => Some(Arc::new(archive) as Arc<dyn AssetIo>),
+                Err(e) => {
+                    eprintln!("Failed to open asset archive {}: {}", archive.display(), e);
+                    None
+                }
+            },
+            (None, None) => None,
+        };
+
+        {
+            let mut db = game.resources().resource_mut::<AssetDatabase>();
+            db.set_root(path.clone());
+            if let Some(io) = io {
+                db.set_io(io);
+            }
+        }
+
+        if config.cache {
+            let mut cache = ArtifactCache::new(config.cache_dir.clone());
+            if config.rebuild {
+                if let Err(e) = cache.clean() {
+                    eprintln!("Failed to clean {}: {}", cache.dir().display(), e);
+                }
+            }
+
+            let resources = game.resources();
+            resources.resource_mut::<AssetDatabase>().set_cache(cache);
+            if let Some(mut console) = resources.try_resource_mut::<Console>() {
+                console.register(cache_command());
+            }
+        }
+
+        if let Some(mut console) = game.resources().try_resource_mut::<Console>() {
+            console.register(pack_command());
+            console.register(errors_command());
+        }
+
+        if preload {
+            AssetDatabase::load(&path, game.resources(), game.importers());
+        }
+
+        let (io, tracker) = {
+            let db = game.resources().resource::<AssetDatabase>();
+            (db.io().clone(), db.tracker().clone())
+        };
+        let importers = game.importers().clone();
+        game.add_resource(AssetServer::new(
+            path.clone(),
+            io.clone(),
+            importers,
+            tracker,
+            threads,
+        ));
+        game.add_system(ScenePhase::PreUpdate, finish_loads);
+
+        if let Some(interval) = watch {
+            game.add_resource(AssetWatcher::new(io, path, interval));
+            game.add_system(ScenePhase::PreUpdate, reload_assets);
+        }
+
+        game.add_system(ScenePhase::PreUpdate, update_assets);
+    }
+}
+
+fn cache_command() -> Command {
+    Command::new(
+        "asset-cache",
+        "Cleans or rebuilds the asset artifact cache",
+        |args, world| {
+            let action = args.get::<String>("action").unwrap_or_default();
+            match action.as_str() {
+                "clean" => {
+                    let removed = world
+                        .resource_mut::<AssetDatabase>()
+                        .cache_mut()
+                        .map(|cache| cache.clean())
+                        .transpose()
+                        .map_err(|e| ConsoleError::Failed(e.to_string()))?
+                        .unwrap_or(0);
+                    world
+                        .resource_mut::<Console>()
+                        .print(format!("Removed {} artifacts", removed));
+                }
+                "rebuild" => {
+                    let importers = world.resource::<ImporterRepo>();
+                    AssetDatabase::rebuild(world.resources(), &importers);
+                }
+                _ => {
+                    let db = world.resource::<AssetDatabase>();
+                    if let Some(cache) = db.cache() {
+                        world.resource_mut::<Console>().print(format!(
+                            "{}: {} hits, {} misses",
+                            cache.dir().display(),
+                            cache.hits(),
+                            cache.misses()
+                        ));
+                    }
+                }
+            }
+
+            Ok(())
+        },
+    )
+    .with_arg(Arg::text("action").with_choices(&["clean", "rebuild", "stats"]))
+}
+
+fn pack_command() -> Command {
+    Command::new(
+        "asset-pack",
+        "Packs an asset folder into an archive",
+        |args, world| {
+            let source = args.get::<String>("source").unwrap_or_default();
+            let output = args.get::<String>("output").unwrap_or_default();
+            let compress = args.get::<bool>("compress").unwrap_or(true);
+
+            let stats = ArchiveIo::pack(Path::new(&source), Path::new(&output), compress)
+                .map_err(|e| ConsoleError::Failed(format!("Failed to pack {}: {}", source, e)))?;
+
+            world.resource_mut::<Console>().print(format!(
+                "Packed {} files into {} ({} of {} bytes)",
+                stats.files, output, stats.stored, stats.bytes
+            ));
+
+            Ok(())
+        },
+    )
+    .with_arg(Arg::text("source"))
+    .with_arg(Arg::text("output"))
+    .with_arg(Arg::bool("compress").optional())
+}
+
+fn errors_command() -> Command {
+    Command::new("asset-errors", "Lists failed asset imports", |_, world| {
+        let db = world.resource::<AssetDatabase>();
+        let mut console = world.resource_mut::<Console>();
+        if db.errors().is_empty() {
+            console.print("No import errors");
+        }
+
+        for error in db.errors() {
+            console.print(error.to_string());
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,359 @@
+use super::{
+    Asset, AssetDatabase, AssetId, AssetIo, AssetMeta, AssetType, Handle, HandleTracker,
+    ImporterRepo,
+};
+use crate::{
+    ecs::{Resource, World},
+    shared::ext::path::PathExt,
+};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadState {
+    NotLoaded,
+    Loading,
+    Loaded,
+    Failed(String),
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct ThreadPool {
+    jobs: Option<Sender<Job>>,
+}
+
+impl ThreadPool {
+    fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for index in 0..threads.max(1) {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("asset-loader-{}", index))
+                .spawn(move || loop {
+                    let job = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => break,
+                    };
+
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Failed to spawn asset loader thread");
+        }
+
+        Self { jobs: Some(sender) }
+    }
+
+    fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(Box::new(job));
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.jobs.take();
+    }
+}
+
+struct LoadRequest {
+    id: AssetId,
+    asset_type: AssetType,
+    path: PathBuf,
+}
+
+struct LoadResult {
+    id: AssetId,
+    asset_type: AssetType,
+    output: Result<AssetDatabase, String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LoadGroup {
+    ids: Vec<AssetId>,
+}
+
+impl LoadGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: Asset>(mut self, handle: &Handle<T>) -> Self {
+        self.add(handle);
+        self
+    }
+
+    pub fn add<T: Asset>(&mut self, handle: &Handle<T>) {
+        if !self.ids.contains(&handle.id()) {
+            self.ids.push(handle.id());
+        }
+    }
+
+    pub fn ids(&self) -> &[AssetId] {
+        &self.ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+pub struct AssetServer {
+    root: PathBuf,
+    io: Arc<dyn AssetIo>,
+    importers: ImporterRepo,
+    tracker: HandleTracker,
+    pool: ThreadPool,
+    states: HashMap<AssetId, LoadState>,
+    requests: Vec<LoadRequest>,
+    sender: Sender<LoadResult>,
+    receiver: Receiver<LoadResult>,
+}
+
+impl AssetServer {
+    pub(super) fn new(
+        root: impl Into<PathBuf>,
+        io: Arc<dyn AssetIo>,
+        importers: ImporterRepo,
+        tracker: HandleTracker,
+        threads: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            root: root.into(),
+            io,
+            importers,
+            tracker,
+            pool: ThreadPool::new(threads),
+            states: HashMap::new(),
+            requests: Vec::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn load<T: Asset>(&mut self, path: impl AsRef<Path>) -> Handle<T> {
+        let path = self.resolve(path.as_ref());
+        let asset_type = TypeId::of::<T>().into();
+
+        let sorted = self.importers.sort();
+        let importer = match sorted.get(path.extension_str()) {
+            Some(_) if !self.io.exists(&path) => Err(format!("{} does not exist", path.display())),
+            Some(entries) if entries.iter().any(|e| e.asset_type() == asset_type) => {
+                Ok(entries[0].importer())
+            }
+            _ => Err(format!(
+                "No importer for {} can load {}",
+                std::any::type_name::<T>(),
+                path.display()
+            )),
+        };
+
+        let importer = match importer {
+            Ok(importer) => importer,
+            Err(e) => {
+                eprintln!("{}", e);
+                let id = AssetId::from(path.as_path());
+                self.states.insert(id, LoadState::Failed(e));
+                return Handle::untracked(id);
+            }
+        };
+
+        let (meta, dirty) = AssetMeta::resolve(
+            self.io.as_ref(),
+            &path,
+            importer.name(),
+            importer.version(),
+            &importer.settings(),
+        );
+        if dirty {
+            meta.store(self.io.as_ref(), &path);
+        }
+
+        let id = meta.id();
+        let alive = self.tracker.strong_count(asset_type, id) > 0;
+        let handle = self.tracker.handle::<T>(id);
+
+        match self.states.get(&id) {
+            Some(LoadState::Loading) | Some(LoadState::Loaded) if alive => return handle,
+            _ => {}
+        }
+
+        self.states.insert(id, LoadState::Loading);
+        self.requests.push(LoadRequest {
+            id,
+            asset_type,
+            path,
+        });
+
+        handle
+    }
+
+    pub fn load_state<T: Asset>(&self, handle: &Handle<T>) -> LoadState {
+        self.state(&handle.id())
+    }
+
+    pub fn state(&self, id: &AssetId) -> LoadState {
+        self.states.get(id).cloned().unwrap_or(LoadState::NotLoaded)
+    }
+
+    pub fn group_state(&self, group: &LoadGroup) -> LoadState {
+        let mut state = LoadState::Loaded;
+        for id in group.ids() {
+            match self.state(id) {
+                LoadState::Failed(e) => return LoadState::Failed(e),
+                LoadState::Loading => state = LoadState::Loading,
+                LoadState::NotLoaded if state == LoadState::Loaded => state = LoadState::NotLoaded,
+                _ => {}
+            }
+        }
+
+        state
+    }
+
+    pub fn progress(&self, group: &LoadGroup) -> f32 {
+        if group.is_empty() {
+            return 1.0;
+        }
+
+        let loaded = group
+            .ids()
+            .iter()
+            .filter(|id| self.state(id) == LoadState::Loaded)
+            .count();
+
+        loaded as f32 / group.len() as f32
+    }
+
+    /// Imports each requested file into its own staging database on the
+    /// thread pool. Panicking importers are reported as failed loads.
+    fn dispatch(&mut self, db: &AssetDatabase) {
+        for request in std::mem::take(&mut self.requests) {
+            let mut staging = db.staging();
+            let importers = self.importers.clone();
+            let sender = self.sender.clone();
+            self.pool.execute(move || {
+                let output = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    staging.import_file(&request.path, &importers.sort());
+                    staging
+                }))
+                .map_err(|panic| {
+                    let message = panic
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    format!(
+                        "Importer panicked while loading {}: {}",
+                        request.path.display(),
+                        message
+                    )
+                });
+
+                let _ = sender.send(LoadResult {
+                    id: request.id,
+                    asset_type: request.asset_type,
+                    output,
+                });
+            });
+        }
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() || path.starts_with(&self.root) {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
+}
+
+impl Resource for AssetServer {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn finish_loads(world: &World) {
+    {
+        let db = world.resource::<AssetDatabase>();
+        world.resource_mut::<AssetServer>().dispatch(&db);
+    }
+
+    let results = world
+        .resource::<AssetServer>()
+        .receiver
+        .try_iter()
+        .collect::<Vec<_>>();
+
+    if results.is_empty() {
+        return;
+    }
+
+    let mut db = world.resource_mut::<AssetDatabase>();
+    for result in results {
+        let state = match result.output {
+            Ok(staged) => {
+                let failure = staged
+                    .failures()
+                    .iter()
+                    .find(|(asset_type, id, _)| {
+                        *asset_type == result.asset_type && *id == result.id
+                    })
+                    .map(|(_, _, error)| error.to_string());
+                db.merge(staged);
+
+                match failure {
+                    Some(e) => LoadState::Failed(e),
+                    None => LoadState::Loaded,
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                LoadState::Failed(e)
+            }
+        };
+
+        world
+            .resource_mut::<AssetServer>()
+            .states
+            .insert(result.id, state);
+    }
+}
+
+pub fn wait_for(world: &World, group: &LoadGroup) -> LoadState {
+    loop {
+        finish_loads(world);
+
+        let state = world.resource::<AssetServer>().group_state(group);
+        if state != LoadState::Loading {
+            return state;
+        }
+
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
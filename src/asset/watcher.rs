@@ -0,0 +1,103 @@
+use super::{AssetDatabase, AssetIo, ImporterRepo};
+use crate::ecs::{Resource, World};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetChange {
+    Added(PathBuf),
+    Modified(PathBuf),
+    Removed(PathBuf),
+}
+
+impl AssetChange {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Added(path) | Self::Modified(path) | Self::Removed(path) => path,
+        }
+    }
+}
+
+pub struct AssetWatcher {
+    io: Arc<dyn AssetIo>,
+    root: PathBuf,
+    interval: Duration,
+    last_poll: Instant,
+    files: HashMap<PathBuf, SystemTime>,
+}
+
+impl AssetWatcher {
+    pub fn new(io: Arc<dyn AssetIo>, root: impl Into<PathBuf>, interval: Duration) -> Self {
+        let root = root.into();
+        let files = io.watch(&root).unwrap_or_default();
+
+        Self {
+            io,
+            root,
+            interval,
+            last_poll: Instant::now(),
+            files,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn poll(&mut self) -> Vec<AssetChange> {
+        if self.last_poll.elapsed() < self.interval {
+            return Vec::new();
+        }
+
+        self.last_poll = Instant::now();
+        self.rescan()
+    }
+
+    pub fn rescan(&mut self) -> Vec<AssetChange> {
+        let files = self.io.watch(&self.root).unwrap_or_default();
+        let mut changes = Vec::new();
+
+        for (path, modified) in &files {
+            match self.files.get(path) {
+                None => changes.push(AssetChange::Added(path.clone())),
+                Some(previous) if previous != modified => {
+                    changes.push(AssetChange::Modified(path.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        for path in self.files.keys() {
+            if !files.contains_key(path) {
+                changes.push(AssetChange::Removed(path.clone()));
+            }
+        }
+
+        self.files = files;
+        changes
+    }
+}
+
+impl Resource for AssetWatcher {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn reload_assets(world: &World) {
+    let changes = world.resource_mut::<AssetWatcher>().poll();
+    if changes.is_empty() {
+        return;
+    }
+
+    let importers = world.resource::<ImporterRepo>();
+    AssetDatabase::reload(&changes, world.resources(), &importers);
+}
@@ -0,0 +1,178 @@
+use super::{Arg, CVar, Command, Completion, Console, ConsoleError, ConsoleType, ConsoleValue};
+use crate::{game::GameTime, scene::SceneManager};
+use std::path::Path;
+
+pub fn builtins(console: Console) -> Console {
+    console
+        .with_command(
+            Command::new("help", "Lists commands or describes one", |args, world| {
+                let mut console = world.resource_mut::<Console>();
+                match args.get::<String>("name") {
+                    Some(name) => match console.help(&name) {
+                        Some(help) => console.print(help),
+                        None => return Err(ConsoleError::UnknownCommand(name)),
+                    },
+                    None => {
+                        let mut lines = console
+                            .commands()
+                            .map(|command| {
+                                format!("{} - {}", command.usage(), command.description())
+                            })
+                            .collect::<Vec<_>>();
+                        lines.sort();
+                        for line in lines {
+                            console.print(line);
+                        }
+                    }
+                }
+
+                Ok(())
+            })
+            .with_arg(
+                Arg::text("name")
+                    .optional()
+                    .with_completion(Completion::Commands),
+            ),
+        )
+        .with_command(Command::new(
+            "cvars",
+            "Lists console variables",
+            |_, world| {
+                let mut console = world.resource_mut::<Console>();
+                let mut names = console
+                    .cvars()
+                    .map(|cvar| cvar.name().to_string())
+                    .collect::<Vec<_>>();
+                names.sort();
+                for name in names {
+                    let help = console.help(&name).unwrap_or_default();
+                    console.print(help);
+                }
+
+                Ok(())
+            },
+        ))
+        .with_command(
+            Command::new("set", "Sets a console variable", |args, world| {
+                let name = args.get::<String>("name").unwrap_or_default();
+                let value = args.get::<String>("value").unwrap_or_default();
+                world.resource_mut::<Console>().assign(&name, &value)
+            })
+            .with_arg(Arg::text("name").with_completion(Completion::CVars))
+            .with_arg(Arg::rest("value")),
+        )
+        .with_command(
+            Command::new("get", "Prints a console variable", |args, world| {
+                let name = args.get::<String>("name").unwrap_or_default();
+                let mut console = world.resource_mut::<Console>();
+                match console.cvar(&name).map(|cvar| cvar.value().to_string()) {
+                    Some(value) => console.print(value),
+                    None => return Err(ConsoleError::UnknownCommand(name)),
+                }
+
+                Ok(())
+            })
+            .with_arg(Arg::text("name").with_completion(Completion::CVars)),
+        )
+        .with_command(
+            Command::new(
+                "reset",
+                "Restores a console variable to its default",
+                |args, world| {
+                    let name = args.get::<String>("name").unwrap_or_default();
+                    world.resource_mut::<Console>().reset(&name)
+                },
+            )
+            .with_arg(Arg::text("name").with_completion(Completion::CVars)),
+        )
+        .with_command(
+            Command::new("echo", "Prints text to the console", |args, world| {
+                let text = args.get::<String>("text").unwrap_or_default();
+                world.resource_mut::<Console>().print(text);
+                Ok(())
+            })
+            .with_arg(Arg::rest("text").optional()),
+        )
+        .with_command(
+            Command::new("exec", "Runs a console script", |args, world| {
+                let path = args.get::<String>("path").unwrap_or_default();
+                world
+                    .resource_mut::<Console>()
+                    .exec_script(Path::new(&path))
+                    .map_err(|e| ConsoleError::Failed(format!("Failed to read {}: {}", path, e)))
+            })
+            .with_arg(Arg::text("path")),
+        )
+        .with_command(Command::new(
+            "history",
+            "Lists previously entered commands",
+            |_, world| {
+                let mut console = world.resource_mut::<Console>();
+                let history = console.history().to_vec();
+                for (index, line) in history.iter().enumerate() {
+                    console.print(format!("{:>3} {}", index, line));
+                }
+
+                Ok(())
+            },
+        ))
+        .with_command(Command::new(
+            "clear",
+            "Clears console output",
+            |_, world| {
+                world.resource_mut::<Console>().clear_output();
+                Ok(())
+            },
+        ))
+        .with_command(Command::new("quit", "Exits the game", |_, world| {
+            world.resource_mut::<SceneManager>().quit();
+            Ok(())
+        }))
+        .with_command(
+            Command::new("scene", "Lists, inspects or loads scenes", |args, world| {
+                let action = args.get::<String>("action").unwrap_or_default();
+                let mut scenes = world.resource_mut::<SceneManager>();
+                let mut console = world.resource_mut::<Console>();
+                match action.as_str() {
+                    "list" => {
+                        let mut names = scenes.names();
+                        names.sort();
+                        for name in names {
+                            console.print(name);
+                        }
+                    }
+                    "current" => console.print(scenes.current_scene().name().to_string()),
+                    _ => {
+                        let name = args.get::<String>("name").ok_or_else(|| {
+                            ConsoleError::MissingArgument {
+                                command: "scene".to_string(),
+                                arg: "name".to_string(),
+                            }
+                        })?;
+                        let id = scenes.find(&name).ok_or_else(|| {
+                            ConsoleError::Failed(format!("Unknown scene: {}", name))
+                        })?;
+                        scenes.set_next(id);
+                    }
+                }
+
+                Ok(())
+            })
+            .with_arg(Arg::text("action").with_choices(&["list", "current", "load"]))
+            .with_arg(Arg::text("name").optional()),
+        )
+        .with_cvar(
+            CVar::new(
+                "timescale",
+                "Multiplier applied to frame time",
+                ConsoleValue::Float(1.0),
+            )
+            .on_change(|value, world| {
+                if let (Some(time), Some(scale)) =
+                    (world.try_resource::<GameTime>(), f64::from_value(value))
+                {
+                    time.set_scale(scale.max(0.0));
+                }
+            }),
+        )
+}
@@ -0,0 +1,272 @@
+use super::{ConsoleType, ConsoleValue, ValueKind};
+use crate::ecs::World;
+use std::{collections::HashMap, fmt::Display, rc::Rc};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleError {
+    UnknownCommand(String),
+    MissingArgument {
+        command: String,
+        arg: String,
+    },
+    InvalidArgument {
+        arg: String,
+        value: String,
+        expected: String,
+    },
+    TooManyArguments {
+        command: String,
+        usage: String,
+    },
+    UnterminatedQuote,
+    Failed(String),
+}
+
+impl Display for ConsoleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCommand(name) => write!(f, "Unknown command: {}", name),
+            Self::MissingArgument { command, arg } => {
+                write!(f, "Missing argument '{}' for {}", arg, command)
+            }
+            Self::InvalidArgument {
+                arg,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid value '{}' for {}, expected {}",
+                value, arg, expected
+            ),
+            Self::TooManyArguments { command, usage } => {
+                write!(f, "Too many arguments for {}, usage: {}", command, usage)
+            }
+            Self::UnterminatedQuote => write!(f, "Unterminated quote"),
+            Self::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Completion {
+    None,
+    Choices(Vec<String>),
+    Commands,
+    CVars,
+}
+
+#[derive(Clone, Debug)]
+pub struct Arg {
+    name: String,
+    kind: ValueKind,
+    optional: bool,
+    rest: bool,
+    completion: Completion,
+}
+
+impl Arg {
+    pub fn new(name: &str, kind: ValueKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            optional: false,
+            rest: false,
+            completion: Completion::None,
+        }
+    }
+
+    pub fn bool(name: &str) -> Self {
+        Self::new(name, ValueKind::Bool)
+    }
+
+    pub fn int(name: &str) -> Self {
+        Self::new(name, ValueKind::Int)
+    }
+
+    pub fn float(name: &str) -> Self {
+        Self::new(name, ValueKind::Float)
+    }
+
+    pub fn text(name: &str) -> Self {
+        Self::new(name, ValueKind::Text)
+    }
+
+    pub fn rest(name: &str) -> Self {
+        let mut arg = Self::new(name, ValueKind::Text);
+        arg.rest = true;
+        arg
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn with_choices(mut self, choices: &[&str]) -> Self {
+        self.completion =
+            Completion::Choices(choices.iter().map(|choice| choice.to_string()).collect());
+        self
+    }
+
+    pub fn with_completion(mut self, completion: Completion) -> Self {
+        self.completion = completion;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> ValueKind {
+        self.kind
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    pub fn completion(&self) -> &Completion {
+        &self.completion
+    }
+
+    fn usage(&self) -> String {
+        let dots = if self.rest { "..." } else { "" };
+        if self.optional {
+            format!("[{}:{}{}]", self.name, self.kind, dots)
+        } else {
+            format!("<{}:{}{}>", self.name, self.kind, dots)
+        }
+    }
+}
+
+pub struct Args {
+    values: HashMap<String, ConsoleValue>,
+}
+
+impl Args {
+    pub fn value(&self, name: &str) -> Option<&ConsoleValue> {
+        self.values.get(name)
+    }
+
+    pub fn get<T: ConsoleType>(&self, name: &str) -> Option<T> {
+        self.values.get(name).and_then(T::from_value)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+}
+
+pub type CommandHandler = Rc<dyn Fn(&Args, &World) -> Result<(), ConsoleError>>;
+
+#[derive(Clone)]
+pub struct Command {
+    name: String,
+    description: String,
+    args: Vec<Arg>,
+    handler: CommandHandler,
+}
+
+impl Command {
+    pub fn new(
+        name: &str,
+        description: &str,
+        handler: impl Fn(&Args, &World) -> Result<(), ConsoleError> + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            args: Vec::new(),
+            handler: Rc::new(handler),
+        }
+    }
+
+    pub fn with_arg(mut self, arg: Arg) -> Self {
+        if self.args.last().is_some_and(|last| last.rest) {
+            panic!(
+                "Command {} has an argument after a rest argument",
+                self.name
+            );
+        }
+
+        self.args.push(arg);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn args(&self) -> &[Arg] {
+        &self.args
+    }
+
+    pub fn handler(&self) -> &CommandHandler {
+        &self.handler
+    }
+
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for arg in &self.args {
+            usage.push(' ');
+            usage.push_str(&arg.usage());
+        }
+
+        usage
+    }
+
+    pub fn parse(&self, tokens: &[String]) -> Result<Args, ConsoleError> {
+        let mut values = HashMap::new();
+        for (index, arg) in self.args.iter().enumerate() {
+            let token = match tokens.get(index) {
+                Some(_) if arg.rest => {
+                    let rest = tokens[index..].join(" ");
+                    values.insert(arg.name.clone(), ConsoleValue::Text(rest));
+                    return Ok(Args { values });
+                }
+                Some(token) => token,
+                None if arg.optional => continue,
+                None => {
+                    return Err(ConsoleError::MissingArgument {
+                        command: self.name.clone(),
+                        arg: arg.name.clone(),
+                    })
+                }
+            };
+
+            let value = arg
+                .kind
+                .parse(token)
+                .ok_or_else(|| ConsoleError::InvalidArgument {
+                    arg: arg.name.clone(),
+                    value: token.clone(),
+                    expected: arg.kind.to_string(),
+                })?;
+
+            if let Completion::Choices(choices) = &arg.completion {
+                if !choices.contains(token) {
+                    return Err(ConsoleError::InvalidArgument {
+                        arg: arg.name.clone(),
+                        value: token.clone(),
+                        expected: choices.join("|"),
+                    });
+                }
+            }
+
+            values.insert(arg.name.clone(), value);
+        }
+
+        if tokens.len() > self.args.len() {
+            return Err(ConsoleError::TooManyArguments {
+                command: self.name.clone(),
+                usage: self.usage(),
+            });
+        }
+
+        Ok(Args { values })
+    }
+}
@@ -0,0 +1,64 @@
+use super::{ConsoleValue, ValueKind};
+use crate::ecs::World;
+use std::rc::Rc;
+
+pub type CVarCallback = Rc<dyn Fn(&ConsoleValue, &World)>;
+
+#[derive(Clone)]
+pub struct CVar {
+    name: String,
+    description: String,
+    value: ConsoleValue,
+    default: ConsoleValue,
+    on_change: Option<CVarCallback>,
+}
+
+impl CVar {
+    pub fn new(name: &str, description: &str, value: ConsoleValue) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            default: value.clone(),
+            value,
+            on_change: None,
+        }
+    }
+
+    pub fn on_change(mut self, callback: impl Fn(&ConsoleValue, &World) + 'static) -> Self {
+        self.on_change = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn kind(&self) -> ValueKind {
+        self.value.kind()
+    }
+
+    pub fn value(&self) -> &ConsoleValue {
+        &self.value
+    }
+
+    pub fn default(&self) -> &ConsoleValue {
+        &self.default
+    }
+
+    pub fn callback(&self) -> Option<&CVarCallback> {
+        self.on_change.as_ref()
+    }
+
+    pub(super) fn set(&mut self, value: ConsoleValue) -> bool {
+        if value.kind() != self.kind() {
+            return false;
+        }
+
+        self.value = value;
+        true
+    }
+}
@@ -0,0 +1,451 @@
+use crate::ecs::{Resource, World};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::Path,
+    sync::mpsc::{self, Receiver},
+};
+
+pub mod builtin;
+pub mod command;
+pub mod cvar;
+pub mod plugin;
+pub mod value;
+
+pub use command::*;
+pub use cvar::*;
+pub use plugin::*;
+pub use value::*;
+
+const MAX_HISTORY: usize = 100;
+const MAX_OUTPUT: usize = 1000;
+const MAX_LINES_PER_FRAME: usize = 1024;
+
+pub struct Console {
+    commands: HashMap<String, Command>,
+    cvars: HashMap<String, CVar>,
+    pending: VecDeque<String>,
+    changed: Vec<String>,
+    history: Vec<String>,
+    output: Vec<String>,
+    echo: bool,
+    stdin: Option<Receiver<String>>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            commands: HashMap::new(),
+            cvars: HashMap::new(),
+            pending: VecDeque::new(),
+            changed: Vec::new(),
+            history: Vec::new(),
+            output: Vec::new(),
+            echo: false,
+            stdin: None,
+        }
+    }
+
+    pub fn with_command(mut self, command: Command) -> Self {
+        self.register(command);
+        self
+    }
+
+    pub fn with_cvar(mut self, cvar: CVar) -> Self {
+        self.register_cvar(cvar);
+        self
+    }
+
+    pub fn register(&mut self, command: Command) {
+        if self.commands.contains_key(command.name()) || self.cvars.contains_key(command.name()) {
+            panic!(
+                "Console command registered more than once: {}",
+                command.name()
+            );
+        }
+
+        self.commands.insert(command.name().to_string(), command);
+    }
+
+    pub fn register_cvar(&mut self, cvar: CVar) {
+        if self.commands.contains_key(cvar.name()) || self.cvars.contains_key(cvar.name()) {
+            panic!(
+                "Console variable registered more than once: {}",
+                cvar.name()
+            );
+        }
+
+        self.changed.push(cvar.name().to_string());
+        self.cvars.insert(cvar.name().to_string(), cvar);
+    }
+
+    pub fn unregister(&mut self, name: &str) {
+        self.commands.remove(name);
+        self.cvars.remove(name);
+    }
+
+    pub fn command(&self, name: &str) -> Option<&Command> {
+        self.commands.get(name)
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.commands.values()
+    }
+
+    pub fn cvar(&self, name: &str) -> Option<&CVar> {
+        self.cvars.get(name)
+    }
+
+    pub fn cvars(&self) -> impl Iterator<Item = &CVar> {
+        self.cvars.values()
+    }
+
+    pub fn get<T: ConsoleType>(&self, name: &str) -> Option<T> {
+        self.cvars
+            .get(name)
+            .and_then(|cvar| T::from_value(cvar.value()))
+    }
+
+    pub fn set<T: ConsoleType>(&mut self, name: &str, value: T) -> Result<(), ConsoleError> {
+        self.set_value(name, value.into_value())
+    }
+
+    pub fn set_value(&mut self, name: &str, value: ConsoleValue) -> Result<(), ConsoleError> {
+        let cvar = self
+            .cvars
+            .get_mut(name)
+            .ok_or_else(|| ConsoleError::UnknownCommand(name.to_string()))?;
+
+        let kind = cvar.kind();
+        let text = value.to_string();
+        if !cvar.set(value) {
+            return Err(ConsoleError::InvalidArgument {
+                arg: name.to_string(),
+                value: text,
+                expected: kind.to_string(),
+            });
+        }
+
+        self.changed.push(name.to_string());
+        Ok(())
+    }
+
+    pub fn assign(&mut self, name: &str, text: &str) -> Result<(), ConsoleError> {
+        let kind = self
+            .cvars
+            .get(name)
+            .map(|cvar| cvar.kind())
+            .ok_or_else(|| ConsoleError::UnknownCommand(name.to_string()))?;
+
+        let value = kind
+            .parse(text)
+            .ok_or_else(|| ConsoleError::InvalidArgument {
+                arg: name.to_string(),
+                value: text.to_string(),
+                expected: kind.to_string(),
+            })?;
+
+        self.set_value(name, value)
+    }
+
+    pub fn reset(&mut self, name: &str) -> Result<(), ConsoleError> {
+        let default = self
+            .cvars
+            .get(name)
+            .map(|cvar| cvar.default().clone())
+            .ok_or_else(|| ConsoleError::UnknownCommand(name.to_string()))?;
+
+        self.set_value(name, default)
+    }
+
+    pub fn submit(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+
+        if self.history.last().map(|last| last.as_str()) != Some(line) {
+            self.history.push(line.to_string());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+
+        self.pending.push_back(line.to_string());
+    }
+
+    pub fn queue(&mut self, line: &str) {
+        self.pending.push_back(line.to_string());
+    }
+
+    pub fn exec_script(&mut self, path: &Path) -> std::io::Result<()> {
+        let script = std::fs::read_to_string(path)?;
+        for line in script.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+
+            self.queue(line);
+        }
+
+        Ok(())
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    pub fn print(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if self.echo {
+            println!("{}", line);
+        }
+
+        self.output.push(line);
+        if self.output.len() > MAX_OUTPUT {
+            let excess = self.output.len() - MAX_OUTPUT;
+            self.output.drain(..excess);
+        }
+    }
+
+    pub fn clear_output(&mut self) {
+        self.output.clear();
+    }
+
+    pub fn set_echo(&mut self, echo: bool) {
+        self.echo = echo;
+    }
+
+    pub fn listen_stdin(&mut self) {
+        if self.stdin.is_some() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.stdin = Some(receiver);
+    }
+
+    pub fn help(&self, name: &str) -> Option<String> {
+        if let Some(command) = self.commands.get(name) {
+            return Some(format!("{} - {}", command.usage(), command.description()));
+        }
+
+        self.cvars.get(name).map(|cvar| {
+            format!(
+                "{} = {} (default {}) - {}",
+                cvar.name(),
+                cvar.value(),
+                cvar.default(),
+                cvar.description()
+            )
+        })
+    }
+
+    pub fn complete(&self, input: &str) -> Vec<String> {
+        let mut tokens = input.split_whitespace().collect::<Vec<_>>();
+        let partial = if input.is_empty() || input.ends_with(char::is_whitespace) {
+            ""
+        } else {
+            tokens.pop().unwrap_or("")
+        };
+
+        let mut candidates = match tokens.first() {
+            None => self.names(),
+            Some(name) => {
+                let index = tokens.len() - 1;
+                if let Some(command) = self.commands.get(*name) {
+                    match command.args().get(index) {
+                        Some(arg) => self.arg_candidates(arg),
+                        None => Vec::new(),
+                    }
+                } else if let Some(cvar) = self.cvars.get(*name) {
+                    match (index, cvar.kind()) {
+                        (0, ValueKind::Bool) => vec!["true".to_string(), "false".to_string()],
+                        (0, _) => vec![cvar.value().to_string()],
+                        _ => Vec::new(),
+                    }
+                } else {
+                    Vec::new()
+                }
+            }
+        };
+
+        candidates.retain(|candidate| candidate.starts_with(partial));
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.commands
+            .keys()
+            .chain(self.cvars.keys())
+            .cloned()
+            .collect()
+    }
+
+    fn arg_candidates(&self, arg: &Arg) -> Vec<String> {
+        match arg.completion() {
+            Completion::Choices(choices) => choices.clone(),
+            Completion::Commands => self.names(),
+            Completion::CVars => self.cvars.keys().cloned().collect(),
+            Completion::None if arg.kind() == ValueKind::Bool => {
+                vec!["true".to_string(), "false".to_string()]
+            }
+            Completion::None => Vec::new(),
+        }
+    }
+
+    fn poll_stdin(&mut self) {
+        let lines = match &self.stdin {
+            Some(stdin) => stdin.try_iter().collect::<Vec<_>>(),
+            None => return,
+        };
+
+        for line in lines {
+            self.submit(&line);
+        }
+    }
+}
+
+impl Resource for Console {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn tokenize(line: &str) -> Result<Vec<Vec<String>>, ConsoleError> {
+    let mut statements = Vec::new();
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    let mut in_token = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_token = true;
+            }
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    token.push(escaped);
+                }
+            }
+            ';' if !quoted => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+
+                if !tokens.is_empty() {
+                    statements.push(std::mem::take(&mut tokens));
+                }
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+            c => {
+                token.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if quoted {
+        return Err(ConsoleError::UnterminatedQuote);
+    }
+
+    if in_token {
+        tokens.push(token);
+    }
+
+    if !tokens.is_empty() {
+        statements.push(tokens);
+    }
+
+    Ok(statements)
+}
+
+pub fn execute(world: &World, line: &str) -> Result<(), ConsoleError> {
+    for tokens in tokenize(line)? {
+        let (name, args) = tokens.split_first().expect("Statements are never empty");
+
+        let command = world.resource::<Console>().command(name).cloned();
+        if let Some(command) = command {
+            let args = command.parse(args)?;
+            (command.handler())(&args, world)?;
+            continue;
+        }
+
+        let mut console = world.resource_mut::<Console>();
+        if console.cvar(name).is_none() {
+            return Err(ConsoleError::UnknownCommand(name.clone()));
+        }
+
+        if args.is_empty() {
+            let help = console.help(name).unwrap_or_default();
+            console.print(help);
+        } else {
+            console.assign(name, &args.join(" "))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn execute_commands(world: &World) {
+    world.resource_mut::<Console>().poll_stdin();
+
+    for _ in 0..MAX_LINES_PER_FRAME {
+        let line = world.resource_mut::<Console>().pending.pop_front();
+        let Some(line) = line else {
+            break;
+        };
+
+        if let Err(error) = execute(world, &line) {
+            world.resource_mut::<Console>().print(error.to_string());
+        }
+    }
+
+    let changed = {
+        let mut console = world.resource_mut::<Console>();
+        let changed = std::mem::take(&mut console.changed);
+        changed
+            .into_iter()
+            .filter_map(|name| {
+                let cvar = console.cvar(&name)?;
+                let callback = cvar.callback()?.clone();
+                Some((callback, cvar.value().clone()))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for (callback, value) in changed {
+        callback(&value, world);
+    }
+}
@@ -0,0 +1,50 @@
+use super::{builtin::builtins, execute_commands, Console};
+use crate::{
+    game::{GameBuilder, Plugin},
+    schedule::ScenePhase,
+};
+use std::path::PathBuf;
+
+pub struct ConsolePlugin {
+    script: Option<PathBuf>,
+    stdin: bool,
+}
+
+impl ConsolePlugin {
+    pub fn new() -> Self {
+        Self {
+            script: None,
+            stdin: false,
+        }
+    }
+
+    pub fn with_script(mut self, path: impl Into<PathBuf>) -> Self {
+        self.script = Some(path.into());
+        self
+    }
+
+    pub fn with_stdin(mut self) -> Self {
+        self.stdin = true;
+        self
+    }
+}
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        let mut console = builtins(Console::new());
+
+        if self.stdin {
+            console.set_echo(true);
+            console.listen_stdin();
+        }
+
+        if let Some(path) = &self.script {
+            if let Err(e) = console.exec_script(path) {
+                eprintln!("Failed to run console script {}: {}", path.display(), e);
+            }
+        }
+
+        game.add_resource(console);
+        game.add_system(ScenePhase::PreUpdate, execute_commands);
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Bool,
+    Int,
+    Float,
+    Text,
+}
+
+impl ValueKind {
+    pub fn parse(&self, text: &str) -> Option<ConsoleValue> {
+        match self {
+            Self::Bool => match text.to_ascii_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Some(ConsoleValue::Bool(true)),
+                "0" | "false" | "off" | "no" => Some(ConsoleValue::Bool(false)),
+                _ => None,
+            },
+            Self::Int => text.parse().ok().map(ConsoleValue::Int),
+            Self::Float => text.parse().ok().map(ConsoleValue::Float),
+            Self::Text => Some(ConsoleValue::Text(text.to_string())),
+        }
+    }
+}
+
+impl Display for ValueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool => write!(f, "bool"),
+            Self::Int => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+            Self::Text => write!(f, "text"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl ConsoleValue {
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Self::Bool(_) => ValueKind::Bool,
+            Self::Int(_) => ValueKind::Int,
+            Self::Float(_) => ValueKind::Float,
+            Self::Text(_) => ValueKind::Text,
+        }
+    }
+}
+
+impl Display for ConsoleValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "\"{}\"", value),
+        }
+    }
+}
+
+pub trait ConsoleType: Sized + 'static {
+    fn kind() -> ValueKind;
+    fn from_value(value: &ConsoleValue) -> Option<Self>;
+    fn into_value(self) -> ConsoleValue;
+}
+
+impl ConsoleType for bool {
+    fn kind() -> ValueKind {
+        ValueKind::Bool
+    }
+
+    fn from_value(value: &ConsoleValue) -> Option<Self> {
+        match value {
+            ConsoleValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> ConsoleValue {
+        ConsoleValue::Bool(self)
+    }
+}
+
+impl ConsoleType for String {
+    fn kind() -> ValueKind {
+        ValueKind::Text
+    }
+
+    fn from_value(value: &ConsoleValue) -> Option<Self> {
+        match value {
+            ConsoleValue::Text(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> ConsoleValue {
+        ConsoleValue::Text(self)
+    }
+}
+
+macro_rules! int_type {
+    ($($t:ty),*) => {
+        $(
+            impl ConsoleType for $t {
+                fn kind() -> ValueKind {
+                    ValueKind::Int
+                }
+
+                fn from_value(value: &ConsoleValue) -> Option<Self> {
+                    match value {
+                        ConsoleValue::Int(value) => <$t>::try_from(*value).ok(),
+                        _ => None,
+                    }
+                }
+
+                fn into_value(self) -> ConsoleValue {
+                    ConsoleValue::Int(self as i64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float_type {
+    ($($t:ty),*) => {
+        $(
+            impl ConsoleType for $t {
+                fn kind() -> ValueKind {
+                    ValueKind::Float
+                }
+
+                fn from_value(value: &ConsoleValue) -> Option<Self> {
+                    match value {
+                        ConsoleValue::Float(value) => Some(*value as $t),
+                        ConsoleValue::Int(value) => Some(*value as $t),
+                        _ => None,
+                    }
+                }
+
+                fn into_value(self) -> ConsoleValue {
+                    ConsoleValue::Float(self as f64)
+                }
+            }
+        )*
+    };
+}
+
+int_type!(i32, i64, u32, u64, usize);
+float_type!(f32, f64);
@@ -0,0 +1,229 @@
+use crate::ecs::Resource;
+use std::{
+    collections::{HashMap, VecDeque},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+pub mod plugin;
+pub mod trace;
+
+pub use plugin::*;
+pub use trace::*;
+
+pub const FRAME: &str = "frame";
+pub const ENTITIES: &str = "entities";
+pub const ARCHETYPES: &str = "archetypes";
+pub const EVENTS: &str = "events";
+pub const VISIBLE: &str = "visible";
+pub const CULLED: &str = "culled";
+
+pub struct Diagnostic {
+    history: VecDeque<f64>,
+    max_history: usize,
+    sum: f64,
+}
+
+impl Diagnostic {
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            history: VecDeque::with_capacity(max_history),
+            max_history: max_history.max(1),
+            sum: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if self.history.len() == self.max_history {
+            if let Some(old) = self.history.pop_front() {
+                self.sum -= old;
+            }
+        }
+
+        self.history.push_back(value);
+        self.sum += value;
+    }
+
+    pub fn latest(&self) -> Option<f64> {
+        self.history.back().copied()
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        if self.history.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.history.len() as f64)
+        }
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.history.iter().copied().reduce(f64::min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.history.iter().copied().reduce(f64::max)
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &f64> {
+        self.history.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.sum = 0.0;
+    }
+}
+
+pub struct Diagnostics {
+    enabled: bool,
+    max_history: usize,
+    diagnostics: HashMap<String, Diagnostic>,
+    origin: Instant,
+    frame: u64,
+    frame_start: Option<Instant>,
+    frame_events: usize,
+    capture: usize,
+    trace: Vec<TraceEvent>,
+}
+
+impl Diagnostics {
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            enabled: true,
+            max_history,
+            diagnostics: HashMap::new(),
+            origin: Instant::now(),
+            frame: 0,
+            frame_start: None,
+            frame_events: 0,
+            capture: 0,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Diagnostic> {
+        self.diagnostics.get(name)
+    }
+
+    pub fn system(&self, name: &str) -> Option<&Diagnostic> {
+        self.get(&format!("system/{}", name))
+    }
+
+    pub fn phase(&self, name: &str) -> Option<&Diagnostic> {
+        self.get(&format!("phase/{}", name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Diagnostic)> {
+        self.diagnostics.iter()
+    }
+
+    pub fn record(&mut self, name: &str, value: f64) {
+        if !self.enabled {
+            return;
+        }
+
+        let max_history = self.max_history;
+        self.diagnostics
+            .entry(name.to_string())
+            .or_insert_with(|| Diagnostic::new(max_history))
+            .add(value);
+    }
+
+    pub fn record_span(&mut self, category: &str, name: &str, start: Instant, duration: Duration) {
+        if !self.enabled {
+            return;
+        }
+
+        self.record(
+            &format!("{}/{}", category, name),
+            duration.as_secs_f64() * 1000.0,
+        );
+        self.trace(category, name, start, duration);
+    }
+
+    pub fn add_events(&mut self, count: usize) {
+        self.frame_events += count;
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Some(Instant::now());
+        self.frame_events = 0;
+    }
+
+    pub fn end_frame(&mut self, entities: usize, archetypes: usize) {
+        if let Some(start) = self.frame_start.take() {
+            let duration = start.elapsed();
+            self.record(FRAME, duration.as_secs_f64() * 1000.0);
+            if self.enabled {
+                self.trace(FRAME, &self.frame.to_string(), start, duration);
+            }
+        }
+
+        self.record(ENTITIES, entities as f64);
+        self.record(ARCHETYPES, archetypes as f64);
+        self.record(EVENTS, self.frame_events as f64);
+        self.capture = self.capture.saturating_sub(1);
+        self.frame += 1;
+    }
+
+    pub fn capture_frames(&mut self, frames: usize) {
+        self.trace.clear();
+        self.capture = frames;
+    }
+
+    pub fn capturing(&self) -> bool {
+        self.capture > 0
+    }
+
+    pub fn captured(&self) -> &[TraceEvent] {
+        &self.trace
+    }
+
+    pub fn export_chrome_trace(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, chrome_trace_json(&self.trace))
+    }
+
+    pub fn clear(&mut self) {
+        self.diagnostics.clear();
+        self.trace.clear();
+    }
+
+    fn trace(&mut self, category: &str, name: &str, start: Instant, duration: Duration) {
+        if self.capture > 0 {
+            self.trace.push(TraceEvent::new(
+                name,
+                category,
+                start.saturating_duration_since(self.origin),
+                duration,
+            ));
+        }
+    }
+}
+
+impl Resource for Diagnostics {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn profiling(world: &crate::ecs::World) -> bool {
+    world
+        .try_resource::<Diagnostics>()
+        .is_some_and(|diagnostics| diagnostics.enabled())
+}
@@ -0,0 +1,24 @@
+use super::Diagnostics;
+use crate::game::{GameBuilder, Plugin};
+
+pub struct DiagnosticsPlugin {
+    max_history: usize,
+}
+
+impl DiagnosticsPlugin {
+    pub fn new(max_history: usize) -> Self {
+        Self { max_history }
+    }
+}
+
+impl Default for DiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(120)
+    }
+}
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        game.add_resource(Diagnostics::new(self.max_history));
+    }
+}
@@ -0,0 +1,60 @@
+use std::{fmt::Write, time::Duration};
+
+#[derive(Clone, Debug)]
+pub struct TraceEvent {
+    pub name: String,
+    pub category: String,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl TraceEvent {
+    pub fn new(name: &str, category: &str, start: Duration, duration: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            category: category.to_string(),
+            start,
+            duration,
+        }
+    }
+}
+
+pub fn chrome_trace_json(events: &[TraceEvent]) -> String {
+    let mut json = String::from("{\"traceEvents\":[");
+    for (index, event) in events.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+
+        let _ = write!(
+            json,
+            "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":1}}",
+            escape(&event.name),
+            escape(&event.category),
+            event.start.as_secs_f64() * 1_000_000.0,
+            event.duration.as_secs_f64() * 1_000_000.0,
+        );
+    }
+    json.push_str("],\"displayTimeUnit\":\"ms\"}");
+
+    json
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -108,6 +108,10 @@ impl ArchetypeManager {
         }
     }
 
+    pub fn count(&self) -> usize {
+        self.archetypes.len()
+    }
+
     pub fn clear(&mut self) {
         self.archetypes.clear();
         self.entity_index.clear();
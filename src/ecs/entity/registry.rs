@@ -19,6 +19,10 @@ impl EntityRegistry {
         }
     }
 
+    pub fn count(&self) -> usize {
+        self.entities.len().saturating_sub(self.destroyed.len())
+    }
+
     pub fn get(&self, id: &EntityId) -> Option<&Entity> {
         if self.destroyed.contains(id) || self.disabled.contains(id) {
             None
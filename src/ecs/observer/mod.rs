@@ -41,6 +41,10 @@ impl EventManager {
         self.observers.observe::<T>(system);
     }
 
+    pub fn count(&self) -> usize {
+        self.events.values().map(|events| events.len()).sum()
+    }
+
     pub fn flush(&mut self, world: &World) {
         for (type_id, events) in self.events.iter_mut() {
             let entity_ids = events
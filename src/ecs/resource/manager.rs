@@ -50,6 +50,29 @@ impl ResourceManager {
         RefMut::map(resource, |x| x.as_any_mut().downcast_mut::<T>().unwrap())
     }
 
+    pub fn contains<T: Resource>(&self) -> bool {
+        let id = TypeId::of::<T>().into();
+        self.resources.contains_key(&id)
+    }
+
+    pub fn try_resource<T: Resource>(&self) -> Option<Ref<'_, T>> {
+        let id = TypeId::of::<T>().into();
+        let resource = self.resources.get(&id)?.borrow();
+
+        Some(Ref::map(resource, |x| {
+            x.as_any().downcast_ref::<T>().unwrap()
+        }))
+    }
+
+    pub fn try_resource_mut<T: Resource>(&self) -> Option<RefMut<'_, T>> {
+        let id = TypeId::of::<T>().into();
+        let resource = self.resources.get(&id)?.borrow_mut();
+
+        Some(RefMut::map(resource, |x| {
+            x.as_any_mut().downcast_mut::<T>().unwrap()
+        }))
+    }
+
     pub fn resource_ref(&self, type_id: &ResourceType) -> &Rc<RefCell<Box<dyn Resource>>> {
         self.resources.get(type_id).expect("Resource not found")
     }
@@ -2,6 +2,10 @@ use super::world::World;
 
 pub trait System: 'static {
     fn run(&self, world: &World);
+
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
 
 impl<T: Fn(&World) + 'static> System for T {
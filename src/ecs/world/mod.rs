@@ -65,6 +65,22 @@ impl World {
         self.resources.resource_mut::<T>()
     }
 
+    pub fn has_resource<T: Resource>(&self) -> bool {
+        self.resources.contains::<T>()
+    }
+
+    pub fn try_resource<T: Resource>(&self) -> Option<Ref<'_, T>> {
+        self.resources.try_resource::<T>()
+    }
+
+    pub fn try_resource_mut<T: Resource>(&self) -> Option<RefMut<'_, T>> {
+        self.resources.try_resource_mut::<T>()
+    }
+
+    pub fn resources(&self) -> &ResourceManager {
+        &self.resources
+    }
+
     pub fn resource_ref(&self, type_id: &ResourceType) -> &Rc<RefCell<Box<dyn Resource>>> {
         self.resources.resource_ref(type_id)
     }
@@ -0,0 +1,271 @@
+use crate::ecs::Resource;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const DEFAULT_CONFIG_FILES: [&str; 2] = ["game.toml", "game.ron"];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub fullscreen: bool,
+    pub vsync: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Rust Game Engine".to_string(),
+            width: 1280,
+            height: 720,
+            fullscreen: false,
+            vsync: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeConfig {
+    pub fixed_rate: f64,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self { fixed_rate: 60.0 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetConfig {
+    pub root: PathBuf,
+    pub archive: Option<PathBuf>,
+    pub watch: bool,
+    pub watch_interval: f64,
+    pub preload: bool,
+    pub loader_threads: usize,
+    pub cache: bool,
+    pub cache_dir: PathBuf,
+    pub rebuild: bool,
+}
+
+impl Default for AssetConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("./assets"),
+            archive: None,
+            watch: false,
+            watch_interval: 0.5,
+            preload: true,
+            loader_threads: 4,
+            cache: true,
+            cache_dir: PathBuf::from("./.cache/assets"),
+            rebuild: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RendererConfig {
+    pub max_lights: usize,
+}
+
+impl Default for RendererConfig {
+    fn default() -> Self {
+        Self { max_lights: 100 }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub window: WindowConfig,
+    pub time: TimeConfig,
+    pub assets: AssetConfig,
+    pub renderer: RendererConfig,
+}
+
+impl GameConfig {
+    pub fn load(path: &Path) -> std::io::Result<GameConfig> {
+        let text = std::fs::read_to_string(path)?;
+        let config = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ron") => ron::from_str(&text).map_err(|e| e.to_string()),
+            _ => toml::from_str(&text).map_err(|e| e.to_string()),
+        };
+
+        config
+            .and_then(|config: GameConfig| config.validate().map(|_| config))
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let text = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ron") => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|e| e.to_string()),
+            _ => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+        };
+
+        std::fs::write(
+            path,
+            text.map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+        )
+    }
+
+    pub fn from_args() -> GameConfig {
+        let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+        let path = args
+            .iter()
+            .position(|arg| arg == "--config")
+            .and_then(|index| args.get(index + 1))
+            .map(PathBuf::from)
+            .or_else(|| {
+                DEFAULT_CONFIG_FILES
+                    .iter()
+                    .map(PathBuf::from)
+                    .find(|path| path.exists())
+            });
+
+        let mut config = match path {
+            Some(path) => GameConfig::load(&path).unwrap_or_else(|e| {
+                eprintln!("Failed to load game config: {}", e);
+                GameConfig::default()
+            }),
+            None => GameConfig::default(),
+        };
+
+        config.apply_args(&args);
+        config
+    }
+
+    pub fn apply_args(&mut self, args: &[String]) {
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let Some(key) = arg.strip_prefix("--") else {
+                continue;
+            };
+
+            let result = match key.split_once('=') {
+                Some((key, value)) => self.set(key, Some(value)),
+                None => match args.peek().filter(|value| !value.starts_with("--")) {
+                    Some(value) if !Self::is_flag(key) => {
+                        let value = value.as_str();
+                        args.next();
+                        self.set(key, Some(value))
+                    }
+                    _ => self.set(key, None),
+                },
+            };
+
+            if let Err(e) = result {
+                eprintln!("Ignoring argument {}: {}", arg, e);
+            }
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), String> {
+        fn parse<T: std::str::FromStr>(key: &str, value: Option<&str>) -> Result<T, String> {
+            let value = value.ok_or_else(|| format!("{} requires a value", key))?;
+            value
+                .parse()
+                .map_err(|_| format!("invalid value '{}' for {}", value, key))
+        }
+
+        let previous = self.clone();
+        match key {
+            "title" | "window.title" => self.window.title = parse(key, value)?,
+            "width" | "window.width" => self.window.width = parse(key, value)?,
+            "height" | "window.height" => self.window.height = parse(key, value)?,
+            "fullscreen" | "window.fullscreen" => {
+                self.window.fullscreen = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "windowed" => self.window.fullscreen = false,
+            "vsync" | "window.vsync" => {
+                self.window.vsync = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "no-vsync" => self.window.vsync = false,
+            "fixed-rate" | "time.fixed_rate" => self.time.fixed_rate = parse(key, value)?,
+            "assets" | "assets.root" => self.assets.root = parse(key, value)?,
+            "archive" | "assets.archive" => self.assets.archive = Some(parse(key, value)?),
+            "watch-assets" | "assets.watch" => {
+                self.assets.watch = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "assets.watch_interval" => self.assets.watch_interval = parse(key, value)?,
+            "preload" | "assets.preload" => {
+                self.assets.preload = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "no-preload" => self.assets.preload = false,
+            "loader-threads" | "assets.loader_threads" => {
+                self.assets.loader_threads = parse(key, value)?
+            }
+            "asset-cache" | "assets.cache" => {
+                self.assets.cache = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "no-asset-cache" => self.assets.cache = false,
+            "asset-cache-dir" | "assets.cache_dir" => self.assets.cache_dir = parse(key, value)?,
+            "rebuild-assets" | "assets.rebuild" => {
+                self.assets.rebuild = value.map_or(Ok(true), |_| parse(key, value))?
+            }
+            "max-lights" | "renderer.max_lights" => self.renderer.max_lights = parse(key, value)?,
+            "config" => {}
+            _ => return Err("unknown option".to_string()),
+        }
+
+        if let Err(e) = self.validate() {
+            *self = previous;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = |name: &str, value: f64| match value.is_finite() && value > 0.0 {
+            true => Ok(()),
+            false => Err(format!("{} must be a positive number", name)),
+        };
+
+        positive("time.fixed_rate", self.time.fixed_rate)?;
+        positive("window.width", self.window.width as f64)?;
+        positive("window.height", self.window.height as f64)?;
+        positive("assets.watch_interval", self.assets.watch_interval)
+    }
+
+    pub fn fixed_delta(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.time.fixed_rate)
+    }
+
+    fn is_flag(key: &str) -> bool {
+        matches!(
+            key,
+            "fullscreen"
+                | "windowed"
+                | "vsync"
+                | "no-vsync"
+                | "watch-assets"
+                | "preload"
+                | "no-preload"
+                | "asset-cache"
+                | "no-asset-cache"
+                | "rebuild-assets"
+        )
+    }
+}
+
+impl Resource for GameConfig {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -1,49 +1,71 @@
-use super::{GameBuilder, GameTime, Timer};
+use super::{DefaultPlugins, GameBuilder, GameConfig, Timer};
 use crate::{
-    asset::AssetDatabase,
+    diagnostics::{profiling, Diagnostics},
     ecs::{observer::EventManager, Registry, World},
-    graphics::{engine::GraphicsEngine, Graphics},
+    graphics::{engine::GraphicsEngine, renderer::graph::RenderGraphBuilder, Graphics},
+    input::{InputEvent, InputEvents, InputRecorder},
     scene::{Scene, SceneManager},
     schedule::{ScenePhase, Scheduler},
 };
-use std::{cell::RefCell, path::Path, rc::Rc, time::Duration};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 use winit::{
     event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
     event_loop::EventLoop,
 };
 
-const FIXED_DELTA: f64 = 1.0 / 60.0;
-
 pub struct Game {
     world: World,
-    graphics: GraphicsEngine,
+    graphics: Option<GraphicsEngine>,
+    systems: Scheduler,
     scheduler: Scheduler,
     timer: Rc<RefCell<Timer>>,
+    accumulator: Duration,
 }
 
 impl Game {
     pub fn new() -> GameBuilder {
         GameBuilder::new()
+            .with_config(GameConfig::from_args())
+            .with_plugins(DefaultPlugins)
     }
 
-    pub(super) async fn build<T: Scene>(events: &EventLoop<()>, builder: GameBuilder) -> Game {
-        let graphics = GraphicsEngine::new(events).await;
+    pub(super) async fn build<T: Scene>(
+        events: Option<&EventLoop<()>>,
+        mut builder: GameBuilder,
+    ) -> Game {
+        let config = builder.game_config.clone();
+        let mut graphics = match events {
+            Some(events) => Some(GraphicsEngine::new(events, &config.window).await),
+            None => None,
+        };
+
+        let timer = Rc::new(RefCell::new(Timer::new(config.fixed_delta())));
+
+        builder.gpu = graphics.as_ref().map(|graphics| graphics.gpu().clone());
+        builder.config = graphics.as_ref().map(|graphics| graphics.config());
+        builder.timer = Some(timer.clone());
+        builder.build_plugins();
+
+        if let Some(graphics) = graphics.as_mut() {
+            let render_graph =
+                std::mem::replace(&mut builder.render_graph, RenderGraphBuilder::new());
+            graphics.set_render_graph(render_graph);
+        }
 
-        let fixed_delta = Duration::from_secs_f64(FIXED_DELTA);
-        let timer = Rc::new(RefCell::new(Timer::new(fixed_delta)));
         let scene_manager = builder.scenes.build::<T>();
-        let importers = builder.importers;
+        let systems = builder.systems;
         let components = builder.components;
         let mut resources = builder.resources;
 
-        resources.register(Graphics::new(graphics.gpu().clone(), graphics.config()));
-        resources.register(GameTime::new(timer.clone()));
-        resources.register(AssetDatabase::new());
+        resources.register(builder.importers);
+        resources.register(config);
         resources.register(EventManager::new());
         resources.register(scene_manager);
 
-        AssetDatabase::load(&Path::new("./assets"), &mut resources, &importers);
-
         let world = World::new(components, resources);
         let scheduler = world.resource::<SceneManager>().current_scene().scheduler();
 
@@ -51,25 +73,48 @@ impl Game {
             world,
             graphics,
             timer,
+            systems,
             scheduler,
+            accumulator: Duration::ZERO,
         }
     }
 
-    fn id(&self) -> winit::window::WindowId {
-        self.graphics.window().id()
+    fn id(&self) -> Option<winit::window::WindowId> {
+        self.graphics
+            .as_ref()
+            .map(|graphics| graphics.window().id())
     }
 
     fn update(&mut self) -> bool {
-        let (mut accumulator, fixed_delta) = {
+        if let Some(mut diagnostics) = self.world.try_resource_mut::<Diagnostics>() {
+            diagnostics.begin_frame();
+        }
+
+        let replaying = self
+            .world
+            .try_resource::<InputRecorder>()
+            .is_some_and(|recorder| recorder.replaying());
+
+        let fixed_delta = {
             let mut timer = self.timer.borrow_mut();
-            timer.update();
+            match replaying {
+                true => {
+                    let fixed_delta = timer.fixed_delta();
+                    timer.advance(fixed_delta);
+                }
+                false => timer.update(),
+            }
 
-            (timer.delta(), timer.fixed_delta())
+            self.accumulator += timer.delta();
+            timer.fixed_delta()
         };
 
-        while accumulator >= fixed_delta {
-            if let Some(scheduler) = self.world.resource_mut::<SceneManager>().update() {
-                self.scheduler.run(ScenePhase::End, &self.world);
+        self.run_phase(ScenePhase::PreUpdate);
+
+        while self.accumulator >= fixed_delta {
+            let next = self.world.resource_mut::<SceneManager>().update();
+            if let Some(scheduler) = next {
+                self.run_phase(ScenePhase::End);
                 self.scheduler = scheduler;
 
                 let observers = self
@@ -79,38 +124,73 @@ impl Game {
                     .observers();
                 self.world.resource_mut::<EventManager>().clear(observers);
 
-                self.scheduler.run(ScenePhase::Start, &self.world);
+                self.run_phase(ScenePhase::Start);
             }
 
-            self.scheduler.run(ScenePhase::Update, &self.world);
-            self.scheduler.run(ScenePhase::PostUpdate, &self.world);
+            self.run_phase(ScenePhase::Update);
+            self.run_phase(ScenePhase::PostUpdate);
 
             self.world.component_manager().update();
             self.world.entities_mut().update();
+            self.timer.borrow_mut().step();
 
-            accumulator -= fixed_delta;
+            self.accumulator -= fixed_delta;
         }
 
-        true
+        !self.world.resource::<SceneManager>().quitting()
     }
 
     fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        self.scheduler.run(ScenePhase::PreRender, &self.world);
+        self.run_phase(ScenePhase::PreRender);
 
-        let mut graphics = self.world.resource_mut::<Graphics>();
-        self.graphics.render(&mut graphics)?;
+        if let Some(engine) = self.graphics.as_mut() {
+            let mut graphics = self.world.resource_mut::<Graphics>();
+            engine.render(&mut graphics)?;
+        }
 
-        self.scheduler.run(ScenePhase::PostRender, &self.world);
+        self.run_phase(ScenePhase::PostRender);
 
         Ok(())
     }
 
-    fn window(&self) -> &winit::window::Window {
-        self.graphics.window()
+    fn run_phase(&mut self, phase: ScenePhase) {
+        let start = Instant::now();
+        self.systems.run(phase, &self.world);
+        self.scheduler.run(phase, &self.world);
+
+        if profiling(&self.world) {
+            let duration = start.elapsed();
+            let mut diagnostics = self.world.resource_mut::<Diagnostics>();
+            diagnostics.record_span("phase", &format!("{:?}", phase), start, duration);
+        }
+    }
+
+    fn end_frame(&mut self) {
+        if let Some(mut diagnostics) = self.world.try_resource_mut::<Diagnostics>() {
+            let entities = self.world.entities().count();
+            let archetypes = self.world.archetypes().count();
+            diagnostics.end_frame(entities, archetypes);
+        }
+    }
+
+    fn input(&mut self, event: InputEvent) {
+        if let Some(mut events) = self.world.try_resource_mut::<InputEvents>() {
+            events.send(event);
+        }
+    }
+
+    fn shutdown(&mut self) {
+        self.run_phase(ScenePhase::End);
+    }
+
+    fn window(&self) -> Option<&winit::window::Window> {
+        self.graphics.as_ref().map(|graphics| graphics.window())
     }
 
     fn resize(&mut self, width: u32, height: u32) {
-        self.graphics.resize(width, height);
+        if let Some(graphics) = self.graphics.as_mut() {
+            graphics.resize(width, height);
+        }
     }
 }
 
@@ -120,45 +200,85 @@ impl GameRunner {
     pub fn run<T: Scene>(builder: GameBuilder) {
         let runner = async {
             let events = EventLoop::new();
-            let mut game = Game::build::<T>(&events, builder).await;
+            let mut game = Game::build::<T>(Some(&events), builder).await;
 
             let _ = events.run(move |event, _, flow| match event {
-                Event::WindowEvent { window_id, event } if window_id == game.id() => match event {
-                    WindowEvent::Resized(size) => game.resize(size.width, size.height),
-                    WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
-                        game.resize(new_inner_size.width, new_inner_size.height)
+                Event::WindowEvent { window_id, event } if Some(window_id) == game.id() => {
+                    if let Some(input) = InputEvent::from_window_event(&event) {
+                        game.input(input);
+                    }
+
+                    match event {
+                        WindowEvent::Resized(size) => game.resize(size.width, size.height),
+                        WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                            game.resize(new_inner_size.width, new_inner_size.height)
+                        }
+                        WindowEvent::CloseRequested
+                        | WindowEvent::KeyboardInput {
+                            input:
+                                KeyboardInput {
+                                    state: ElementState::Pressed,
+                                    virtual_keycode: Some(VirtualKeyCode::Escape),
+                                    ..
+                                },
+                            ..
+                        } => flow.set_exit(),
+                        _ => {}
+                    }
+                }
+                Event::DeviceEvent { event, .. } => {
+                    if let Some(input) = InputEvent::from_device_event(&event) {
+                        game.input(input);
                     }
-                    WindowEvent::CloseRequested
-                    | WindowEvent::KeyboardInput {
-                        input:
-                            KeyboardInput {
-                                state: ElementState::Pressed,
-                                virtual_keycode: Some(VirtualKeyCode::Escape),
-                                ..
-                            },
-                        ..
-                    } => flow.set_exit(),
-                    _ => {}
-                },
+                }
                 Event::MainEventsCleared => {
                     if game.update() {
                         match game.render() {
                             Ok(_) => {}
                             Err(wgpu::SurfaceError::Lost) => {
-                                let size = game.window().inner_size();
-                                game.resize(size.width, size.height);
+                                if let Some(size) = game.window().map(|w| w.inner_size()) {
+                                    game.resize(size.width, size.height);
+                                }
                             }
                             Err(wgpu::SurfaceError::OutOfMemory) => flow.set_exit(),
                             Err(e) => eprintln!("{:?}", e),
                         }
+
+                        game.end_frame();
                     } else {
                         flow.set_exit();
                     }
                 }
+                Event::LoopDestroyed => game.shutdown(),
                 _ => {}
             });
         };
 
         pollster::block_on(runner);
     }
+
+    pub fn run_headless<T: Scene>(builder: GameBuilder) {
+        let mut game = pollster::block_on(Game::build::<T>(None, builder));
+
+        loop {
+            let start = Instant::now();
+            let replaying = game.world.has_resource::<InputRecorder>()
+                && game.world.resource::<InputRecorder>().replaying();
+
+            if !game.update() {
+                break;
+            }
+
+            game.end_frame();
+
+            if !replaying {
+                let fixed_delta = game.timer.borrow().fixed_delta();
+                if let Some(remaining) = fixed_delta.checked_sub(start.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+        }
+
+        game.shutdown();
+    }
 }
@@ -1,58 +1,242 @@
 use crate::{
-    asset::{Asset, AssetImporter, ImporterRepo},
-    ecs::{Component, ComponentManager, Resource, ResourceManager},
+    asset::{Asset, AssetDatabase, AssetImporter, ImporterRepo},
+    ecs::{Component, ComponentManager, Resource, ResourceManager, System},
+    graphics::{
+        renderer::{
+            graph::RenderGraphBuilder, node::RenderGraphNodeBuilder, BufferInfo, TextureInfo,
+        },
+        Config, Gpu,
+    },
     scene::{Scene, ScenesBuilder},
+    schedule::{ScenePhase, Scheduler},
 };
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+pub mod config;
 pub mod inner;
+pub mod plugin;
 pub mod time;
 
+pub use config::*;
 pub use inner::*;
+pub use plugin::*;
 pub use time::*;
 
+pub type Placeholder = Box<dyn FnOnce(&mut AssetDatabase)>;
+
 pub struct GameBuilder {
     importers: ImporterRepo,
+    placeholders: Vec<Placeholder>,
     resources: ResourceManager,
     components: ComponentManager,
     scenes: ScenesBuilder,
+    systems: Scheduler,
+    plugins: Plugins,
+    render_graph: RenderGraphBuilder,
+    game_config: GameConfig,
+    gpu: Option<Rc<Gpu>>,
+    config: Option<Config>,
+    timer: Option<Rc<RefCell<Timer>>>,
 }
 
 impl GameBuilder {
     pub fn new() -> Self {
         Self {
             importers: ImporterRepo::new(),
+            placeholders: Vec::new(),
             resources: ResourceManager::new(),
             components: ComponentManager::new(HashMap::new()),
             scenes: ScenesBuilder::new(),
+            systems: Scheduler::new(),
+            plugins: Plugins::new(),
+            render_graph: RenderGraphBuilder::new(),
+            game_config: GameConfig::default(),
+            gpu: None,
+            config: None,
+            timer: None,
         }
     }
 
+    pub fn with_config(mut self, config: GameConfig) -> Self {
+        self.game_config = config;
+
+        self
+    }
+
     pub fn with_importer<T: Asset, U: AssetImporter<T>>(mut self, importer: U) -> Self {
-        self.importers.add_importer::<T, U>(importer);
+        self.add_importer::<T, U>(importer);
+
+        self
+    }
+
+    pub fn with_importer_priority<T: Asset, U: AssetImporter<T>>(
+        mut self,
+        importer: U,
+        priority: i32,
+    ) -> Self {
+        self.add_importer_with_priority::<T, U>(importer, priority);
 
         self
     }
 
     pub fn with_resource<T: Resource>(mut self, resource: T) -> Self {
-        self.resources.register(resource);
+        self.add_resource(resource);
 
         self
     }
 
     pub fn with_component<T: Component>(mut self) -> Self {
-        self.components.register::<T>();
+        self.add_component::<T>();
 
         self
     }
 
     pub fn with_scene<T: Scene>(mut self, scene: T) -> Self {
-        self.scenes.add_scene(scene);
+        self.add_scene(scene);
+
+        self
+    }
+
+    pub fn with_system<T: System>(mut self, phase: ScenePhase, system: T) -> Self {
+        self.add_system(phase, system);
+
+        self
+    }
+
+    pub fn with_plugin<T: Plugin>(mut self, plugin: T) -> Self {
+        self.add_plugin(plugin);
 
         self
     }
 
+    pub fn with_plugins<T: PluginGroup>(mut self, group: T) -> Self {
+        self.add_plugins(group);
+
+        self
+    }
+
+    pub fn add_importer<T: Asset, U: AssetImporter<T>>(&mut self, importer: U) {
+        self.importers.add_importer::<T, U>(importer);
+    }
+
+    pub fn add_importer_with_priority<T: Asset, U: AssetImporter<T>>(
+        &mut self,
+        importer: U,
+        priority: i32,
+    ) {
+        self.importers
+            .add_importer_with_priority::<T, U>(importer, priority);
+    }
+
+    pub fn add_placeholder<T: Asset>(
+        &mut self,
+        placeholder: impl Fn() -> T + Send + Sync + 'static,
+    ) {
+        self.placeholders
+            .push(Box::new(move |db| db.set_placeholder(placeholder)));
+    }
+
+    pub fn add_resource<T: Resource>(&mut self, resource: T) {
+        self.resources.register(resource);
+    }
+
+    pub fn add_component<T: Component>(&mut self) {
+        self.components.register::<T>();
+    }
+
+    pub fn add_scene<T: Scene>(&mut self, scene: T) {
+        self.scenes.add_scene(scene);
+    }
+
+    pub fn add_system<T: System>(&mut self, phase: ScenePhase, system: T) {
+        self.systems.add_system(phase, system);
+    }
+
+    pub fn add_plugin<T: Plugin>(&mut self, plugin: T) {
+        self.plugins.add(plugin_id::<T>(), Box::new(plugin));
+    }
+
+    pub fn add_plugins<T: PluginGroup>(&mut self, group: T) {
+        for (id, plugin) in group.build().into_plugins() {
+            self.plugins.add(id, plugin);
+        }
+    }
+
+    pub fn add_render_node<T: RenderGraphNodeBuilder>(&mut self, name: &str, node: T) {
+        self.render_graph.add_node(name, node);
+    }
+
+    pub fn add_render_texture(&mut self, name: &str, info: TextureInfo) {
+        self.render_graph.add_texture(name, info);
+    }
+
+    pub fn add_render_buffer(&mut self, name: &str, info: BufferInfo) {
+        self.render_graph.add_buffer(name, info);
+    }
+
+    pub fn has_plugin<T: Plugin>(&self) -> bool {
+        self.plugins.contains(&plugin_id::<T>())
+    }
+
+    pub fn render_graph(&self) -> &RenderGraphBuilder {
+        &self.render_graph
+    }
+
+    pub fn importers(&self) -> &ImporterRepo {
+        &self.importers
+    }
+
+    pub fn take_placeholders(&mut self) -> Vec<Placeholder> {
+        std::mem::take(&mut self.placeholders)
+    }
+
+    pub fn resources(&self) -> &ResourceManager {
+        &self.resources
+    }
+
+    pub fn game_config(&self) -> &GameConfig {
+        &self.game_config
+    }
+
+    pub fn gpu(&self) -> Option<&Rc<Gpu>> {
+        self.gpu.as_ref()
+    }
+
+    pub fn graphics_config(&self) -> Option<Config> {
+        self.config
+    }
+
+    pub fn headless(&self) -> bool {
+        self.gpu.is_none()
+    }
+
+    pub fn timer(&self) -> &Rc<RefCell<Timer>> {
+        self.timer
+            .as_ref()
+            .expect("Timer is only available while building plugins")
+    }
+
     pub fn run<T: Scene>(self) {
         GameRunner::run::<T>(self)
     }
+
+    pub fn run_headless<T: Scene>(self) {
+        GameRunner::run_headless::<T>(self)
+    }
+
+    fn build_plugins(&mut self) {
+        let mut built = Vec::new();
+        while !self.plugins.is_empty() {
+            let plugins = self.plugins.take_sorted();
+            for plugin in &plugins {
+                plugin.build(self);
+            }
+
+            built.extend(plugins);
+        }
+
+        for plugin in &built {
+            plugin.finish(self);
+        }
+    }
 }
@@ -0,0 +1,174 @@
+use super::{GameBuilder, TimePlugin};
+use crate::{
+    asset::plugin::AssetPlugin, console::ConsolePlugin, graphics::plugin::GraphicsPlugin,
+    input::InputPlugin, shared::ResourceType,
+};
+use std::{any::TypeId, collections::HashSet};
+
+pub type PluginId = ResourceType;
+
+pub trait Plugin: 'static {
+    fn build(&self, game: &mut GameBuilder);
+
+    fn finish(&self, _game: &mut GameBuilder) {}
+
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![]
+    }
+}
+
+pub fn plugin_id<T: Plugin>() -> PluginId {
+    TypeId::of::<T>().into()
+}
+
+pub trait PluginGroup {
+    fn build(self) -> PluginGroupBuilder;
+}
+
+pub struct PluginGroupBuilder {
+    plugins: Vec<(PluginId, Box<dyn Plugin>)>,
+}
+
+impl PluginGroupBuilder {
+    pub fn new() -> Self {
+        Self {
+            plugins: Vec::new(),
+        }
+    }
+
+    pub fn with<T: Plugin>(mut self, plugin: T) -> Self {
+        let id = plugin_id::<T>();
+        if self.plugins.iter().any(|(plugin_id, _)| *plugin_id == id) {
+            panic!("Plugin added to group more than once: {}", plugin.name());
+        }
+
+        self.plugins.push((id, Box::new(plugin)));
+        self
+    }
+
+    pub fn set<T: Plugin>(mut self, plugin: T) -> Self {
+        let id = plugin_id::<T>();
+        match self
+            .plugins
+            .iter_mut()
+            .find(|(plugin_id, _)| *plugin_id == id)
+        {
+            Some((_, existing)) => *existing = Box::new(plugin),
+            None => panic!("Plugin doesn't exist in group: {}", plugin.name()),
+        }
+
+        self
+    }
+
+    pub fn disable<T: Plugin>(mut self) -> Self {
+        let id = plugin_id::<T>();
+        self.plugins.retain(|(plugin_id, _)| *plugin_id != id);
+        self
+    }
+
+    pub(super) fn into_plugins(self) -> Vec<(PluginId, Box<dyn Plugin>)> {
+        self.plugins
+    }
+}
+
+impl PluginGroup for PluginGroupBuilder {
+    fn build(self) -> PluginGroupBuilder {
+        self
+    }
+}
+
+pub struct DefaultPlugins;
+
+impl PluginGroup for DefaultPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::new()
+            .with(TimePlugin)
+            .with(InputPlugin::new())
+            .with(GraphicsPlugin)
+            .with(AssetPlugin::default())
+            .with(ConsolePlugin::new())
+    }
+}
+
+pub struct Plugins {
+    pending: Vec<(PluginId, Box<dyn Plugin>)>,
+    added: HashSet<PluginId>,
+    built: HashSet<PluginId>,
+}
+
+impl Plugins {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            added: HashSet::new(),
+            built: HashSet::new(),
+        }
+    }
+
+    pub fn add(&mut self, id: PluginId, plugin: Box<dyn Plugin>) {
+        if !self.added.insert(id) {
+            panic!("Plugin added more than once: {}", plugin.name());
+        }
+
+        self.pending.push((id, plugin));
+    }
+
+    pub fn contains(&self, id: &PluginId) -> bool {
+        self.added.contains(id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub(super) fn take_sorted(&mut self) -> Vec<Box<dyn Plugin>> {
+        let mut sorted = Vec::new();
+        while let Some(index) = self.pending.iter().position(|(_, plugin)| {
+            plugin
+                .dependencies()
+                .iter()
+                .all(|dependency| self.built.contains(dependency))
+        }) {
+            let (id, plugin) = self.pending.remove(index);
+            self.built.insert(id);
+            sorted.push(plugin);
+        }
+
+        if sorted.is_empty() && !self.pending.is_empty() {
+            self.unresolved();
+        }
+
+        sorted
+    }
+
+    fn unresolved(&self) -> ! {
+        let pending = self
+            .pending
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<HashSet<_>>();
+
+        for (_, plugin) in &self.pending {
+            for dependency in plugin.dependencies() {
+                if !self.built.contains(&dependency) && !pending.contains(&dependency) {
+                    panic!(
+                        "Plugin {} depends on a plugin that was never added",
+                        plugin.name()
+                    );
+                }
+            }
+        }
+
+        let cycle = self
+            .pending
+            .iter()
+            .map(|(_, plugin)| plugin.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        panic!("Plugin dependency cycle detected between: {}", cycle);
+    }
+}
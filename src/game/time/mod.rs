@@ -4,6 +4,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use super::{GameBuilder, Plugin};
 use crate::ecs::Resource;
 
 pub struct Timer {
@@ -11,6 +12,8 @@ pub struct Timer {
     last: Instant,
     delta: Duration,
     fixed_delta: Duration,
+    scale: f64,
+    step: u64,
 }
 
 impl Timer {
@@ -20,15 +23,26 @@ impl Timer {
             last: Instant::now(),
             delta: Duration::from_secs(0),
             fixed_delta,
+            scale: 1.0,
+            step: 0,
         }
     }
 
     pub(super) fn update(&mut self) {
         let now = Instant::now();
-        self.delta = now - self.last;
+        self.delta = (now - self.last).mul_f64(self.scale);
         self.last = now;
     }
 
+    pub(crate) fn advance(&mut self, delta: Duration) {
+        self.delta = delta.mul_f64(self.scale);
+        self.last += delta;
+    }
+
+    pub(crate) fn step(&mut self) {
+        self.step += 1;
+    }
+
     pub fn start(&self) -> Instant {
         self.start
     }
@@ -44,6 +58,14 @@ impl Timer {
     pub fn fixed_delta(&self) -> Duration {
         self.fixed_delta
     }
+
+    pub fn steps(&self) -> u64 {
+        self.step
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
 }
 
 pub struct GameTime {
@@ -71,9 +93,26 @@ impl GameTime {
         self.timer.borrow().fixed_delta
     }
 
+    pub fn steps(&self) -> u64 {
+        self.timer.borrow().step
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.timer.borrow().scale
+    }
+
+    pub fn set_scale(&self, scale: f64) {
+        self.timer.borrow_mut().scale = scale;
+    }
+
     pub fn since_start(&self) -> Duration {
         Instant::now() - self.timer.borrow().start
     }
+
+    pub fn elapsed(&self) -> Duration {
+        let timer = self.timer.borrow();
+        timer.last - timer.start
+    }
 }
 
 impl Resource for GameTime {
@@ -85,3 +124,12 @@ impl Resource for GameTime {
         self
     }
 }
+
+pub struct TimePlugin;
+
+impl Plugin for TimePlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        let time = GameTime::new(game.timer().clone());
+        game.add_resource(time);
+    }
+}
@@ -1,8 +1,14 @@
 use crate::{
     ecs::Component,
-    graphics::{color::Color, frustum::Frustum, view::ProjectionMode},
+    graphics::{
+        color::Color,
+        frustum::Frustum,
+        view::{ProjectionMode, RenderTarget},
+    },
+    shared::Rect,
 };
 
+#[derive(Clone, Copy)]
 pub struct Camera {
     pub size: f32,
     pub near: f32,
@@ -11,6 +17,8 @@ pub struct Camera {
     pub order: usize,
     pub mode: ProjectionMode,
     pub clear_color: Color,
+    pub viewport: Rect,
+    pub target: RenderTarget,
 }
 
 impl Camera {
@@ -31,9 +39,21 @@ impl Camera {
             order,
             mode,
             clear_color,
+            viewport: Rect::new(0.0, 0.0, 1.0, 1.0),
+            target: RenderTarget::Window,
         }
     }
 
+    pub fn with_viewport(mut self, viewport: Rect) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn with_target(mut self, target: RenderTarget) -> Self {
+        self.target = target;
+        self
+    }
+
     pub fn orthographic(&self, aspect: f32) -> glam::Mat4 {
         let Self {
             size, near, far, ..
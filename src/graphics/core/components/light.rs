@@ -12,6 +12,7 @@ pub enum LightKind {
     Spot = 2,
 }
 
+#[derive(Clone, Copy)]
 pub struct Light {
     pub color: Color,
     pub intensity: f32,
@@ -1,20 +1,76 @@
 use crate::{
+    asset::{AssetDatabase, Handle},
     ecs::Component,
-    graphics::{MaterialId, MeshId},
+    graphics::{color::Color, material::MaterialInfo, mesh::MeshInfo, MaterialId, MeshId},
 };
 
 pub struct MeshElement {
-    pub mesh: MeshId,
-    pub material: MaterialId,
+    pub mesh: Handle<MeshInfo>,
+    pub material: Handle<MaterialInfo>,
+}
+
+impl MeshElement {
+    pub fn new(mesh: Handle<MeshInfo>, material: Handle<MaterialInfo>) -> Self {
+        Self { mesh, material }
+    }
+
+    pub fn from_ids(db: &AssetDatabase, mesh: MeshId, material: MaterialId) -> Self {
+        Self {
+            mesh: db.track(mesh),
+            material: db.track(material),
+        }
+    }
+
+    pub fn mesh_id(&self) -> MeshId {
+        self.mesh.id()
+    }
+
+    pub fn material_id(&self) -> MaterialId {
+        self.material.id()
+    }
 }
 
 pub struct MeshRenderer {
     pub elements: Vec<MeshElement>,
+    pub color: Option<Color>,
 }
 
 impl MeshRenderer {
     pub fn new(elements: Vec<MeshElement>) -> Self {
-        Self { elements }
+        Self {
+            elements,
+            color: None,
+        }
+    }
+
+    pub fn from_ids(db: &AssetDatabase, mesh: MeshId, materials: &[MaterialId]) -> Self {
+        let elements = materials
+            .iter()
+            .map(|material| MeshElement::from_ids(db, mesh, *material))
+            .collect();
+
+        Self::new(elements)
+    }
+
+    pub fn from_handles(mesh: &Handle<MeshInfo>, materials: &[Handle<MaterialInfo>]) -> Self {
+        let elements = materials
+            .iter()
+            .map(|material| MeshElement::new(mesh.clone(), material.clone()))
+            .collect();
+
+        Self::new(elements)
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn materials(&self) -> Vec<MaterialId> {
+        self.elements
+            .iter()
+            .map(|element| element.material_id())
+            .collect()
     }
 }
 
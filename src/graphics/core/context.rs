@@ -1,35 +1,52 @@
-use super::view::RenderView;
-use crate::graphics::{Graphics, TextureId};
-use std::collections::HashMap;
+use super::{view::RenderView, DrawMesh, ViewVisibility};
+use crate::{
+    graphics::{renderer::graph::GraphContext, Graphics},
+    shared::Rect,
+};
 
 pub struct RenderContext<'a> {
-    graphics: &'a Graphics,
+    graph: &'a GraphContext<'a>,
+    index: usize,
     view: &'a RenderView,
-    textures: &'a HashMap<TextureId, wgpu::TextureView>,
+    viewport: Rect,
 }
 
 impl<'a> RenderContext<'a> {
-    pub fn new(
-        graphics: &'a Graphics,
-        view: &'a RenderView,
-        textures: &'a HashMap<TextureId, wgpu::TextureView>,
-    ) -> Self {
+    pub fn new(graph: &'a GraphContext<'a>, index: usize, viewport: Rect) -> Self {
         Self {
-            graphics,
-            view,
-            textures,
+            graph,
+            index,
+            view: &graph.graphics().views()[index],
+            viewport,
         }
     }
 
     pub fn graphics(&self) -> &'a Graphics {
-        self.graphics
+        self.graph.graphics()
     }
 
     pub fn view(&self) -> &'a RenderView {
         self.view
     }
 
-    pub fn texture(&self, id: TextureId) -> &'a wgpu::TextureView {
-        self.textures.get(&id).unwrap()
+    pub fn visibility(&self) -> Option<&'a ViewVisibility> {
+        self.graphics().scene().visibility(self.index)
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &'a DrawMesh> {
+        self.graphics().scene().visible(self.index)
+    }
+
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    pub fn projection(&self) -> glam::Mat4 {
+        self.view
+            .projection(self.viewport.width / self.viewport.height)
+    }
+
+    pub fn texture(&self, name: &str) -> &'a wgpu::TextureView {
+        self.graph.texture(name).unwrap()
     }
 }
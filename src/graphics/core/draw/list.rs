@@ -0,0 +1,605 @@
+use super::DrawMesh;
+use crate::graphics::{
+    color::Color,
+    context::RenderContext,
+    material::{BlendMode, Material, ShaderModel},
+    mesh::SubMesh,
+    renderer::node::RenderPassKind,
+    shader::{CameraData, InstanceData},
+    Graphics, MaterialId, MeshId,
+};
+use std::ops::Range;
+
+const PASS_SHIFT: u128 = 126;
+const BLEND_SHIFT: u128 = 125;
+const ID_BITS: u128 = 31;
+const ID_MASK: u128 = (1 << ID_BITS) - 1;
+const DEPTH_BITS: u128 = 32;
+const DEPTH_MASK: u128 = (1 << DEPTH_BITS) - 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey(u128);
+
+impl SortKey {
+    pub fn opaque(
+        pass: RenderPassKind,
+        program: usize,
+        material: usize,
+        mesh: usize,
+        depth: f32,
+    ) -> Self {
+        let state = Self::state(program, material, mesh);
+        Self(Self::header(pass, BlendMode::Opaque) | state << DEPTH_BITS | Self::depth(depth))
+    }
+
+    pub fn translucent(
+        pass: RenderPassKind,
+        program: usize,
+        material: usize,
+        mesh: usize,
+        depth: f32,
+    ) -> Self {
+        let depth = DEPTH_MASK - Self::depth(depth);
+        let state = Self::state(program, material, mesh);
+        Self(Self::header(pass, BlendMode::Translucent) | depth << (ID_BITS * 3) | state)
+    }
+
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+
+    pub fn pass(&self) -> u128 {
+        self.0 >> PASS_SHIFT
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        match (self.0 >> BLEND_SHIFT) & 1 {
+            0 => BlendMode::Opaque,
+            _ => BlendMode::Translucent,
+        }
+    }
+
+    fn header(pass: RenderPassKind, blend_mode: BlendMode) -> u128 {
+        let blend = match blend_mode {
+            BlendMode::Opaque => 0,
+            BlendMode::Translucent => 1,
+        };
+
+        (pass as u128) << PASS_SHIFT | blend << BLEND_SHIFT
+    }
+
+    fn state(program: usize, material: usize, mesh: usize) -> u128 {
+        debug_assert!(
+            [program, material, mesh]
+                .iter()
+                .all(|index| *index as u128 <= ID_MASK),
+            "Draw state index out of range for a sort key"
+        );
+
+        (program as u128 & ID_MASK) << (ID_BITS * 2)
+            | (material as u128 & ID_MASK) << ID_BITS
+            | (mesh as u128 & ID_MASK)
+    }
+
+    fn depth(depth: f32) -> u128 {
+        (depth.clamp(0.0, 1.0) as f64 * DEPTH_MASK as f64) as u128
+    }
+}
+
+pub struct DrawItem {
+    key: SortKey,
+    program: usize,
+    material: MaterialId,
+    mesh: MeshId,
+    submesh: SubMesh,
+    transform: glam::Mat4,
+    color: Option<Color>,
+}
+
+impl DrawItem {
+    pub fn key(&self) -> SortKey {
+        self.key
+    }
+
+    pub fn program(&self) -> usize {
+        self.program
+    }
+
+    pub fn material(&self) -> MaterialId {
+        self.material
+    }
+
+    pub fn mesh(&self) -> MeshId {
+        self.mesh
+    }
+
+    pub fn submesh(&self) -> SubMesh {
+        self.submesh
+    }
+
+    pub fn transform(&self) -> &glam::Mat4 {
+        &self.transform
+    }
+
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    pub fn instance(&self) -> InstanceData {
+        let color = self.color.unwrap_or(Color::white());
+        InstanceData::new(self.transform, color.into())
+    }
+
+    fn batches_with(&self, other: &DrawItem) -> bool {
+        self.program == other.program
+            && self.material == other.material
+            && self.mesh == other.mesh
+            && self.submesh == other.submesh
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DrawBatch {
+    item: usize,
+    instances: Range<u32>,
+}
+
+impl DrawBatch {
+    pub fn item(&self) -> usize {
+        self.item
+    }
+
+    pub fn instances(&self) -> Range<u32> {
+        self.instances.clone()
+    }
+
+    pub fn instance_count(&self) -> u32 {
+        self.instances.end - self.instances.start
+    }
+}
+
+pub struct DrawBuffers {
+    instances: wgpu::Buffer,
+    lit: Option<wgpu::BindGroup>,
+    unlit: Option<wgpu::BindGroup>,
+}
+
+impl DrawBuffers {
+    pub fn instances(&self) -> &wgpu::Buffer {
+        &self.instances
+    }
+
+    pub fn global(&self, model: ShaderModel) -> Option<&wgpu::BindGroup> {
+        match model {
+            ShaderModel::Lit => self.lit.as_ref(),
+            ShaderModel::Unlit => self.unlit.as_ref(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawCommand {
+    SetPipeline(usize),
+    SetMaterial(MaterialId),
+    SetMesh(MeshId),
+    Draw(usize),
+}
+
+pub struct DrawList {
+    pass: RenderPassKind,
+    programs: Vec<Material>,
+    materials: Vec<MaterialId>,
+    meshes: Vec<MeshId>,
+    items: Vec<DrawItem>,
+}
+
+impl DrawList {
+    pub fn new(pass: RenderPassKind) -> Self {
+        Self {
+            pass,
+            programs: Vec::new(),
+            materials: Vec::new(),
+            meshes: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn build(pass: RenderPassKind, ctx: &RenderContext) -> Self {
+        let graphics = ctx.graphics();
+        let view = ctx.view();
+        let position = view.view().inverse().w_axis.truncate();
+        let far = view.far().max(f32::EPSILON);
+
+        let mut list = Self::new(pass);
+        for draw in ctx.visible() {
+            let Some(mesh) = graphics.mesh(&draw.mesh()) else {
+                continue;
+            };
+
+            let depth = position.distance(draw.bounds().center()) / far;
+            for (submesh, id) in mesh.submeshes().iter().zip(draw.materials()) {
+                let Some(material) = graphics.material(id) else {
+                    continue;
+                };
+
+                let ready = graphics
+                    .shader_program(material)
+                    .is_some_and(|program| program.bind_group(id).is_some());
+                if ready {
+                    list.push(material, *id, draw, *submesh, depth);
+                }
+            }
+        }
+
+        list.sort();
+        list
+    }
+
+    pub fn push(
+        &mut self,
+        material: &Material,
+        id: MaterialId,
+        draw: &DrawMesh,
+        submesh: SubMesh,
+        depth: f32,
+    ) {
+        let mesh = draw.mesh();
+        let program = Self::intern(&mut self.programs, material.clone());
+        let material_index = Self::intern(&mut self.materials, id);
+        let mesh_index = Self::intern(&mut self.meshes, mesh);
+
+        let key = match material.blend_mode() {
+            BlendMode::Opaque => {
+                SortKey::opaque(self.pass, program, material_index, mesh_index, depth)
+            }
+            BlendMode::Translucent => {
+                SortKey::translucent(self.pass, program, material_index, mesh_index, depth)
+            }
+        };
+
+        self.items.push(DrawItem {
+            key,
+            program,
+            material: id,
+            mesh,
+            submesh,
+            transform: *draw.transform(),
+            color: draw.color(),
+        });
+    }
+
+    pub fn sort(&mut self) {
+        self.items.sort_by_key(|item| item.key);
+    }
+
+    pub fn items(&self) -> &[DrawItem] {
+        &self.items
+    }
+
+    pub fn program(&self, index: usize) -> &Material {
+        &self.programs[index]
+    }
+
+    pub fn batches(&self) -> Vec<DrawBatch> {
+        let mut batches: Vec<DrawBatch> = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            match batches.last_mut() {
+                Some(batch) if self.items[batch.item].batches_with(item) => {
+                    batch.instances.end += 1;
+                }
+                _ => batches.push(DrawBatch {
+                    item: index,
+                    instances: index as u32..index as u32 + 1,
+                }),
+            }
+        }
+
+        batches
+    }
+
+    pub fn instances(&self) -> Vec<InstanceData> {
+        self.items.iter().map(DrawItem::instance).collect()
+    }
+
+    pub fn commands(&self) -> Vec<DrawCommand> {
+        let mut commands = Vec::new();
+        let mut program = None;
+        let mut material = None;
+        let mut mesh = None;
+
+        for (index, batch) in self.batches().iter().enumerate() {
+            let item = &self.items[batch.item];
+            if program != Some(item.program) {
+                commands.push(DrawCommand::SetPipeline(item.program));
+                program = Some(item.program);
+                material = None;
+            }
+
+            if material != Some(item.material) {
+                commands.push(DrawCommand::SetMaterial(item.material));
+                material = Some(item.material);
+            }
+
+            if mesh != Some(item.mesh) {
+                commands.push(DrawCommand::SetMesh(item.mesh));
+                mesh = Some(item.mesh);
+            }
+
+            commands.push(DrawCommand::Draw(index));
+        }
+
+        commands
+    }
+
+    pub fn upload(&self, ctx: &RenderContext) -> Option<DrawBuffers> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        let graphics = ctx.graphics();
+        let device = graphics.gpu().device();
+        let resources = graphics.shader_resources();
+
+        let camera = CameraData::new(*ctx.view().view(), ctx.projection());
+        let camera = graphics.create_uniform_buffer(bytemuck::bytes_of(&camera));
+        let uses = |model: ShaderModel| self.programs.iter().any(|p| p.shader_model() == model);
+
+        let lit = uses(ShaderModel::Lit).then(|| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("lit_global"),
+                layout: resources.lit_global_layout(),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: camera.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: resources.lights().as_entire_binding(),
+                    },
+                ],
+            })
+        });
+
+        let unlit = uses(ShaderModel::Unlit).then(|| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("unlit_global"),
+                layout: resources.unlit_global_layout(),
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: camera.as_entire_binding(),
+                }],
+            })
+        });
+
+        Some(DrawBuffers {
+            instances: graphics.create_instance_buffer(&self.instances()),
+            lit,
+            unlit,
+        })
+    }
+
+    pub fn execute<'a>(
+        &self,
+        graphics: &'a Graphics,
+        buffers: &'a DrawBuffers,
+        pass: &mut wgpu::RenderPass<'a>,
+    ) {
+        let batches = self.batches();
+        let mut program = None;
+
+        pass.set_vertex_buffer(1, buffers.instances().slice(..));
+        for command in self.commands() {
+            match command {
+                DrawCommand::SetPipeline(index) => {
+                    program = graphics.shader_program(&self.programs[index]);
+                    if let Some(program) = program {
+                        pass.set_pipeline(program.pipeline());
+                        if let Some(global) = buffers.global(program.model()) {
+                            pass.set_bind_group(0, global, &[]);
+                        }
+                    }
+                }
+                DrawCommand::SetMaterial(id) => {
+                    if let Some(bind_group) = program.and_then(|program| program.bind_group(&id)) {
+                        pass.set_bind_group(1, bind_group, &[]);
+                    }
+                }
+                DrawCommand::SetMesh(id) => {
+                    if let Some(mesh) = graphics.mesh(&id) {
+                        pass.set_vertex_buffer(0, mesh.vertex_buffer().slice(..));
+                        pass.set_index_buffer(
+                            mesh.index_buffer().slice(..),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                    }
+                }
+                DrawCommand::Draw(index) => {
+                    let batch = &batches[index];
+                    let submesh = self.items[batch.item].submesh;
+                    let start = submesh.index_start;
+                    pass.draw_indexed(start..start + submesh.index_count, 0, batch.instances());
+                }
+            }
+        }
+    }
+
+    fn intern<T: PartialEq>(values: &mut Vec<T>, value: T) -> usize {
+        match values.iter().position(|v| *v == value) {
+            Some(index) => index,
+            None => {
+                values.push(value);
+                values.len() - 1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{Bounds, ResourceId};
+
+    const MESH_A: u64 = 1;
+    const MESH_B: u64 = 2;
+
+    fn material(model: ShaderModel, blend_mode: BlendMode) -> Material {
+        Material::builder()
+            .shader_model(model)
+            .blend_mode(blend_mode)
+            .build()
+    }
+
+    fn id(id: u64) -> ResourceId {
+        ResourceId::new(id)
+    }
+
+    fn submesh(index: u32) -> SubMesh {
+        SubMesh {
+            index_start: index * 6,
+            index_count: 6,
+        }
+    }
+
+    fn push(
+        list: &mut DrawList,
+        material: &Material,
+        id: u64,
+        mesh: u64,
+        submesh: SubMesh,
+        depth: f32,
+    ) {
+        let index = list.items().len() as f32;
+        let transform = glam::Mat4::from_translation(glam::Vec3::X * index);
+        let draw = DrawMesh::new(transform, ResourceId::new(mesh), vec![], Bounds::ZERO);
+        list.push(material, ResourceId::new(id), &draw, submesh, depth);
+    }
+
+    fn order(list: &DrawList) -> Vec<usize> {
+        list.items()
+            .iter()
+            .map(|item| item.transform().w_axis.x as usize)
+            .collect()
+    }
+
+    #[test]
+    fn commands_skip_redundant_state_changes() {
+        let lit = material(ShaderModel::Lit, BlendMode::Opaque);
+        let unlit = material(ShaderModel::Unlit, BlendMode::Opaque);
+        let glass = material(ShaderModel::Lit, BlendMode::Translucent);
+
+        let mut list = DrawList::new(RenderPassKind::Forward);
+        push(&mut list, &lit, 10, MESH_A, submesh(0), 0.5);
+        push(&mut list, &glass, 30, MESH_A, submesh(0), 0.2);
+        push(&mut list, &lit, 10, MESH_A, submesh(0), 0.1);
+        push(&mut list, &unlit, 20, MESH_B, submesh(0), 0.3);
+        push(&mut list, &lit, 10, MESH_B, submesh(0), 0.4);
+        push(&mut list, &glass, 30, MESH_B, submesh(0), 0.9);
+        push(&mut list, &lit, 40, MESH_A, submesh(0), 0.05);
+        list.sort();
+
+        assert_eq!(order(&list), vec![2, 0, 4, 6, 3, 5, 1]);
+        assert_eq!(list.batches()[0].instances(), 0..2);
+
+        let (lit, glass, unlit) = (0, 1, 2);
+        assert_eq!(
+            list.commands(),
+            vec![
+                DrawCommand::SetPipeline(lit),
+                DrawCommand::SetMaterial(id(10)),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(0),
+                DrawCommand::SetMesh(id(MESH_B)),
+                DrawCommand::Draw(1),
+                DrawCommand::SetMaterial(id(40)),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(2),
+                DrawCommand::SetPipeline(unlit),
+                DrawCommand::SetMaterial(id(20)),
+                DrawCommand::SetMesh(id(MESH_B)),
+                DrawCommand::Draw(3),
+                DrawCommand::SetPipeline(glass),
+                DrawCommand::SetMaterial(id(30)),
+                DrawCommand::Draw(4),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn opaque_draws_sort_front_to_back() {
+        let lit = material(ShaderModel::Lit, BlendMode::Opaque);
+
+        let mut list = DrawList::new(RenderPassKind::Forward);
+        push(&mut list, &lit, 10, MESH_A, submesh(0), 0.7);
+        push(&mut list, &lit, 10, MESH_A, submesh(1), 0.3);
+        push(&mut list, &lit, 10, MESH_A, submesh(2), 0.5);
+        push(&mut list, &lit, 10, MESH_A, submesh(3), 0.1);
+        list.sort();
+
+        assert_eq!(order(&list), vec![3, 1, 2, 0]);
+        assert_eq!(
+            list.commands(),
+            vec![
+                DrawCommand::SetPipeline(0),
+                DrawCommand::SetMaterial(id(10)),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(0),
+                DrawCommand::Draw(1),
+                DrawCommand::Draw(2),
+                DrawCommand::Draw(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn translucent_draws_sort_back_to_front_after_opaque() {
+        let lit = material(ShaderModel::Lit, BlendMode::Opaque);
+        let glass = material(ShaderModel::Lit, BlendMode::Translucent);
+        let tinted = material(ShaderModel::Unlit, BlendMode::Translucent);
+
+        let mut list = DrawList::new(RenderPassKind::Forward);
+        push(&mut list, &glass, 30, MESH_A, submesh(0), 0.2);
+        push(&mut list, &tinted, 50, MESH_B, submesh(0), 0.6);
+        push(&mut list, &lit, 10, MESH_A, submesh(0), 0.9);
+        push(&mut list, &glass, 30, MESH_A, submesh(0), 0.8);
+        push(&mut list, &tinted, 50, MESH_B, submesh(0), 0.4);
+        list.sort();
+
+        assert_eq!(order(&list), vec![2, 3, 1, 4, 0]);
+        assert_eq!(list.batches()[2].instances(), 2..4);
+        assert_eq!(
+            list.commands(),
+            vec![
+                DrawCommand::SetPipeline(2),
+                DrawCommand::SetMaterial(id(10)),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(0),
+                DrawCommand::SetPipeline(0),
+                DrawCommand::SetMaterial(id(30)),
+                DrawCommand::Draw(1),
+                DrawCommand::SetPipeline(1),
+                DrawCommand::SetMaterial(id(50)),
+                DrawCommand::SetMesh(id(MESH_B)),
+                DrawCommand::Draw(2),
+                DrawCommand::SetPipeline(0),
+                DrawCommand::SetMaterial(id(30)),
+                DrawCommand::SetMesh(id(MESH_A)),
+                DrawCommand::Draw(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_keys_keep_large_indices_apart() {
+        let pass = RenderPassKind::Forward;
+        let low = SortKey::opaque(pass, 0, 1, 0, 0.5);
+        let high = SortKey::opaque(pass, 0, 4097, 0, 0.5);
+        assert!(low < high);
+
+        let near = SortKey::translucent(pass, 0, 4096, 0, 0.1);
+        let far = SortKey::translucent(pass, 0, 0, 0, 0.9);
+        assert!(far < near);
+    }
+}
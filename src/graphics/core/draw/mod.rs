@@ -1,18 +1,26 @@
 use super::{MaterialId, MeshId};
 use crate::{
-    graphics::light::{Light2D, Light3D, LightRef},
-    shared::{Bounds, Rect},
-    spatial::{
-        octtree::{Object3D, OctTree},
-        quadtree::QuadTree,
+    graphics::{
+        color::Color,
+        light::{Light2D, Light3D, LightRef},
+        view::RenderView,
     },
+    shared::{Bounds, Rect},
+    spatial::{octtree::OctTree, quadtree::QuadTree},
 };
 
+pub mod list;
+pub mod visibility;
+
+pub use list::*;
+pub use visibility::*;
+
 pub struct DrawMesh {
     transform: glam::Mat4,
     mesh: MeshId,
     materials: Vec<MaterialId>,
     bounds: Bounds,
+    color: Option<Color>,
 }
 
 impl DrawMesh {
@@ -27,9 +35,15 @@ impl DrawMesh {
             mesh,
             materials,
             bounds,
+            color: None,
         }
     }
 
+    pub fn with_color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
     pub fn transform(&self) -> &glam::Mat4 {
         &self.transform
     }
@@ -45,16 +59,17 @@ impl DrawMesh {
     pub fn bounds(&self) -> &Bounds {
         &self.bounds
     }
-}
 
-impl Object3D for DrawMesh {
-    fn bounds(&self) -> &Bounds {
-        &self.bounds
+    pub fn color(&self) -> Option<Color> {
+        self.color
     }
 }
 
 pub struct RenderScene {
-    meshes: OctTree<DrawMesh>,
+    views: Vec<RenderView>,
+    visibility: Vec<ViewVisibility>,
+    draws: Vec<DrawMesh>,
+    meshes: OctTree<DrawBounds>,
     lights3d: OctTree<Light3D>,
     lights2d: QuadTree<Light2D>,
 }
@@ -62,14 +77,69 @@ pub struct RenderScene {
 impl RenderScene {
     pub fn new() -> RenderScene {
         RenderScene {
-            meshes: OctTree::new(Bounds::MAX, 8, 8), 
+            views: Vec::new(),
+            visibility: Vec::new(),
+            draws: Vec::new(),
+            meshes: OctTree::new(Bounds::ZERO, 8, 8),
             lights3d: OctTree::new(Bounds::MAX, 8, 8),
             lights2d: QuadTree::new(Rect::MAX, 8, 8),
         }
     }
 
+    pub fn views(&self) -> &[RenderView] {
+        &self.views
+    }
+
+    pub fn set_views(&mut self, views: Vec<RenderView>) {
+        self.views = views;
+    }
+
+    pub fn draws(&self) -> &[DrawMesh] {
+        &self.draws
+    }
+
     pub fn add_mesh(&mut self, mesh: DrawMesh) {
-        self.meshes.insert(mesh);
+        self.draws.push(mesh);
+    }
+
+    pub fn clear_meshes(&mut self) {
+        self.draws.clear();
+        self.meshes.clear();
+    }
+
+    pub fn cull(&mut self, aspect: impl Fn(&RenderView) -> f32) {
+        let bounds = self.draws.iter().map(|draw| draw.bounds).collect::<Vec<_>>();
+        self.meshes = OctTree::new(Bounds::from_bounds(&bounds), 8, 8);
+        for (index, bounds) in bounds.into_iter().enumerate() {
+            self.meshes.insert(DrawBounds::new(index, bounds));
+        }
+
+        self.visibility = self
+            .views
+            .iter()
+            .map(|view| cull(&self.meshes, self.draws.len(), &view.frustum(aspect(view))))
+            .collect();
+    }
+
+    pub fn visibility(&self, view: usize) -> Option<&ViewVisibility> {
+        self.visibility.get(view)
+    }
+
+    pub fn visible(&self, view: usize) -> impl Iterator<Item = &DrawMesh> {
+        self.visibility
+            .get(view)
+            .map(|visibility| visibility.visible())
+            .unwrap_or_default()
+            .iter()
+            .map(|index| &self.draws[*index])
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.visibility.iter().map(|v| v.visible_count()).sum()
+    }
+
+    pub fn culled_count(&self) -> usize {
+        self.visibility.iter().map(|v| v.culled_count()).sum()
     }
 
     pub fn add_light(&mut self, light: LightRef) {
@@ -80,6 +150,9 @@ impl RenderScene {
     }
 
     pub fn clear(&mut self) {
+        self.views.clear();
+        self.visibility.clear();
+        self.draws.clear();
         self.meshes.clear();
         self.lights2d.clear();
         self.lights3d.clear();
@@ -0,0 +1,222 @@
+use crate::{
+    graphics::frustum::Frustum,
+    shared::{Bounds, Sphere},
+    spatial::octtree::{Object3D, OctTree},
+};
+
+pub struct DrawBounds {
+    index: usize,
+    bounds: Bounds,
+}
+
+impl DrawBounds {
+    pub fn new(index: usize, bounds: Bounds) -> Self {
+        Self { index, bounds }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Object3D for DrawBounds {
+    fn bounds(&self) -> &Bounds {
+        &self.bounds
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ViewVisibility {
+    visible: Vec<usize>,
+    culled: usize,
+}
+
+impl ViewVisibility {
+    pub fn visible(&self) -> &[usize] {
+        &self.visible
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.visible.len()
+    }
+
+    pub fn culled_count(&self) -> usize {
+        self.culled
+    }
+}
+
+pub fn is_visible(frustum: &Frustum, bounds: &Bounds) -> bool {
+    let sphere = Sphere::new(bounds.center(), bounds.extents().length());
+    frustum.intersects_sphere(&sphere) && frustum.intersects_bounds(bounds)
+}
+
+pub fn cull(tree: &OctTree<DrawBounds>, count: usize, frustum: &Frustum) -> ViewVisibility {
+    let mut visible = tree
+        .query_with(|bounds| is_visible(frustum, bounds))
+        .into_iter()
+        .map(|draw| draw.index)
+        .collect::<Vec<_>>();
+    visible.sort_unstable();
+
+    ViewVisibility {
+        culled: count.saturating_sub(visible.len()),
+        visible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::{
+        color::Color,
+        draw::{DrawMesh, RenderScene},
+        view::{ProjectionMode, RenderTarget, RenderView},
+        MeshId,
+    };
+
+    fn view_projection() -> glam::Mat4 {
+        let projection = glam::Mat4::perspective_rh(90f32.to_radians(), 1.0, 0.1, 100.0);
+        projection * view()
+    }
+
+    fn view() -> glam::Mat4 {
+        glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, 5.0),
+            glam::Vec3::ZERO,
+            glam::Vec3::Y,
+        )
+    }
+
+    fn cube(center: glam::Vec3) -> Bounds {
+        Bounds::new(
+            center - glam::Vec3::splat(0.5),
+            center + glam::Vec3::splat(0.5),
+        )
+    }
+
+    fn grid() -> Vec<Bounds> {
+        let mut bounds = Vec::new();
+        for x in -5..=5 {
+            for y in [-2.0, 2.0] {
+                for z in -3..=3 {
+                    bounds.push(cube(glam::Vec3::new(x as f32 * 4.0, y, z as f32 * 40.0)));
+                }
+            }
+        }
+
+        bounds
+    }
+
+    #[test]
+    fn is_visible_rejects_bounds_outside_any_plane() {
+        let frustum = Frustum::from_matrix(&view_projection());
+
+        assert!(is_visible(&frustum, &cube(glam::Vec3::ZERO)));
+        assert!(is_visible(&frustum, &cube(glam::Vec3::new(5.2, 0.0, 0.0))));
+        assert!(is_visible(
+            &frustum,
+            &cube(glam::Vec3::new(0.0, 0.0, -95.2))
+        ));
+
+        assert!(!is_visible(&frustum, &cube(glam::Vec3::new(7.0, 0.0, 0.0))));
+        assert!(!is_visible(
+            &frustum,
+            &cube(glam::Vec3::new(0.0, -7.0, 0.0))
+        ));
+        assert!(!is_visible(&frustum, &cube(glam::Vec3::new(0.0, 0.0, 6.0))));
+        assert!(!is_visible(
+            &frustum,
+            &cube(glam::Vec3::new(0.0, 0.0, -97.0))
+        ));
+    }
+
+    #[test]
+    fn cull_matches_testing_every_draw() {
+        let frustum = Frustum::from_matrix(&view_projection());
+        let bounds = grid();
+
+        let mut tree = OctTree::new(Bounds::from_bounds(&bounds), 8, 4);
+        for (index, bounds) in bounds.iter().enumerate() {
+            tree.insert(DrawBounds::new(index, *bounds));
+        }
+
+        let expected = bounds
+            .iter()
+            .enumerate()
+            .filter(|(_, bounds)| is_visible(&frustum, bounds))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let visibility = cull(&tree, bounds.len(), &frustum);
+
+        assert!(!expected.is_empty() && expected.len() < bounds.len());
+        assert_eq!(visibility.visible(), expected.as_slice());
+        assert_eq!(
+            visibility.visible_count() + visibility.culled_count(),
+            bounds.len()
+        );
+    }
+
+    #[test]
+    fn cull_skips_nodes_outside_the_frustum() {
+        let frustum = Frustum::from_matrix(&view_projection());
+        let bounds = grid();
+
+        let mut tree = OctTree::new(Bounds::from_bounds(&bounds), 8, 4);
+        for (index, bounds) in bounds.iter().enumerate() {
+            tree.insert(DrawBounds::new(index, *bounds));
+        }
+
+        let tested = std::cell::Cell::new(0);
+        tree.query_with(|node| {
+            if bounds.contains(node) {
+                tested.set(tested.get() + 1);
+            }
+            is_visible(&frustum, node)
+        });
+
+        assert!(tested.get() < bounds.len());
+    }
+
+    #[test]
+    fn render_scene_culls_each_view() {
+        let mut scene = RenderScene::new();
+        let camera = |view| {
+            RenderView::new(
+                view,
+                1.0,
+                0.1,
+                100.0,
+                90f32.to_radians(),
+                0,
+                ProjectionMode::Perspective,
+                Color::black(),
+                RenderTarget::Window,
+            )
+        };
+        let behind = glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, -5.0),
+            glam::Vec3::new(0.0, 0.0, -10.0),
+            glam::Vec3::Y,
+        );
+        scene.set_views(vec![camera(view()), camera(behind)]);
+
+        for bounds in [
+            cube(glam::Vec3::ZERO),
+            cube(glam::Vec3::new(0.0, 0.0, -20.0)),
+            cube(glam::Vec3::new(50.0, 0.0, 0.0)),
+        ] {
+            scene.add_mesh(DrawMesh::new(
+                glam::Mat4::IDENTITY,
+                MeshId::default(),
+                vec![],
+                bounds,
+            ));
+        }
+        scene.cull(|_| 1.0);
+
+        assert_eq!(scene.visibility(0).unwrap().visible(), &[0, 1]);
+        assert_eq!(scene.visibility(1).unwrap().visible(), &[1]);
+        assert_eq!(scene.visible_count(), 3);
+        assert_eq!(scene.culled_count(), 3);
+    }
+}
@@ -0,0 +1,774 @@
+use super::texture::decode_image;
+use crate::{
+    asset::{AssetImporter, BaseImporter, Handle, ImportContext, ImportError, ImportSettings},
+    ecs::builtin::Transform,
+    graphics::{
+        camera::Camera,
+        color::Color,
+        light::{Light, LightKind},
+        material::{BlendMode, MaterialInfo, ShaderInput, ShaderModel},
+        mesh::{MeshInfo, SubMesh},
+        prefab::{Prefab, PrefabNode},
+        texture::{
+            linear_to_srgb, srgb_to_linear, Dimension, FilterMode, Format, TextureInfo, WrapMode,
+        },
+        vertex::Vertex,
+        view::ProjectionMode,
+        MaterialId, TextureId,
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use glam::{Quat, Vec3};
+use gltf::{
+    camera::Projection,
+    khr_lights_punctual::Kind,
+    material::AlphaMode,
+    mesh::Mode,
+    texture::{MagFilter, MinFilter, WrappingMode},
+    Gltf,
+};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_FAR: f32 = 1000.0;
+const DEFAULT_FOV: f32 = std::f32::consts::FRAC_PI_3;
+const DEFAULT_LIGHT_RANGE: f32 = 100.0;
+
+type ImportedMesh = (Handle<MeshInfo>, Vec<Handle<MaterialInfo>>);
+
+struct GltfData {
+    buffers: Vec<Vec<u8>>,
+    textures: HashMap<(usize, bool), Option<TextureId>>,
+    meshes: Vec<Option<ImportedMesh>>,
+}
+
+pub struct GltfImporter;
+
+impl GltfImporter {
+    fn buffers(&self, ctx: &mut ImportContext, gltf: &Gltf) -> Result<Vec<Vec<u8>>, ImportError> {
+        let mut buffers = Vec::new();
+        for buffer in gltf.buffers() {
+            let bytes = match buffer.source() {
+                gltf::buffer::Source::Bin => gltf
+                    .blob
+                    .clone()
+                    .ok_or_else(|| ctx.error("Missing binary chunk"))?,
+                gltf::buffer::Source::Uri(uri) => match decode_data_uri(uri) {
+                    Some(bytes) => bytes.map_err(|e| ctx.error(e))?,
+                    None => ctx.read_path(decode_uri(uri)).map_err(|e| {
+                        ctx.error(format!("Failed to read buffer '{}': {}", uri, e))
+                    })?,
+                },
+            };
+
+            if bytes.len() < buffer.length() {
+                return Err(ctx.error(format!(
+                    "Buffer {} is {} bytes, expected {}",
+                    buffer.index(),
+                    bytes.len(),
+                    buffer.length()
+                )));
+            }
+
+            buffers.push(bytes);
+        }
+
+        Ok(buffers)
+    }
+
+    fn texture(
+        &self,
+        ctx: &mut ImportContext,
+        data: &mut GltfData,
+        texture: gltf::Texture,
+        srgb: bool,
+    ) -> Option<TextureId> {
+        let image = texture.source();
+        let key = (image.index(), srgb);
+        if let Some(id) = data.textures.get(&key) {
+            return *id;
+        }
+
+        let bytes = match image.source() {
+            gltf::image::Source::View { view, .. } => {
+                let buffer = &data.buffers[view.buffer().index()];
+                Ok(buffer[view.offset()..view.offset() + view.length()].to_vec())
+            }
+            gltf::image::Source::Uri { uri, .. } => match decode_data_uri(uri) {
+                Some(bytes) => bytes,
+                None => {
+                    let settings = ImportSettings::new().with("srgb", srgb);
+                    let id = ctx.load_dependency_with(decode_uri(uri), &settings);
+                    data.textures.insert(key, id);
+                    return id;
+                }
+            },
+        };
+
+        let decoded = bytes.and_then(|bytes| {
+            let format = image::guess_format(&bytes).map_err(|e| e.to_string())?;
+            decode_image(&bytes, format, srgb, false)
+        });
+
+        let id = match decoded {
+            Ok((width, height, format, pixels)) => {
+                let sampler = texture.sampler();
+                let filter_mode = match (sampler.mag_filter(), sampler.min_filter()) {
+                    (Some(MagFilter::Nearest), _) => FilterMode::Point,
+                    (_, Some(MinFilter::LinearMipmapLinear)) => FilterMode::Trilinear,
+                    _ => FilterMode::Bilinear,
+                };
+                let wrap_mode = match sampler.wrap_s() {
+                    WrappingMode::ClampToEdge => WrapMode::Clamp,
+                    WrappingMode::MirroredRepeat => WrapMode::Mirror,
+                    WrappingMode::Repeat => WrapMode::Repeat,
+                };
+
+                let info = TextureInfo {
+                    width,
+                    height,
+                    depth: 1,
+                    dimension: Dimension::D2,
+                    format,
+                    filter_mode,
+                    wrap_mode,
+                    mipmaps: filter_mode == FilterMode::Trilinear,
+                    pixels,
+                };
+
+                let name = match srgb {
+                    true => format!("texture{}", image.index()),
+                    false => format!("texture{}-linear", image.index()),
+                };
+                let id = ctx.sub_asset_id(&name);
+                ctx.assets().insert(id, info);
+                Some(id)
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to decode image {} in {}: {}",
+                    image.index(),
+                    ctx.info().path.display(),
+                    e
+                );
+                None
+            }
+        };
+
+        data.textures.insert(key, id);
+        id
+    }
+
+    fn derive(
+        &self,
+        ctx: &mut ImportContext,
+        source: TextureId,
+        name: &str,
+        format: Format,
+        map: impl Fn([u8; 4]) -> [u8; 4],
+    ) -> TextureId {
+        let mut info = match ctx.assets().get::<TextureInfo>(&source) {
+            Some(info) if matches!(info.format, Format::Rgba8Unorm | Format::Rgba8UnormSrgb) => {
+                info.clone()
+            }
+            _ => {
+                eprintln!(
+                    "Failed to derive {} in {}: the source texture is not RGBA8",
+                    name,
+                    ctx.info().path.display()
+                );
+                return source;
+            }
+        };
+
+        info.format = format;
+        info.pixels = info
+            .pixels
+            .chunks_exact(4)
+            .flat_map(|pixel| map([pixel[0], pixel[1], pixel[2], pixel[3]]))
+            .collect();
+
+        let id = ctx.sub_asset_id(name);
+        ctx.assets().insert(id, info);
+        id
+    }
+
+    fn material(
+        &self,
+        ctx: &mut ImportContext,
+        data: &mut GltfData,
+        material: &gltf::Material,
+    ) -> MaterialInfo {
+        let name = match material.index() {
+            Some(index) => format!("material{}", index),
+            None => "material".to_string(),
+        };
+
+        let pbr = material.pbr_metallic_roughness();
+        let factor = pbr.base_color_factor();
+        let [r, g, b, a] = factor.map(|c| c as f64);
+        let color = match pbr.base_color_texture() {
+            Some(info) => self.texture(ctx, data, info.texture(), true),
+            None => None,
+        };
+        let color = color.map(|texture| match factor == [1.0; 4] {
+            true => texture,
+            false => self.derive(
+                ctx,
+                texture,
+                &format!("{}-color", name),
+                Format::Rgba8UnormSrgb,
+                |pixel| {
+                    [
+                        scale_srgb(pixel[0], factor[0]),
+                        scale_srgb(pixel[1], factor[1]),
+                        scale_srgb(pixel[2], factor[2]),
+                        scale(pixel[3], factor[3]),
+                    ]
+                },
+            ),
+        });
+
+        let mut info = MaterialInfo::new().color(match color {
+            Some(texture) => ShaderInput::Texture(texture),
+            None => ShaderInput::Color(Color::new(r, g, b, a)),
+        });
+
+        if material.unlit() {
+            info = info.shader_model(ShaderModel::Unlit);
+        }
+
+        let cutoff = material.alpha_cutoff().unwrap_or(0.5);
+        info = match (material.alpha_mode(), color) {
+            (AlphaMode::Opaque, _) => info,
+            (AlphaMode::Blend, Some(texture)) => {
+                let opacity = self.derive(
+                    ctx,
+                    texture,
+                    &format!("{}-opacity", name),
+                    Format::Rgba8Unorm,
+                    |[_, _, _, a]| [a, a, a, 255],
+                );
+                info.blend_mode(BlendMode::Translucent).opacity(opacity)
+            }
+            (AlphaMode::Blend, None) => info
+                .blend_mode(BlendMode::Translucent)
+                .opacity_value(a as f32),
+            (AlphaMode::Mask, Some(texture)) => {
+                let opacity = self.derive(
+                    ctx,
+                    texture,
+                    &format!("{}-opacity", name),
+                    Format::Rgba8Unorm,
+                    |[_, _, _, a]| match a as f32 / 255.0 >= cutoff {
+                        true => [255; 4],
+                        false => [0, 0, 0, 255],
+                    },
+                );
+                info.blend_mode(BlendMode::Translucent).opacity(opacity)
+            }
+            (AlphaMode::Mask, None) => match a as f32 >= cutoff {
+                true => info,
+                false => info.blend_mode(BlendMode::Translucent).opacity_value(0.0),
+            },
+        };
+
+        let metallic_roughness = match pbr.metallic_roughness_texture() {
+            Some(info) => self.texture(ctx, data, info.texture(), false),
+            None => None,
+        };
+        let (metallic, roughness) = (pbr.metallic_factor(), pbr.roughness_factor());
+        info = match metallic_roughness {
+            Some(texture) => {
+                let metallic = self.derive(
+                    ctx,
+                    texture,
+                    &format!("{}-metallic", name),
+                    Format::Rgba8Unorm,
+                    |[_, _, b, _]| gray(scale(b, metallic)),
+                );
+                let roughness = self.derive(
+                    ctx,
+                    texture,
+                    &format!("{}-roughness", name),
+                    Format::Rgba8Unorm,
+                    |[_, g, _, _]| gray(scale(g, roughness)),
+                );
+                info.metallic(metallic).roughness(roughness)
+            }
+            None => info.metallic_value(metallic).roughness_value(roughness),
+        };
+
+        if let Some(normal) = material.normal_texture() {
+            if let Some(texture) = self.texture(ctx, data, normal.texture(), false) {
+                info = info.normal(texture);
+            }
+        }
+
+        let emissive = match material.emissive_texture() {
+            Some(info) => self.texture(ctx, data, info.texture(), true),
+            None => None,
+        };
+        let factor = material.emissive_factor();
+        let [r, g, b] = factor.map(|c| c as f64);
+        info = match emissive {
+            Some(_) if factor == [0.0; 3] => info,
+            Some(texture) if factor == [1.0; 3] => info.emissive(texture),
+            Some(texture) => info.emissive(self.derive(
+                ctx,
+                texture,
+                &format!("{}-emissive", name),
+                Format::Rgba8UnormSrgb,
+                |pixel| {
+                    [
+                        scale_srgb(pixel[0], factor[0]),
+                        scale_srgb(pixel[1], factor[1]),
+                        scale_srgb(pixel[2], factor[2]),
+                        pixel[3],
+                    ]
+                },
+            )),
+            None if r + g + b > 0.0 => info.emissive_color(Color::new(r, g, b, 1.0)),
+            None => info,
+        };
+
+        info
+    }
+
+    fn mesh(
+        &self,
+        ctx: &ImportContext,
+        data: &GltfData,
+        mesh: &gltf::Mesh,
+        materials: &[MaterialId],
+        default: MaterialId,
+    ) -> Result<Option<(MeshInfo, Vec<MaterialId>)>, ImportError> {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut submeshes = Vec::new();
+        let mut mesh_materials = Vec::new();
+
+        for primitive in mesh.primitives() {
+            let reader =
+                primitive.reader(|buffer| data.buffers.get(buffer.index()).map(Vec::as_slice));
+            let positions = reader
+                .read_positions()
+                .ok_or_else(|| {
+                    ctx.error(format!(
+                        "Primitive {} of mesh {} has no positions",
+                        primitive.index(),
+                        mesh.index()
+                    ))
+                })?
+                .collect::<Vec<_>>();
+
+            let local = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..positions.len() as u32).collect(),
+            };
+            let Some(local) = triangulate(primitive.mode(), local) else {
+                eprintln!(
+                    "Skipping {:?} primitive {} of mesh {} in {}",
+                    primitive.mode(),
+                    primitive.index(),
+                    mesh.index(),
+                    ctx.info().path.display()
+                );
+                continue;
+            };
+
+            if local.iter().any(|index| *index as usize >= positions.len()) {
+                return Err(ctx.error(format!(
+                    "Primitive {} of mesh {} has an index out of range",
+                    primitive.index(),
+                    mesh.index()
+                )));
+            }
+
+            let normals = match reader.read_normals() {
+                Some(normals) => normals.collect(),
+                None => generate_normals(&positions, &local),
+            };
+            let uvs = reader
+                .read_tex_coords(0)
+                .map(|uvs| uvs.into_f32().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let base = vertices.len() as u32;
+            for (index, position) in positions.iter().enumerate() {
+                vertices.push(Vertex {
+                    position: *position,
+                    normal: normals.get(index).copied().unwrap_or([0.0, 1.0, 0.0]),
+                    uv: uvs.get(index).copied().unwrap_or([0.0, 0.0]),
+                });
+            }
+
+            submeshes.push(SubMesh {
+                index_start: indices.len() as u32,
+                index_count: local.len() as u32,
+            });
+            indices.extend(local.iter().map(|index| index + base));
+
+            let material = match primitive.material().index() {
+                Some(index) => materials[index],
+                None => default,
+            };
+            mesh_materials.push(material);
+        }
+
+        if submeshes.is_empty() {
+            return Ok(None);
+        }
+
+        let info =
+            MeshInfo::new(vertices, indices).with_submeshes(submeshes, mesh_materials.clone());
+
+        Ok(Some((info, mesh_materials)))
+    }
+
+    fn node(
+        &self,
+        prefab: &mut Prefab,
+        data: &GltfData,
+        node: gltf::Node,
+        parent: Option<usize>,
+        visited: &mut HashSet<usize>,
+    ) {
+        if !visited.insert(node.index()) {
+            return;
+        }
+
+        let (translation, rotation, scale) = node.transform().decomposed();
+        let transform = Transform::new(
+            Vec3::from_array(translation),
+            Quat::from_array(rotation),
+            Vec3::from_array(scale),
+        );
+
+        let name = match node.name() {
+            Some(name) => name.to_string(),
+            None => format!("node{}", node.index()),
+        };
+
+        let mut prefab_node = PrefabNode::new(name, transform).with_parent(parent);
+        if let Some(mesh) = node.mesh() {
+            if let Some((mesh, materials)) = &data.meshes[mesh.index()] {
+                prefab_node = prefab_node.with_mesh(mesh.clone(), materials.clone());
+            }
+        }
+
+        if let Some(camera) = node.camera() {
+            let camera = match camera.projection() {
+                Projection::Perspective(perspective) => Camera::new(
+                    1.0,
+                    perspective.znear(),
+                    perspective.zfar().unwrap_or(DEFAULT_FAR),
+                    perspective.yfov(),
+                    0,
+                    ProjectionMode::Perspective,
+                    Color::black(),
+                ),
+                Projection::Orthographic(orthographic) => Camera::new(
+                    orthographic.ymag(),
+                    orthographic.znear(),
+                    orthographic.zfar(),
+                    DEFAULT_FOV,
+                    0,
+                    ProjectionMode::Orthographic,
+                    Color::black(),
+                ),
+            };
+            prefab_node = prefab_node.with_camera(camera);
+        }
+
+        if let Some(light) = node.light() {
+            let [r, g, b] = light.color().map(|c| c as f64);
+            let (kind, spot_angle) = match light.kind() {
+                Kind::Directional => (LightKind::Directional, 0.0),
+                Kind::Point => (LightKind::Point, 0.0),
+                Kind::Spot {
+                    outer_cone_angle, ..
+                } => (LightKind::Spot, outer_cone_angle),
+            };
+
+            prefab_node = prefab_node.with_light(Light::new(
+                Color::new(r, g, b, 1.0),
+                light.intensity(),
+                light.range().unwrap_or(DEFAULT_LIGHT_RANGE),
+                kind,
+                spot_angle,
+            ));
+        }
+
+        let index = prefab.add_node(prefab_node);
+        for child in node.children() {
+            self.node(prefab, data, child, Some(index), visited);
+        }
+    }
+}
+
+impl BaseImporter for GltfImporter {
+    fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+        let bytes = ctx.read()?;
+        let gltf = Gltf::from_slice(&bytes).map_err(|e| match e {
+            gltf::Error::Deserialize(json) => ctx.error_at(json.line(), json.to_string()),
+            e => ctx.error(e.to_string()),
+        })?;
+
+        let buffers = self.buffers(ctx, &gltf)?;
+        let mut data = GltfData {
+            buffers,
+            textures: HashMap::new(),
+            meshes: Vec::new(),
+        };
+
+        let mut materials = Vec::new();
+        for material in gltf.materials() {
+            let id = ctx.sub_asset_id(&format!("material{}", materials.len()));
+            let info = self.material(ctx, &mut data, &material);
+            ctx.assets().insert(id, info);
+            materials.push(id);
+        }
+
+        let default = ctx.sub_asset_id("material");
+        let needs_default = gltf
+            .meshes()
+            .flat_map(|mesh| mesh.primitives())
+            .any(|primitive| primitive.material().index().is_none());
+        if needs_default {
+            ctx.assets().insert(default, MaterialInfo::new());
+        }
+
+        for mesh in gltf.meshes() {
+            let imported = match self.mesh(ctx, &data, &mesh, &materials, default)? {
+                Some((info, materials)) => {
+                    let id = ctx.sub_asset_id(&format!("mesh{}", mesh.index()));
+                    let assets = ctx.assets();
+                    assets.insert(id, info);
+
+                    let materials = materials.iter().map(|id| assets.track(*id)).collect();
+                    Some((assets.track(id), materials))
+                }
+                None => None,
+            };
+            data.meshes.push(imported);
+        }
+
+        let roots = match gltf.default_scene().or_else(|| gltf.scenes().next()) {
+            Some(scene) => scene.nodes().collect::<Vec<_>>(),
+            None => {
+                let children = gltf
+                    .nodes()
+                    .flat_map(|node| node.children())
+                    .map(|node| node.index())
+                    .collect::<HashSet<_>>();
+                gltf.nodes()
+                    .filter(|node| !children.contains(&node.index()))
+                    .collect()
+            }
+        };
+
+        let mut prefab = Prefab::new();
+        let mut visited = HashSet::new();
+        for root in roots {
+            self.node(&mut prefab, &data, root, None, &mut visited);
+        }
+
+        let id = ctx.info().id;
+        ctx.assets().insert(id, prefab);
+
+        Ok(())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["gltf", "glb"]
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> ImportSettings {
+        ImportSettings::new()
+    }
+}
+
+impl AssetImporter<Prefab> for GltfImporter {}
+
+fn triangulate(mode: Mode, indices: Vec<u32>) -> Option<Vec<u32>> {
+    match mode {
+        Mode::Triangles => Some(indices),
+        Mode::TriangleStrip => Some(
+            (0..indices.len().saturating_sub(2))
+                .flat_map(|i| match i % 2 {
+                    0 => [indices[i], indices[i + 1], indices[i + 2]],
+                    _ => [indices[i + 1], indices[i], indices[i + 2]],
+                })
+                .collect(),
+        ),
+        Mode::TriangleFan => Some(
+            (1..indices.len().saturating_sub(1))
+                .flat_map(|i| [indices[0], indices[i], indices[i + 1]])
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn generate_normals(positions: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from_array(positions[triangle[i] as usize]));
+        let face = (b - a).cross(c - a);
+        for index in triangle {
+            normals[*index as usize] += face;
+        }
+    }
+
+    normals
+        .into_iter()
+        .map(|normal| normal.try_normalize().unwrap_or(Vec3::Y).to_array())
+        .collect()
+}
+
+fn scale(value: u8, factor: f32) -> u8 {
+    (value as f32 * factor).round().clamp(0.0, 255.0) as u8
+}
+
+fn scale_srgb(value: u8, factor: f32) -> u8 {
+    linear_to_srgb(srgb_to_linear(value) * factor)
+}
+
+fn gray(value: u8) -> [u8; 4] {
+    [value, value, value, 255]
+}
+
+fn decode_data_uri(uri: &str) -> Option<Result<Vec<u8>, String>> {
+    let data = uri.strip_prefix("data:")?;
+    Some(match data.split_once(";base64,") {
+        Some((_, encoded)) => STANDARD.decode(encoded).map_err(|e| e.to_string()),
+        None => Err("Only base64 data URIs are supported".to_string()),
+    })
+}
+
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::texture::TextureImporter, *};
+    use crate::{
+        asset::{AssetDatabase, AssetId, AssetMeta, ImporterRepo, MemoryIo},
+        ecs::ResourceManager,
+        graphics::{material::Material, texture::Format},
+    };
+    use std::{io::Cursor, path::Path, sync::Arc};
+
+    fn png(pixels: [[u8; 4]; 2]) -> Vec<u8> {
+        let image = image::RgbaImage::from_raw(2, 1, pixels.concat()).unwrap();
+        let mut bytes = Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    fn pixels(db: &AssetDatabase, input: Option<&ShaderInput>) -> (Format, Vec<u8>) {
+        let Some(ShaderInput::Texture(id)) = input else {
+            panic!("Expected a texture, found {:?}", input);
+        };
+
+        let info = db.get::<TextureInfo>(id).unwrap();
+        (info.format, info.pixels.clone())
+    }
+
+    #[test]
+    fn materials_apply_factors_channels_and_alpha() {
+        let color = STANDARD.encode(png([[255, 255, 255, 200], [128, 0, 255, 50]]));
+        let gltf = format!(
+            r#"{{
+                "asset": {{ "version": "2.0" }},
+                "images": [{{ "uri": "orm.png" }}, {{ "uri": "data:image/png;base64,{}" }}],
+                "textures": [{{ "source": 0 }}, {{ "source": 1 }}],
+                "materials": [{{
+                    "pbrMetallicRoughness": {{
+                        "baseColorTexture": {{ "index": 1 }},
+                        "baseColorFactor": [0.5, 1.0, 1.0, 1.0],
+                        "metallicRoughnessTexture": {{ "index": 0 }},
+                        "metallicFactor": 0.5,
+                        "roughnessFactor": 1.0
+                    }},
+                    "alphaMode": "MASK",
+                    "alphaCutoff": 0.5
+                }}]
+            }}"#,
+            color
+        );
+
+        let io = Arc::new(
+            MemoryIo::new()
+                .with_file(
+                    "assets/orm.png",
+                    png([[0, 10, 200, 255], [0, 250, 20, 255]]),
+                )
+                .with_file("assets/model.gltf", gltf),
+        );
+        let mut importers = ImporterRepo::new();
+        importers.add_importer::<TextureInfo, _>(TextureImporter);
+        importers.add_importer::<Prefab, _>(GltfImporter);
+
+        let mut resources = ResourceManager::new();
+        let mut db = AssetDatabase::new();
+        db.set_io(io.clone());
+        resources.register(db);
+        AssetDatabase::load(Path::new("assets"), &resources, &importers);
+
+        let db = resources.resource::<AssetDatabase>();
+        assert!(db.errors().is_empty());
+
+        let model = db.id(Path::new("assets/model.gltf")).unwrap();
+        let id = AssetId::from(format!("{}#material0", model));
+        let material: Material = db.get::<MaterialInfo>(&id).unwrap().build();
+
+        let (format, color) = pixels(&db, Some(material.color()));
+        assert_eq!(format, Format::Rgba8UnormSrgb);
+        assert_eq!(color, vec![188, 255, 255, 200, 92, 0, 255, 50]);
+
+        assert_eq!(material.blend_mode(), BlendMode::Translucent);
+        let (_, opacity) = pixels(&db, material.opacity());
+        assert_eq!(opacity, vec![255, 255, 255, 255, 0, 0, 0, 255]);
+
+        let (format, metallic) = pixels(&db, material.metallic());
+        assert_eq!(format, Format::Rgba8Unorm);
+        assert_eq!(metallic, vec![100, 100, 100, 255, 10, 10, 10, 255]);
+
+        let (_, roughness) = pixels(&db, material.roughness());
+        assert_eq!(roughness, vec![10, 10, 10, 255, 250, 250, 250, 255]);
+
+        let orm = db.get_by_path::<TextureInfo>(Path::new("assets/orm.png"));
+        assert_eq!(orm.unwrap().format, Format::Rgba8Unorm);
+        let meta = AssetMeta::load(io.as_ref(), Path::new("assets/orm.png.meta")).unwrap();
+        assert_eq!(meta.settings.bool("srgb"), Some(false));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    asset::{AssetImporter, BaseImporter, ImportContext, ImportError, ImportSettings},
+    graphics::{
+        color::Color,
+        material::{BlendMode, MaterialInfo, ShaderInput, ShaderModel},
+    },
+};
+use ron::{extensions::Extensions, Options};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Deserialize)]
+enum MaterialInput {
+    Texture(PathBuf),
+    Color(f64, f64, f64, f64),
+    Scalar(f32),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaterialFile {
+    shader_model: Option<ShaderModel>,
+    blend_mode: Option<BlendMode>,
+    color: Option<MaterialInput>,
+    specular: Option<MaterialInput>,
+    normal: Option<MaterialInput>,
+    metallic: Option<MaterialInput>,
+    roughness: Option<MaterialInput>,
+    emissive: Option<MaterialInput>,
+    opacity: Option<MaterialInput>,
+}
+
+pub struct MaterialImporter;
+
+impl MaterialImporter {
+    fn input(
+        &self,
+        ctx: &mut ImportContext,
+        name: &str,
+        input: MaterialInput,
+    ) -> Result<ShaderInput, ImportError> {
+        let srgb = matches!(name, "color" | "specular" | "emissive");
+        let settings = ImportSettings::new().with("srgb", srgb);
+        match input {
+            MaterialInput::Texture(path) => match ctx.load_dependency_with(&path, &settings) {
+                Some(id) => Ok(ShaderInput::Texture(id)),
+                None => Err(ctx.error(format!("Missing {} texture {}", name, path.display()))),
+            },
+            MaterialInput::Color(r, g, b, a) => Ok(ShaderInput::Color(Color::new(r, g, b, a))),
+            MaterialInput::Scalar(value) => Ok(ShaderInput::Scalar(value)),
+        }
+    }
+
+    fn apply(
+        &self,
+        ctx: &mut ImportContext,
+        info: MaterialInfo,
+        name: &str,
+        input: Option<MaterialInput>,
+    ) -> Result<MaterialInfo, ImportError> {
+        let Some(input) = input else {
+            return Ok(info);
+        };
+
+        let info = match (name, self.input(ctx, name, input)?) {
+            ("color", input) => info.color(input),
+            ("specular", ShaderInput::Texture(id)) => info.specular(id),
+            ("specular", ShaderInput::Color(color)) => info.specular_color(color),
+            ("specular", ShaderInput::Scalar(value)) => info.specular_value(value),
+            ("normal", ShaderInput::Texture(id)) => info.normal(id),
+            ("metallic", ShaderInput::Texture(id)) => info.metallic(id),
+            ("metallic", ShaderInput::Scalar(value)) => info.metallic_value(value),
+            ("roughness", ShaderInput::Texture(id)) => info.roughness(id),
+            ("roughness", ShaderInput::Scalar(value)) => info.roughness_value(value),
+            ("emissive", ShaderInput::Texture(id)) => info.emissive(id),
+            ("emissive", ShaderInput::Color(color)) => info.emissive_color(color),
+            ("opacity", ShaderInput::Texture(id)) => info.opacity(id),
+            ("opacity", ShaderInput::Scalar(value)) => info.opacity_value(value),
+            (_, ShaderInput::Texture(_)) => {
+                return Err(ctx.error(format!("{} does not accept a texture", name)))
+            }
+            (_, ShaderInput::Color(_)) => {
+                return Err(ctx.error(format!("{} does not accept a color", name)))
+            }
+            (_, ShaderInput::Scalar(_)) => {
+                return Err(ctx.error(format!("{} does not accept a scalar", name)))
+            }
+        };
+
+        Ok(info)
+    }
+}
+
+impl BaseImporter for MaterialImporter {
+    fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+        let bytes = ctx.read()?;
+        let text = String::from_utf8(bytes).map_err(|e| ctx.error(e.to_string()))?;
+        let file: MaterialFile = Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_str(&text)
+            .map_err(|e| ctx.error_at(e.position.line, e.code.to_string()))?;
+
+        let mut info = MaterialInfo::new()
+            .shader_model(file.shader_model.unwrap_or(ShaderModel::Lit))
+            .blend_mode(file.blend_mode.unwrap_or(BlendMode::Opaque));
+
+        info = self.apply(ctx, info, "color", file.color)?;
+        info = self.apply(ctx, info, "specular", file.specular)?;
+        info = self.apply(ctx, info, "normal", file.normal)?;
+        info = self.apply(ctx, info, "metallic", file.metallic)?;
+        info = self.apply(ctx, info, "roughness", file.roughness)?;
+        info = self.apply(ctx, info, "emissive", file.emissive)?;
+        info = self.apply(ctx, info, "opacity", file.opacity)?;
+
+        let id = ctx.info().id;
+        ctx.assets().insert(id, info);
+
+        Ok(())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mat"]
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+impl AssetImporter<MaterialInfo> for MaterialImporter {}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::texture::TextureImporter, *};
+    use crate::{
+        asset::{AssetDatabase, ImporterRepo, MemoryIo},
+        ecs::ResourceManager,
+        graphics::texture::{Format, TextureInfo},
+    };
+    use std::{io::Cursor, path::Path, sync::Arc};
+
+    fn png() -> Vec<u8> {
+        let image = image::RgbaImage::from_raw(1, 1, vec![128, 128, 255, 255]).unwrap();
+        let mut bytes = Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn linear_inputs_load_without_srgb() {
+        let io = Arc::new(
+            MemoryIo::new()
+                .with_file("assets/albedo.png", png())
+                .with_file("assets/normal.png", png())
+                .with_file("assets/rough.png", png())
+                .with_file(
+                    "assets/a.mat",
+                    r#"(
+                        color: Texture("albedo.png"),
+                        normal: Texture("normal.png"),
+                        roughness: Texture("rough.png"),
+                    )"#,
+                ),
+        );
+        let mut importers = ImporterRepo::new();
+        importers.add_importer::<TextureInfo, _>(TextureImporter);
+        importers.add_importer::<MaterialInfo, _>(MaterialImporter);
+
+        let mut resources = ResourceManager::new();
+        let mut db = AssetDatabase::new();
+        db.set_io(io);
+        resources.register(db);
+        AssetDatabase::load(Path::new("assets"), &resources, &importers);
+
+        let db = resources.resource::<AssetDatabase>();
+        assert!(db.errors().is_empty());
+
+        let format = |path: &str| {
+            db.get_by_path::<TextureInfo>(Path::new(path))
+                .unwrap()
+                .format
+        };
+        assert_eq!(format("assets/albedo.png"), Format::Rgba8UnormSrgb);
+        assert_eq!(format("assets/normal.png"), Format::Rgba8Unorm);
+        assert_eq!(format("assets/rough.png"), Format::Rgba8Unorm);
+    }
+}
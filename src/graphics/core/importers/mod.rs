@@ -0,0 +1,11 @@
+pub mod gltf;
+pub mod material;
+pub mod obj;
+pub mod shader;
+pub mod texture;
+
+pub use self::gltf::*;
+pub use material::*;
+pub use obj::*;
+pub use shader::*;
+pub use texture::*;
@@ -0,0 +1,459 @@
+use crate::{
+    asset::{AssetImporter, BaseImporter, ImportContext, ImportError, ImportSettings},
+    graphics::{
+        color::Color,
+        material::{BlendMode, MaterialInfo, ShaderInput, ShaderModel},
+        mesh::{MeshInfo, SubMesh},
+        vertex::Vertex,
+        TextureId,
+    },
+    shared::ext::path::PathExt,
+};
+use glam::{Vec2, Vec3};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+const DEFAULT_MATERIAL: &str = "default";
+
+type Corner = (usize, Option<usize>, Option<usize>);
+
+struct Group {
+    material: String,
+    triangles: Vec<[Corner; 3]>,
+}
+
+struct ObjData {
+    positions: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    normals: Vec<Vec3>,
+    groups: Vec<Group>,
+    libraries: Vec<String>,
+}
+
+impl ObjData {
+    fn group(&mut self, material: &str) -> &mut Group {
+        let index = match self.groups.iter().position(|g| g.material == material) {
+            Some(index) => index,
+            None => {
+                self.groups.push(Group {
+                    material: material.to_string(),
+                    triangles: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        };
+
+        &mut self.groups[index]
+    }
+}
+
+pub struct ObjImporter;
+
+impl ObjImporter {
+    fn parse(&self, ctx: &ImportContext, source: &str) -> Result<ObjData, ImportError> {
+        let mut data = ObjData {
+            positions: Vec::new(),
+            uvs: Vec::new(),
+            normals: Vec::new(),
+            groups: Vec::new(),
+            libraries: Vec::new(),
+        };
+        let mut material = DEFAULT_MATERIAL.to_string();
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or("").trim();
+            let mut parts = line.split_whitespace();
+            let Some(keyword) = parts.next() else {
+                continue;
+            };
+            let parts = parts.collect::<Vec<_>>();
+
+            match keyword {
+                "v" => {
+                    let [x, y, z] = parse_floats(ctx, line_number, &parts)?;
+                    data.positions.push(Vec3::new(x, y, z));
+                }
+                "vt" => {
+                    let u = parse_float(ctx, line_number, parts.first())?;
+                    let v = match parts.get(1) {
+                        Some(_) => parse_float(ctx, line_number, parts.get(1))?,
+                        None => 0.0,
+                    };
+                    data.uvs.push(Vec2::new(u, v));
+                }
+                "vn" => {
+                    let [x, y, z] = parse_floats(ctx, line_number, &parts)?;
+                    data.normals.push(Vec3::new(x, y, z));
+                }
+                "f" => {
+                    if parts.len() < 3 {
+                        return Err(ctx.error_at(line_number, "Face needs at least 3 vertices"));
+                    }
+
+                    let corners = parts
+                        .iter()
+                        .map(|part| parse_corner(&data, part))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| ctx.error_at(line_number, "Invalid face index"))?;
+
+                    let group = data.group(&material);
+                    for i in 1..corners.len() - 1 {
+                        group
+                            .triangles
+                            .push([corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+                "usemtl" => {
+                    material = parts.join(" ");
+                    if material.is_empty() {
+                        return Err(ctx.error_at(line_number, "Missing material name"));
+                    }
+                }
+                "mtllib" => {
+                    data.libraries
+                        .extend(parts.iter().map(|part| part.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        if data.groups.iter().all(|group| group.triangles.is_empty()) {
+            return Err(ctx.error("Mesh has no faces"));
+        }
+
+        Ok(data)
+    }
+
+    fn build(&self, data: &ObjData, flip_uv: bool) -> (Vec<Vertex>, Vec<u32>, Vec<SubMesh>) {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut submeshes = Vec::new();
+        let mut lookup = HashMap::<Corner, u32>::new();
+        let mut smooth = Vec::new();
+        let mut accumulated = vec![Vec3::ZERO; data.positions.len()];
+
+        for group in &data.groups {
+            let index_start = indices.len() as u32;
+            for triangle in &group.triangles {
+                let [a, b, c] = triangle.map(|corner| data.positions[corner.0]);
+                let face = (b - a).cross(c - a);
+
+                for corner in triangle {
+                    if corner.2.is_none() {
+                        accumulated[corner.0] += face;
+                    }
+
+                    let index = *lookup.entry(*corner).or_insert_with(|| {
+                        let (position, uv, normal) = *corner;
+                        let mut uv = uv.map(|i| data.uvs[i]).unwrap_or(Vec2::ZERO);
+                        if flip_uv {
+                            uv.y = 1.0 - uv.y;
+                        }
+
+                        let normal = match normal {
+                            Some(i) => data.normals[i],
+                            None => {
+                                smooth.push((vertices.len(), position));
+                                Vec3::ZERO
+                            }
+                        };
+
+                        vertices.push(Vertex::new(data.positions[position], normal, uv));
+                        vertices.len() as u32 - 1
+                    });
+                    indices.push(index);
+                }
+            }
+
+            submeshes.push(SubMesh {
+                index_start,
+                index_count: indices.len() as u32 - index_start,
+            });
+        }
+
+        for (vertex, position) in smooth {
+            let normal = accumulated[position].try_normalize().unwrap_or(Vec3::Y);
+            vertices[vertex].normal = normal.to_array();
+        }
+
+        (vertices, indices, submeshes)
+    }
+
+    fn load_library(
+        &self,
+        ctx: &mut ImportContext,
+        library: &str,
+    ) -> Result<Vec<(String, MaterialInfo)>, ImportError> {
+        let path = match ctx.info().path.parent() {
+            Some(parent) => parent.join(library).normalize(),
+            None => PathBuf::from(library),
+        };
+
+        let bytes = match ctx.read_path(library) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                eprintln!(
+                    "Failed to read material library {} of {}: {}",
+                    path.display(),
+                    ctx.info().path.display(),
+                    e
+                );
+                return Ok(Vec::new());
+            }
+        };
+
+        let source = String::from_utf8_lossy(&bytes);
+        let folder = Path::new(library).parent().unwrap_or(Path::new(""));
+        let error =
+            |line: usize, message: &str| ImportError::new(message).with_path(&path).with_line(line);
+
+        let mut materials = Vec::<MtlMaterial>::new();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or("").trim();
+            let mut parts = line.split_whitespace();
+            let Some(keyword) = parts.next() else {
+                continue;
+            };
+            let parts = parts.collect::<Vec<_>>();
+
+            if keyword == "newmtl" {
+                if parts.is_empty() {
+                    return Err(error(line_number, "Missing material name"));
+                }
+
+                materials.push(MtlMaterial::new(parts.join(" ")));
+                continue;
+            }
+
+            let Some(material) = materials.last_mut() else {
+                return Err(error(line_number, "Material property before 'newmtl'"));
+            };
+
+            let floats = || {
+                parts
+                    .iter()
+                    .map(|part| part.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .filter(|values| !values.is_empty())
+                    .ok_or_else(|| error(line_number, "Expected a number"))
+            };
+
+            match keyword {
+                "Kd" => {
+                    let values = floats()?;
+                    let value = |i: usize| values.get(i).copied().unwrap_or(values[0]);
+                    material.diffuse = [value(0), value(1), value(2)];
+                }
+                "d" => material.opacity = floats()?[0],
+                "Tr" => material.opacity = 1.0 - floats()?[0],
+                "illum" => material.unlit = floats()?[0] == 0.0,
+                "map_Kd" | "map_Ks" | "map_Ke" | "map_d" | "map_Bump" | "map_bump" | "bump"
+                | "norm" | "map_Pr" | "map_Pm" => {
+                    let Some(texture) = parts.last() else {
+                        return Err(error(line_number, "Missing texture path"));
+                    };
+
+                    let Some(id) = ctx.load_dependency(folder.join(texture)) else {
+                        continue;
+                    };
+
+                    let slot = match keyword {
+                        "map_Kd" => &mut material.diffuse_map,
+                        "map_Ks" => &mut material.specular_map,
+                        "map_Ke" => &mut material.emissive_map,
+                        "map_d" => &mut material.opacity_map,
+                        "map_Pr" => &mut material.roughness_map,
+                        "map_Pm" => &mut material.metallic_map,
+                        _ => &mut material.normal_map,
+                    };
+                    *slot = Some(id);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(materials
+            .into_iter()
+            .map(|material| (material.name.clone(), material.info()))
+            .collect())
+    }
+}
+
+impl BaseImporter for ObjImporter {
+    fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+        let flip_uv = ctx.settings().bool("flip_uv").unwrap_or(true);
+
+        let bytes = ctx.read()?;
+        let source = String::from_utf8_lossy(&bytes);
+        let data = self.parse(ctx, &source)?;
+
+        let mut library = HashMap::new();
+        for name in &data.libraries {
+            library.extend(self.load_library(ctx, name)?);
+        }
+
+        let id = ctx.info().id;
+        let (vertices, indices, submeshes) = self.build(&data, flip_uv);
+        let mut materials = Vec::new();
+        for group in &data.groups {
+            let info = match library.get(&group.material) {
+                Some(info) => *info,
+                None => {
+                    if group.material != DEFAULT_MATERIAL {
+                        eprintln!(
+                            "Unknown material '{}' in {}",
+                            group.material,
+                            ctx.info().path.display()
+                        );
+                    }
+
+                    MaterialInfo::new()
+                }
+            };
+
+            let material = ctx.sub_asset_id(&group.material);
+            ctx.assets().insert(material, info);
+            materials.push(material);
+        }
+
+        let info = MeshInfo::new(vertices, indices).with_submeshes(submeshes, materials);
+        ctx.assets().insert(id, info);
+
+        Ok(())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["obj"]
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> ImportSettings {
+        ImportSettings::new().with("flip_uv", true)
+    }
+}
+
+impl AssetImporter<MeshInfo> for ObjImporter {}
+
+struct MtlMaterial {
+    name: String,
+    diffuse: [f32; 3],
+    opacity: f32,
+    unlit: bool,
+    diffuse_map: Option<TextureId>,
+    specular_map: Option<TextureId>,
+    emissive_map: Option<TextureId>,
+    opacity_map: Option<TextureId>,
+    normal_map: Option<TextureId>,
+    metallic_map: Option<TextureId>,
+    roughness_map: Option<TextureId>,
+}
+
+impl MtlMaterial {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            diffuse: [1.0, 1.0, 1.0],
+            opacity: 1.0,
+            unlit: false,
+            diffuse_map: None,
+            specular_map: None,
+            emissive_map: None,
+            opacity_map: None,
+            normal_map: None,
+            metallic_map: None,
+            roughness_map: None,
+        }
+    }
+
+    fn info(&self) -> MaterialInfo {
+        let color = match self.diffuse_map {
+            Some(texture) => ShaderInput::Texture(texture),
+            None => {
+                let [r, g, b] = self.diffuse.map(|c| c as f64);
+                ShaderInput::Color(Color::new(r, g, b, self.opacity as f64))
+            }
+        };
+
+        let mut info = MaterialInfo::new().color(color);
+        if self.unlit {
+            info = info.shader_model(ShaderModel::Unlit);
+        }
+
+        if self.opacity < 1.0 || self.opacity_map.is_some() {
+            info = info.blend_mode(BlendMode::Translucent);
+        }
+
+        if let Some(texture) = self.specular_map {
+            info = info.specular(texture);
+        }
+
+        if let Some(texture) = self.normal_map {
+            info = info.normal(texture);
+        }
+
+        if let Some(texture) = self.metallic_map {
+            info = info.metallic(texture);
+        }
+
+        if let Some(texture) = self.roughness_map {
+            info = info.roughness(texture);
+        }
+
+        if let Some(texture) = self.emissive_map {
+            info = info.emissive(texture);
+        }
+
+        if let Some(texture) = self.opacity_map {
+            info = info.opacity(texture);
+        }
+
+        info
+    }
+}
+
+fn parse_float(ctx: &ImportContext, line: usize, part: Option<&&str>) -> Result<f32, ImportError> {
+    part.and_then(|part| part.parse::<f32>().ok())
+        .ok_or_else(|| ctx.error_at(line, "Expected a number"))
+}
+
+fn parse_floats(ctx: &ImportContext, line: usize, parts: &[&str]) -> Result<[f32; 3], ImportError> {
+    Ok([
+        parse_float(ctx, line, parts.first())?,
+        parse_float(ctx, line, parts.get(1))?,
+        parse_float(ctx, line, parts.get(2))?,
+    ])
+}
+
+fn parse_corner(data: &ObjData, part: &str) -> Option<Corner> {
+    let mut indices = part.split('/');
+    let position = parse_index(indices.next()?, data.positions.len())?;
+    let uv = match indices.next() {
+        Some("") | None => None,
+        Some(index) => Some(parse_index(index, data.uvs.len())?),
+    };
+    let normal = match indices.next() {
+        Some("") | None => None,
+        Some(index) => Some(parse_index(index, data.normals.len())?),
+    };
+
+    Some((position, uv, normal))
+}
+
+fn parse_index(part: &str, count: usize) -> Option<usize> {
+    let index = part.parse::<i64>().ok()?;
+    let index = match index {
+        0 => return None,
+        i if i > 0 => i - 1,
+        i => count as i64 + i,
+    };
+
+    (0..count as i64).contains(&index).then_some(index as usize)
+}
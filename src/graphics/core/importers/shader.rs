@@ -0,0 +1,81 @@
+use crate::{
+    asset::{AssetImporter, BaseImporter, ImportContext, ImportError, ImportSettings},
+    graphics::shader::source::{ShaderDefs, ShaderInfo},
+    shared::ext::path::PathExt,
+};
+use std::{io::ErrorKind, path::Path};
+
+pub struct ShaderImporter;
+
+impl BaseImporter for ShaderImporter {
+    fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+        let settings = ctx.settings().clone();
+        let defs = ShaderDefs::parse(settings.text("defines").unwrap_or(""));
+        let permutations = settings
+            .text("permutations")
+            .unwrap_or("")
+            .split(';')
+            .filter(|permutation| !permutation.trim().is_empty())
+            .map(ShaderDefs::parse)
+            .collect::<Vec<_>>();
+
+        let bytes = ctx.read()?;
+        let source = String::from_utf8(bytes).map_err(|e| ctx.error(e.to_string()))?;
+        let path = ctx.info().path.clone();
+        let folder = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        let mut info = ShaderInfo::new(&path, source)
+            .with_defs(defs)
+            .with_permutations(permutations);
+
+        let mut pending = vec![0];
+        while let Some(index) = pending.pop() {
+            for key in info.includes(index) {
+                if info.file(&key).is_some() {
+                    continue;
+                }
+
+                let bytes = match ctx.read_path(&key) {
+                    Ok(bytes) => bytes,
+                    Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                    Err(e) => return Err(ctx.error(e.to_string())),
+                };
+
+                let source = String::from_utf8_lossy(&bytes).into_owned();
+                let path = folder.join(&key).normalize();
+                pending.push(info.add_include(key, path, source));
+            }
+        }
+
+        let mut permutations = vec![ShaderDefs::new()];
+        permutations.extend(info.permutations().iter().cloned());
+        for defs in &permutations {
+            let composed = info.compose(defs)?;
+            if settings.bool("validate").unwrap_or(true) && composed.has_entry_points() {
+                info.validate(defs)?;
+            }
+        }
+
+        let id = ctx.info().id;
+        ctx.assets().insert(id, info);
+
+        Ok(())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["wgsl"]
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> ImportSettings {
+        ImportSettings::new()
+            .with("defines", "")
+            .with("permutations", "")
+            .with("validate", true)
+    }
+}
+
+impl AssetImporter<ShaderInfo> for ShaderImporter {}
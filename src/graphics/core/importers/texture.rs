@@ -0,0 +1,200 @@
+use crate::{
+    asset::{AssetImporter, BaseImporter, ImportContext, ImportError, ImportSettings},
+    graphics::texture::{Dimension, FilterMode, Format, TextureInfo, WrapMode},
+    shared::ext::path::PathExt,
+};
+use image::{codecs::hdr::HdrDecoder, ImageFormat};
+
+const ARTIFACT_VERSION: u8 = 1;
+
+pub struct TextureImporter;
+
+impl TextureImporter {
+    fn decode(
+        &self,
+        ctx: &ImportContext,
+        bytes: &[u8],
+        srgb: bool,
+        flip_y: bool,
+    ) -> Result<(u32, u32, Format, Vec<u8>), ImportError> {
+        let path = &ctx.info().path;
+        let format = ImageFormat::from_extension(path.extension_str())
+            .ok_or_else(|| ctx.error("Unsupported image format"))?;
+
+        decode_image(bytes, format, srgb, flip_y).map_err(|e| ctx.error(e))
+    }
+}
+
+pub(super) fn decode_image(
+    bytes: &[u8],
+    format: ImageFormat,
+    srgb: bool,
+    flip_y: bool,
+) -> Result<(u32, u32, Format, Vec<u8>), String> {
+    if format == ImageFormat::Hdr {
+        let decoder = HdrDecoder::new(bytes).map_err(|e| e.to_string())?;
+        let metadata = decoder.metadata();
+        let (width, height) = (metadata.width, metadata.height);
+        let mut rows = decoder
+            .read_image_hdr()
+            .map_err(|e| e.to_string())?
+            .chunks(width as usize)
+            .map(|row| row.to_vec())
+            .collect::<Vec<_>>();
+        if flip_y {
+            rows.reverse();
+        }
+
+        let pixels = rows
+            .into_iter()
+            .flatten()
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 1.0])
+            .flat_map(|value| f16_bits(value).to_le_bytes())
+            .collect();
+
+        return Ok((width, height, Format::Rgba16Float, pixels));
+    }
+
+    let mut image =
+        image::load_from_memory_with_format(bytes, format).map_err(|e| e.to_string())?;
+    if flip_y {
+        image = image.flipv();
+    }
+
+    let (width, height) = (image.width(), image.height());
+    let format = match srgb {
+        true => Format::Rgba8UnormSrgb,
+        false => Format::Rgba8Unorm,
+    };
+
+    let pixels = image.to_rgba8().into_raw();
+
+    Ok((width, height, format, pixels))
+}
+
+impl BaseImporter for TextureImporter {
+    fn import(&self, ctx: &mut ImportContext) -> Result<(), ImportError> {
+        let settings = ctx.settings().clone();
+        let srgb = settings.bool("srgb").unwrap_or(true);
+        let flip_y = settings.bool("flip_y").unwrap_or(false);
+        let mipmaps = settings.bool("mipmaps").unwrap_or(false);
+
+        let filter = settings.text("filter").unwrap_or("bilinear");
+        let filter_mode = FilterMode::from_name(filter)
+            .ok_or_else(|| ctx.error(format!("Unknown filter mode '{}'", filter)))?;
+
+        let wrap = settings.text("wrap").unwrap_or("repeat");
+        let wrap_mode = WrapMode::from_name(wrap)
+            .ok_or_else(|| ctx.error(format!("Unknown wrap mode '{}'", wrap)))?;
+
+        let (width, height, format, pixels) = match ctx.read_artifact().and_then(read_artifact) {
+            Some(decoded) => decoded,
+            None => {
+                let bytes = ctx.read()?;
+                let decoded = self.decode(ctx, &bytes, srgb, flip_y)?;
+                ctx.write_artifact(&write_artifact(&decoded));
+                decoded
+            }
+        };
+
+        let info = TextureInfo {
+            width,
+            height,
+            depth: 1,
+            dimension: Dimension::D2,
+            format,
+            filter_mode,
+            wrap_mode,
+            mipmaps,
+            pixels,
+        };
+
+        let id = ctx.info().id;
+        ctx.assets().insert(id, info);
+
+        Ok(())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["png", "jpg", "jpeg", "tga", "bmp", "hdr"]
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn settings(&self) -> ImportSettings {
+        ImportSettings::new()
+            .with("srgb", true)
+            .with("flip_y", false)
+            .with("mipmaps", false)
+            .with("filter", "bilinear")
+            .with("wrap", "repeat")
+    }
+}
+
+impl AssetImporter<TextureInfo> for TextureImporter {}
+
+fn write_artifact((width, height, format, pixels): &(u32, u32, Format, Vec<u8>)) -> Vec<u8> {
+    let format: u8 = match format {
+        Format::Rgba8UnormSrgb => 0,
+        Format::Rgba8Unorm => 1,
+        _ => 2,
+    };
+
+    let mut bytes = Vec::with_capacity(pixels.len() + 10);
+    bytes.push(ARTIFACT_VERSION);
+    bytes.push(format);
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(pixels);
+    bytes
+}
+
+fn read_artifact(bytes: Vec<u8>) -> Option<(u32, u32, Format, Vec<u8>)> {
+    if bytes.len() < 10 || bytes[0] != ARTIFACT_VERSION {
+        return None;
+    }
+
+    let format = match bytes[1] {
+        0 => Format::Rgba8UnormSrgb,
+        1 => Format::Rgba8Unorm,
+        2 => Format::Rgba16Float,
+        _ => return None,
+    };
+
+    let width = u32::from_le_bytes(bytes[2..6].try_into().ok()?);
+    let height = u32::from_le_bytes(bytes[6..10].try_into().ok()?);
+    let pixels = bytes[10..].to_vec();
+    let size = format.block_size(None)? as usize;
+    if pixels.len() != width as usize * height as usize * size {
+        return None;
+    }
+
+    Some((width, height, format, pixels))
+}
+
+fn f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent >= 0x1f {
+        return match (bits & 0x7fff_ffff) > 0x7f80_0000 {
+            true => sign | 0x7e00,
+            false => sign | 0x7c00,
+        };
+    }
+
+    if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+
+        let mantissa = mantissa | 0x0080_0000;
+        return sign | (mantissa >> (14 - exponent)) as u16;
+    }
+
+    sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+}
@@ -2,6 +2,7 @@ pub mod components;
 pub mod context;
 pub mod draw;
 pub mod gpu;
+pub mod importers;
 pub mod primitives;
 pub mod resources;
 pub mod service;
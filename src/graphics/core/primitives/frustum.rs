@@ -86,6 +86,39 @@ impl Frustum {
         }
     }
 
+    pub fn from_matrix(view_projection: &glam::Mat4) -> Frustum {
+        let row = |index| view_projection.row(index);
+        let planes = [
+            row(3) - row(1),
+            row(3) + row(1),
+            row(3) + row(0),
+            row(3) - row(0),
+            row(2),
+            row(3) - row(2),
+        ]
+        .map(Plane::from_coefficients);
+
+        let inverse = view_projection.inverse();
+        let point = |x, y, z| inverse.project_point3(glam::Vec3::new(x, y, z));
+        let far_center = point(0.0, 0.0, 1.0);
+        let far_right = point(1.0, 0.0, 1.0) - far_center;
+        let far_up = point(0.0, 1.0, 1.0) - far_center;
+
+        let axes = [
+            far_right.normalize(),
+            far_up.normalize(),
+            (far_center - point(0.0, 0.0, 0.0)).normalize(),
+        ];
+
+        let half_extents = glam::Vec3::new(far_right.length(), far_up.length(), far_right.length());
+
+        Frustum {
+            planes,
+            axes,
+            half_extents,
+        }
+    }
+
     pub fn contains_point(&self, point: glam::Vec3) -> bool {
         for plane in self.planes.iter() {
             if plane.distance_to_point(point) < 0.0 {
@@ -107,19 +140,10 @@ impl Frustum {
     }
 
     pub fn contains_bounds(&self, bounds: &Bounds) -> bool {
-        let points = bounds.points();
-
         for plane in self.planes.iter() {
-            let mut inside = false;
-
-            for point in points.iter() {
-                if plane.distance_to_point(*point) >= 0.0 {
-                    inside = true;
-                    break;
-                }
-            }
-
-            if !inside {
+            let point =
+                glam::Vec3::select(plane.normal.cmpge(glam::Vec3::ZERO), bounds.min, bounds.max);
+            if plane.distance_to_point(point) < 0.0 {
                 return false;
             }
         }
@@ -128,19 +152,10 @@ impl Frustum {
     }
 
     pub fn intersects_bounds(&self, bounds: &Bounds) -> bool {
-        let points = bounds.points();
-
         for plane in self.planes.iter() {
-            let mut inside = false;
-
-            for point in points.iter() {
-                if plane.distance_to_point(*point) >= 0.0 {
-                    inside = true;
-                    break;
-                }
-            }
-
-            if !inside {
+            let point =
+                glam::Vec3::select(plane.normal.cmpge(glam::Vec3::ZERO), bounds.max, bounds.min);
+            if plane.distance_to_point(point) < 0.0 {
                 return false;
             }
         }
@@ -150,7 +165,7 @@ impl Frustum {
 
     pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
         for plane in self.planes.iter() {
-            if plane.distance_to_sphere(sphere.center, sphere.radius) < 0.0 {
+            if plane.distance_to_point(sphere.center) < -sphere.radius {
                 return false;
             }
         }
@@ -185,3 +200,67 @@ impl Frustum {
         Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frustum() -> Frustum {
+        let projection = glam::Mat4::perspective_rh(90f32.to_radians(), 1.0, 0.1, 100.0);
+        let view = glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, 5.0),
+            glam::Vec3::ZERO,
+            glam::Vec3::Y,
+        );
+        Frustum::from_matrix(&(projection * view))
+    }
+
+    #[test]
+    fn from_matrix_planes_face_inward() {
+        let frustum = frustum();
+
+        assert!(frustum.contains_point(glam::Vec3::ZERO));
+        assert!(frustum.contains_point(glam::Vec3::new(4.9, 0.0, 0.0)));
+        assert!(frustum.contains_point(glam::Vec3::new(0.0, -4.9, 0.0)));
+        assert!(frustum.contains_point(glam::Vec3::new(0.0, 0.0, -94.0)));
+
+        assert!(!frustum.contains_point(glam::Vec3::new(5.1, 0.0, 0.0)));
+        assert!(!frustum.contains_point(glam::Vec3::new(0.0, 5.1, 0.0)));
+        assert!(!frustum.contains_point(glam::Vec3::new(0.0, 0.0, 4.95)));
+        assert!(!frustum.contains_point(glam::Vec3::new(0.0, 0.0, -96.0)));
+    }
+
+    #[test]
+    fn from_matrix_axes_follow_the_view() {
+        let frustum = frustum();
+
+        assert!(frustum.axes[0].abs_diff_eq(glam::Vec3::X, 1e-4));
+        assert!(frustum.axes[1].abs_diff_eq(glam::Vec3::Y, 1e-4));
+        assert!(frustum.axes[2].abs_diff_eq(glam::Vec3::NEG_Z, 1e-4));
+        assert!((frustum.half_extents.x - 100.0).abs() < 0.1);
+        assert!((frustum.half_extents.y - 100.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn bounds_straddling_a_plane_intersect_but_are_not_contained() {
+        let frustum = frustum();
+        let inside = Bounds::new(glam::Vec3::splat(-1.0), glam::Vec3::splat(1.0));
+        let straddling = Bounds::new(
+            glam::Vec3::new(4.0, -1.0, -1.0),
+            glam::Vec3::new(6.0, 1.0, 1.0),
+        );
+        let outside = Bounds::new(
+            glam::Vec3::new(10.0, -1.0, -1.0),
+            glam::Vec3::new(12.0, 1.0, 1.0),
+        );
+
+        assert!(frustum.contains_bounds(&inside));
+        assert!(frustum.intersects_bounds(&inside));
+
+        assert!(!frustum.contains_bounds(&straddling));
+        assert!(frustum.intersects_bounds(&straddling));
+
+        assert!(!frustum.contains_bounds(&outside));
+        assert!(!frustum.intersects_bounds(&outside));
+    }
+}
@@ -22,6 +22,14 @@ impl Plane {
         Self { normal, distance }
     }
 
+    pub fn from_coefficients(coefficients: glam::Vec4) -> Self {
+        let length = coefficients.truncate().length();
+        let normal = coefficients.truncate() / length;
+        let distance = -coefficients.w / length;
+
+        Self { normal, distance }
+    }
+
     pub fn from_point_and_normal(point: glam::Vec3, normal: glam::Vec3) -> Self {
         let distance = normal.dot(point);
 
@@ -1,5 +1,8 @@
-use super::color::Color;
-use crate::graphics::TextureId;
+use super::{color::Color, frustum::Frustum};
+use crate::{
+    graphics::{camera::Camera, TextureId},
+    shared::Rect,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub enum ProjectionMode {
@@ -7,6 +10,7 @@ pub enum ProjectionMode {
     Orthographic,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RenderTarget {
     Window,
     Texture(TextureId),
@@ -21,6 +25,7 @@ pub struct RenderView {
     order: usize,
     mode: ProjectionMode,
     clear_color: Color,
+    viewport: Rect,
     target: RenderTarget,
 }
 
@@ -45,10 +50,26 @@ impl RenderView {
             order,
             mode,
             clear_color,
+            viewport: Rect::new(0.0, 0.0, 1.0, 1.0),
             target,
         }
     }
 
+    pub fn from_camera(camera: &Camera, view: glam::Mat4) -> Self {
+        Self {
+            view,
+            size: camera.size,
+            near: camera.near,
+            far: camera.far,
+            fov: camera.fov,
+            order: camera.order,
+            mode: camera.mode,
+            clear_color: camera.clear_color,
+            viewport: camera.viewport,
+            target: camera.target,
+        }
+    }
+
     pub fn view(&self) -> &glam::Mat4 {
         &self.view
     }
@@ -81,10 +102,38 @@ impl RenderView {
         self.clear_color
     }
 
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
     pub fn target(&self) -> &RenderTarget {
         &self.target
     }
 
+    pub fn pixel_viewport(&self, width: u32, height: u32) -> Rect {
+        let (width, height) = (width as f32, height as f32);
+        let x = (self.viewport.x * width).clamp(0.0, width);
+        let y = (self.viewport.y * height).clamp(0.0, height);
+
+        Rect::new(
+            x,
+            y,
+            (self.viewport.width * width).min(width - x),
+            (self.viewport.height * height).min(height - y),
+        )
+    }
+
+    pub fn projection(&self, aspect: f32) -> glam::Mat4 {
+        match self.mode {
+            ProjectionMode::Perspective => self.perspective(aspect),
+            ProjectionMode::Orthographic => self.orthographic(aspect),
+        }
+    }
+
+    pub fn frustum(&self, aspect: f32) -> Frustum {
+        Frustum::from_matrix(&(self.projection(aspect) * self.view))
+    }
+
     pub fn orthographic(&self, aspect: f32) -> glam::Mat4 {
         let size = self.size;
         let near = self.near;
@@ -1,14 +1,18 @@
-use crate::graphics::{color::Color, TextureId};
+use crate::{
+    asset::Asset,
+    graphics::{color::Color, TextureId},
+};
+use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 use std::hash::Hasher;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ShaderModel {
     Lit = 0,
     Unlit = 1,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlendMode {
     Opaque = 0,
     Translucent = 1,
@@ -129,6 +133,24 @@ impl Material {
         self.opacity.as_ref()
     }
 
+    pub fn textures(&self) -> Vec<TextureId> {
+        [
+            Some(&self.color),
+            self.specular.as_ref(),
+            self.normal.as_ref(),
+            self.metallic.as_ref(),
+            self.roughness.as_ref(),
+            self.emissive.as_ref(),
+            self.opacity.as_ref(),
+        ]
+        .into_iter()
+        .filter_map(|input| match input {
+            Some(ShaderInput::Texture(id)) => Some(*id),
+            _ => None,
+        })
+        .collect()
+    }
+
     pub fn get_input_color(input: &Option<&ShaderInput>, default: [f32; 4]) -> [f32; 4] {
         match input {
             Some(ShaderInput::Color(color)) => color.into(),
@@ -154,6 +176,8 @@ impl Hash for Material {
     }
 }
 
+impl Asset for Material {}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct MaterialInfo {
     shader_model: ShaderModel,
@@ -226,6 +250,37 @@ impl MaterialInfo {
         self.opacity = Some(ShaderInput::Texture(opacity));
         self
     }
+
+    pub fn specular_color(mut self, specular: Color) -> Self {
+        self.specular = Some(ShaderInput::Color(specular));
+        self
+    }
+
+    pub fn specular_value(mut self, specular: f32) -> Self {
+        self.specular = Some(ShaderInput::Scalar(specular));
+        self
+    }
+
+    pub fn metallic_value(mut self, metallic: f32) -> Self {
+        self.metallic = Some(ShaderInput::Scalar(metallic));
+        self
+    }
+
+    pub fn roughness_value(mut self, roughness: f32) -> Self {
+        self.roughness = Some(ShaderInput::Scalar(roughness));
+        self
+    }
+
+    pub fn emissive_color(mut self, emissive: Color) -> Self {
+        self.emissive = Some(ShaderInput::Color(emissive));
+        self
+    }
+
+    pub fn opacity_value(mut self, opacity: f32) -> Self {
+        self.opacity = Some(ShaderInput::Scalar(opacity));
+        self
+    }
+
     pub fn build(&self) -> Material {
         Material {
             shader_model: self.shader_model,
@@ -240,3 +295,5 @@ impl MaterialInfo {
         }
     }
 }
+
+impl Asset for MaterialInfo {}
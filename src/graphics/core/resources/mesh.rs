@@ -1,8 +1,12 @@
-use crate::{graphics::vertex::Vertex, shared::Bounds};
-use std::rc::Rc;
+use crate::{
+    asset::Asset,
+    graphics::{vertex::Vertex, MaterialId},
+    shared::Bounds,
+};
+use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SubMesh {
     pub index_start: u32,
     pub index_count: u32,
@@ -10,8 +14,8 @@ pub struct SubMesh {
 
 #[derive(Clone)]
 pub struct Mesh {
-    vertex_buffer: Rc<wgpu::Buffer>,
-    index_buffer: Rc<wgpu::Buffer>,
+    vertex_buffer: Arc<wgpu::Buffer>,
+    index_buffer: Arc<wgpu::Buffer>,
     submeshes: Vec<SubMesh>,
     vertex_count: u32,
     index_count: u32,
@@ -44,8 +48,8 @@ impl Mesh {
         );
 
         Mesh {
-            vertex_buffer: Rc::new(vertex_buffer),
-            index_buffer: Rc::new(index_buffer),
+            vertex_buffer: Arc::new(vertex_buffer),
+            index_buffer: Arc::new(index_buffer),
             submeshes: submeshes.to_vec(),
             vertex_count: vertices.len() as u32,
             index_count: indices.len() as u32,
@@ -77,3 +81,47 @@ impl Mesh {
         &self.bounds
     }
 }
+
+impl Asset for Mesh {}
+
+#[derive(Clone, Debug)]
+pub struct MeshInfo {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub submeshes: Vec<SubMesh>,
+    pub materials: Vec<MaterialId>,
+}
+
+impl MeshInfo {
+    pub fn new(vertices: Vec<Vertex>, indices: Vec<u32>) -> Self {
+        let submeshes = vec![SubMesh {
+            index_start: 0,
+            index_count: indices.len() as u32,
+        }];
+
+        Self {
+            vertices,
+            indices,
+            submeshes,
+            materials: Vec::new(),
+        }
+    }
+
+    pub fn with_submeshes(mut self, submeshes: Vec<SubMesh>, materials: Vec<MaterialId>) -> Self {
+        self.submeshes = submeshes;
+        self.materials = materials;
+        self
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::from_points(
+            &self
+                .vertices
+                .iter()
+                .map(|v| glam::Vec3::from_array(v.position))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Asset for MeshInfo {}
@@ -1,4 +1,5 @@
 pub mod material;
 pub mod mesh;
+pub mod prefab;
 pub mod texture;
 pub mod shader;
\ No newline at end of file
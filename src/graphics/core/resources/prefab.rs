@@ -0,0 +1,144 @@
+use crate::{
+    asset::{Asset, Handle},
+    ecs::{
+        builtin::{Children, Parent, Transform},
+        world::events::CreateEntity,
+        EntityId, World,
+    },
+    graphics::{
+        camera::Camera, light::Light, material::MaterialInfo, mesh::MeshInfo,
+        mesh_renderer::MeshRenderer,
+    },
+};
+
+#[derive(Clone)]
+pub struct PrefabNode {
+    pub name: String,
+    pub transform: Transform,
+    pub parent: Option<usize>,
+    pub mesh: Option<Handle<MeshInfo>>,
+    pub materials: Vec<Handle<MaterialInfo>>,
+    pub camera: Option<Camera>,
+    pub light: Option<Light>,
+}
+
+impl PrefabNode {
+    pub fn new(name: impl Into<String>, transform: Transform) -> Self {
+        Self {
+            name: name.into(),
+            transform,
+            parent: None,
+            mesh: None,
+            materials: Vec::new(),
+            camera: None,
+            light: None,
+        }
+    }
+
+    pub fn with_parent(mut self, parent: Option<usize>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    pub fn with_mesh(
+        mut self,
+        mesh: Handle<MeshInfo>,
+        materials: Vec<Handle<MaterialInfo>>,
+    ) -> Self {
+        self.mesh = Some(mesh);
+        self.materials = materials;
+        self
+    }
+
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    pub fn with_light(mut self, light: Light) -> Self {
+        self.light = Some(light);
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct Prefab {
+    nodes: Vec<PrefabNode>,
+}
+
+impl Prefab {
+    pub fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    pub fn add_node(&mut self, node: PrefabNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn nodes(&self) -> &[PrefabNode] {
+        &self.nodes
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(index, _)| index)
+    }
+
+    pub fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, node)| node.parent == Some(index))
+            .map(|(index, _)| index)
+    }
+
+    pub fn spawn(&self, world: &World) -> Vec<EntityId> {
+        let entities = self
+            .nodes
+            .iter()
+            .map(|_| CreateEntity::new())
+            .collect::<Vec<_>>();
+        let ids = entities
+            .iter()
+            .map(|entity| *entity.id())
+            .collect::<Vec<_>>();
+
+        for (index, (node, mut entity)) in self.nodes.iter().zip(entities).enumerate() {
+            entity = entity.with(node.transform);
+
+            if let Some(parent) = node.parent {
+                entity = entity.with(Parent(ids[parent]));
+            }
+
+            let children = self
+                .children(index)
+                .map(|child| ids[child])
+                .collect::<Vec<_>>();
+            if !children.is_empty() {
+                entity = entity.with(Children { children });
+            }
+
+            if let Some(mesh) = &node.mesh {
+                entity = entity.with(MeshRenderer::from_handles(mesh, &node.materials));
+            }
+
+            if let Some(camera) = node.camera {
+                entity = entity.with(camera);
+            }
+
+            if let Some(light) = node.light {
+                entity = entity.with(light);
+            }
+
+            world.spawn(entity);
+        }
+
+        ids
+    }
+}
+
+impl Asset for Prefab {}
@@ -3,6 +3,7 @@ use crate::graphics::light::Light;
 
 pub mod program;
 pub mod resources;
+pub mod source;
 pub mod templates;
 
 #[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
@@ -28,20 +29,41 @@ impl CameraData {
     }
 }
 
-pub struct ObjectData {
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct InstanceData {
     model: [f32; 16],
+    color: [f32; 4],
 }
 
-impl ObjectData {
-    pub fn new(model: glam::Mat4) -> Self {
+impl InstanceData {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Float32x4,
+        7 => Float32x4,
+    ];
+
+    pub fn new(model: glam::Mat4, color: [f32; 4]) -> Self {
         Self {
             model: model.to_cols_array(),
+            color,
         }
     }
 
     pub fn identity() -> Self {
         Self {
             model: glam::Mat4::IDENTITY.to_cols_array(),
+            color: [1.0; 4],
+        }
+    }
+
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceData>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
         }
     }
 }
@@ -1,10 +1,14 @@
-use super::templates::{
-    forward::ForwardShaderTemplate,
-    layout::{MaterialBindGroupLayout, ShaderLayout},
-    unlit::UnlitShaderTemplate,
+use super::{
+    templates::{
+        forward::ForwardShaderTemplate,
+        layout::{MaterialBindGroupLayout, ShaderLayout},
+        unlit::UnlitShaderTemplate,
+    },
+    InstanceData,
 };
 use crate::graphics::{
     material::{Material, ShaderModel},
+    vertex::Vertex,
     Graphics, MaterialId,
 };
 use std::collections::HashMap;
@@ -29,9 +33,11 @@ impl ShaderProgram {
                 });
 
         let shader_module = match material.shader_model() {
-            ShaderModel::Lit => {
-                ForwardShaderTemplate::create_shader(graphics.gpu().device(), &shader_layout, 100)
-            }
+            ShaderModel::Lit => ForwardShaderTemplate::create_shader(
+                graphics.gpu().device(),
+                &shader_layout,
+                graphics.max_lights() as u32,
+            ),
             ShaderModel::Unlit => {
                 UnlitShaderTemplate::create_shader(graphics.gpu().device(), &shader_layout)
             }
@@ -55,11 +61,15 @@ impl ShaderProgram {
         material: &Material,
     ) {
         if !self.material_bind_groups.contains_key(&id) {
-            let bind_group = self.layout.create_bind_group(graphics, material);
+            let bind_group = self.layout.create_bind_group(graphics, &id, material);
             self.material_bind_groups.insert(id, bind_group);
         }
     }
 
+    pub fn remove_material_bind_group(&mut self, id: &MaterialId) -> bool {
+        self.material_bind_groups.remove(id).is_some()
+    }
+
     pub fn pipeline(&self) -> &wgpu::RenderPipeline {
         &self.pipeline
     }
@@ -82,7 +92,6 @@ impl ShaderProgram {
             ShaderModel::Lit => graphics.shader_resources().lit_global_layout(),
             ShaderModel::Unlit => graphics.shader_resources().unlit_global_layout(),
         };
-        let object_layout = graphics.shader_resources().object_layout();
 
         let pipeline_layout =
             graphics
@@ -90,7 +99,7 @@ impl ShaderProgram {
                 .device()
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                     label: Some("shader_pipeline_layout"),
-                    bind_group_layouts: &[global_layout, object_layout, &material_layout],
+                    bind_group_layouts: &[global_layout, &material_layout],
                     push_constant_ranges: &[],
                 });
 
@@ -104,27 +113,30 @@ impl ShaderProgram {
                     vertex: wgpu::VertexState {
                         module: &shader_module,
                         entry_point: "vs_main",
-                        buffers: &[wgpu::VertexBufferLayout {
-                            array_stride: std::mem::size_of::<f32>() as wgpu::BufferAddress,
-                            step_mode: wgpu::VertexStepMode::Vertex,
-                            attributes: &[
-                                wgpu::VertexAttribute {
-                                    offset: 0,
-                                    shader_location: 0,
-                                    format: wgpu::VertexFormat::Float32x3,
-                                },
-                                wgpu::VertexAttribute {
-                                    offset: std::mem::size_of::<[f32; 3]>() as u64,
-                                    shader_location: 1,
-                                    format: wgpu::VertexFormat::Float32x3,
-                                },
-                                wgpu::VertexAttribute {
-                                    offset: std::mem::size_of::<[f32; 6]>() as u64,
-                                    shader_location: 2,
-                                    format: wgpu::VertexFormat::Float32x2,
-                                },
-                            ],
-                        }],
+                        buffers: &[
+                            wgpu::VertexBufferLayout {
+                                array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                                step_mode: wgpu::VertexStepMode::Vertex,
+                                attributes: &[
+                                    wgpu::VertexAttribute {
+                                        offset: 0,
+                                        shader_location: 0,
+                                        format: wgpu::VertexFormat::Float32x3,
+                                    },
+                                    wgpu::VertexAttribute {
+                                        offset: std::mem::size_of::<[f32; 3]>() as u64,
+                                        shader_location: 1,
+                                        format: wgpu::VertexFormat::Float32x3,
+                                    },
+                                    wgpu::VertexAttribute {
+                                        offset: std::mem::size_of::<[f32; 6]>() as u64,
+                                        shader_location: 2,
+                                        format: wgpu::VertexFormat::Float32x2,
+                                    },
+                                ],
+                            },
+                            InstanceData::layout(),
+                        ],
                     },
                     fragment: Some(wgpu::FragmentState {
                         module: &shader_module,
@@ -1,12 +1,10 @@
-use super::{CameraData, LightData, LitMaterialData, ObjectData, UnlitMaterialData};
+use super::{CameraData, LightData, LitMaterialData, UnlitMaterialData};
 
 pub struct ShaderResources {
     unlit_global_layout: wgpu::BindGroupLayout,
     lit_global_layout: wgpu::BindGroupLayout,
-    object_layout: wgpu::BindGroupLayout,
     lit_material: wgpu::Buffer,
     unlit_material: wgpu::Buffer,
-    object: wgpu::Buffer,
     camera: wgpu::Buffer,
     lights: wgpu::Buffer,
 }
@@ -27,13 +25,6 @@ impl ShaderResources {
             mapped_at_creation: false,
         });
 
-        let object = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("object"),
-            size: std::mem::size_of::<ObjectData>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         let camera = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("camera"),
             size: std::mem::size_of::<CameraData>() as u64,
@@ -89,27 +80,11 @@ impl ShaderResources {
                 }],
             });
 
-        let object_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("object_layout"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        });
-
         ShaderResources {
             lit_global_layout,
             unlit_global_layout,
-            object_layout,
             lit_material,
             unlit_material,
-            object,
             camera,
             lights,
         }
@@ -123,10 +98,6 @@ impl ShaderResources {
         &self.unlit_global_layout
     }
 
-    pub fn object_layout(&self) -> &wgpu::BindGroupLayout {
-        &self.object_layout
-    }
-
     pub fn lit_material(&self) -> &wgpu::Buffer {
         &self.lit_material
     }
@@ -135,10 +106,6 @@ impl ShaderResources {
         &self.unlit_material
     }
 
-    pub fn object(&self) -> &wgpu::Buffer {
-        &self.object
-    }
-
     pub fn camera(&self) -> &wgpu::Buffer {
         &self.camera
     }
@@ -0,0 +1,618 @@
+use crate::{
+    asset::{Asset, ImportError},
+    shared::ext::path::PathExt,
+};
+use naga::{
+    valid::{Capabilities, ValidationFlags, Validator},
+    SourceLocation,
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+const ENTRY_POINTS: [&str; 3] = ["vertex", "fragment", "compute"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShaderDefs {
+    values: BTreeMap<String, String>,
+}
+
+impl ShaderDefs {
+    pub fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+        }
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut defs = Self::new();
+        for entry in text.split_whitespace() {
+            match entry.split_once('=') {
+                Some((name, value)) => defs.define(name, value),
+                None => defs.define(entry, ""),
+            }
+        }
+
+        defs
+    }
+
+    pub fn with(mut self, name: &str) -> Self {
+        self.define(name, "");
+        self
+    }
+
+    pub fn with_value(mut self, name: &str, value: impl ToString) -> Self {
+        self.define(name, value.to_string());
+        self
+    }
+
+    pub fn define(&mut self, name: &str, value: impl Into<String>) {
+        self.values.insert(name.to_string(), value.into());
+    }
+
+    pub fn undefine(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn merge(&self, other: &ShaderDefs) -> ShaderDefs {
+        let mut defs = self.clone();
+        defs.values
+            .extend(other.values.iter().map(|(k, v)| (k.clone(), v.clone())));
+        defs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShaderFile {
+    pub key: PathBuf,
+    pub path: PathBuf,
+    pub source: String,
+}
+
+#[derive(Debug)]
+pub struct ComposedShader {
+    pub code: String,
+    lines: Vec<(usize, usize)>,
+}
+
+impl ComposedShader {
+    pub fn has_entry_points(&self) -> bool {
+        let code = strip_comments(&self.code);
+        let mut rest = code.as_str();
+        while let Some(start) = rest.find('@') {
+            rest = &rest[start + 1..];
+            let name = identifier(rest);
+            if ENTRY_POINTS.contains(&name) && declares_fn(&rest[name.len()..]) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn source_line(&self, line: usize) -> Option<(usize, usize)> {
+        self.lines.get(line.checked_sub(1)?).copied()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShaderInfo {
+    files: Vec<ShaderFile>,
+    defs: ShaderDefs,
+    permutations: Vec<ShaderDefs>,
+}
+
+impl ShaderInfo {
+    pub fn new(path: impl AsRef<Path>, source: impl Into<String>) -> Self {
+        let path = path.as_ref();
+        let key = PathBuf::from(path.file_name().unwrap_or_default());
+        let main = ShaderFile {
+            key,
+            path: path.to_path_buf(),
+            source: source.into(),
+        };
+
+        Self {
+            files: vec![main],
+            defs: ShaderDefs::new(),
+            permutations: Vec::new(),
+        }
+    }
+
+    pub fn with_defs(mut self, defs: ShaderDefs) -> Self {
+        self.defs = defs;
+        self
+    }
+
+    pub fn with_permutations(mut self, permutations: Vec<ShaderDefs>) -> Self {
+        self.permutations = permutations;
+        self
+    }
+
+    pub fn add_include(&mut self, key: PathBuf, path: PathBuf, source: String) -> usize {
+        self.files.push(ShaderFile { key, path, source });
+        self.files.len() - 1
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.files[0].path
+    }
+
+    pub fn files(&self) -> &[ShaderFile] {
+        &self.files
+    }
+
+    pub fn file(&self, key: &Path) -> Option<usize> {
+        self.files.iter().position(|file| file.key == key)
+    }
+
+    pub fn defs(&self) -> &ShaderDefs {
+        &self.defs
+    }
+
+    pub fn permutations(&self) -> &[ShaderDefs] {
+        &self.permutations
+    }
+
+    pub fn includes(&self, index: usize) -> Vec<PathBuf> {
+        let file = &self.files[index];
+        file.source
+            .lines()
+            .filter_map(|text| {
+                let include = directive(text)?.strip_prefix("include")?;
+                resolve_include(&file.key, include)
+            })
+            .collect()
+    }
+
+    pub fn compose(&self, defs: &ShaderDefs) -> Result<ComposedShader, ImportError> {
+        let mut composed = ComposedShader {
+            code: String::new(),
+            lines: Vec::new(),
+        };
+
+        let mut defs = self.defs.merge(defs);
+        let mut stack = Vec::new();
+        let mut included = Vec::new();
+        self.compose_file(0, &mut defs, &mut composed, &mut stack, &mut included)?;
+
+        Ok(composed)
+    }
+
+    pub fn validate(&self, defs: &ShaderDefs) -> Result<String, ImportError> {
+        let composed = self.compose(defs)?;
+        let module = naga::front::wgsl::parse_str(&composed.code).map_err(|e| {
+            let message = match e.labels().next() {
+                Some((_, label)) if !label.is_empty() => format!("{}: {}", e.message(), label),
+                _ => e.message().to_string(),
+            };
+            self.locate(&composed, e.location(&composed.code), message)
+        })?;
+
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map_err(|e| {
+                let mut message = e.as_inner().to_string();
+                let mut source = e.as_inner().source();
+                while let Some(inner) = source {
+                    message.push_str(&format!(": {}", inner));
+                    source = inner.source();
+                }
+                self.locate(&composed, e.location(&composed.code), message)
+            })?;
+
+        Ok(composed.code)
+    }
+
+    fn compose_file(
+        &self,
+        index: usize,
+        defs: &mut ShaderDefs,
+        composed: &mut ComposedShader,
+        stack: &mut Vec<usize>,
+        included: &mut Vec<usize>,
+    ) -> Result<(), ImportError> {
+        let file = &self.files[index];
+        let error = |line: usize, message: String| {
+            ImportError::new(message)
+                .with_path(&file.path)
+                .with_line(line)
+        };
+
+        stack.push(index);
+        included.push(index);
+
+        let mut conditions: Vec<Condition> = Vec::new();
+        for (line, text) in file.source.lines().enumerate() {
+            let line = line + 1;
+            let active = conditions.iter().all(|condition| condition.active);
+
+            let Some(directive) = directive(text) else {
+                if active {
+                    composed.code.push_str(&substitute(text, defs));
+                    composed.code.push('\n');
+                    composed.lines.push((index, line));
+                }
+                continue;
+            };
+
+            let (name, argument) = match directive.split_once(char::is_whitespace) {
+                Some((name, argument)) => (name, argument.trim()),
+                None => (directive, ""),
+            };
+
+            match name {
+                "ifdef" | "ifndef" => {
+                    if argument.is_empty() {
+                        return Err(error(line, format!("#{} needs a name", name)));
+                    }
+
+                    let defined = defs.contains(argument);
+                    conditions.push(Condition {
+                        line,
+                        active: if name == "ifdef" { defined } else { !defined },
+                        has_else: false,
+                    });
+                }
+                "else" => match conditions.last_mut() {
+                    Some(condition) if !condition.has_else => {
+                        condition.active = !condition.active;
+                        condition.has_else = true;
+                    }
+                    Some(_) => return Err(error(line, "Duplicate #else".to_string())),
+                    None => return Err(error(line, "#else without #ifdef".to_string())),
+                },
+                "endif" => {
+                    if conditions.pop().is_none() {
+                        return Err(error(line, "#endif without #ifdef".to_string()));
+                    }
+                }
+                _ if !active => {}
+                "define" => {
+                    let (define, value) = match argument.split_once(char::is_whitespace) {
+                        Some((define, value)) => (define, value.trim()),
+                        None => (argument, ""),
+                    };
+                    if define.is_empty() {
+                        return Err(error(line, "#define needs a name".to_string()));
+                    }
+
+                    defs.define(define, value);
+                }
+                "undef" => defs.undefine(argument),
+                "include" => {
+                    let key = resolve_include(&file.key, argument)
+                        .ok_or_else(|| error(line, format!("Invalid include '{}'", argument)))?;
+                    let include = self.file(&key).ok_or_else(|| {
+                        error(line, format!("Include '{}' not found", key.display()))
+                    })?;
+
+                    if stack.contains(&include) {
+                        let cycle = stack
+                            .iter()
+                            .chain(std::iter::once(&include))
+                            .map(|i| self.files[*i].key.display().to_string())
+                            .collect::<Vec<_>>();
+                        return Err(error(
+                            line,
+                            format!("Include cycle: {}", cycle.join(" -> ")),
+                        ));
+                    }
+
+                    if !included.contains(&include) {
+                        self.compose_file(include, defs, composed, stack, included)?;
+                    }
+                }
+                _ => return Err(error(line, format!("Unknown directive #{}", name))),
+            }
+        }
+
+        if let Some(condition) = conditions.last() {
+            return Err(error(condition.line, "Unterminated #ifdef".to_string()));
+        }
+
+        stack.pop();
+        Ok(())
+    }
+
+    fn locate(
+        &self,
+        composed: &ComposedShader,
+        location: Option<SourceLocation>,
+        message: String,
+    ) -> ImportError {
+        let source =
+            location.and_then(|location| composed.source_line(location.line_number as usize));
+        match source {
+            Some((file, line)) => ImportError::new(message)
+                .with_path(&self.files[file].path)
+                .with_line(line),
+            None => ImportError::new(message).with_path(self.path()),
+        }
+    }
+}
+
+impl Asset for ShaderInfo {}
+
+struct Condition {
+    line: usize,
+    active: bool,
+    has_else: bool,
+}
+
+fn directive(line: &str) -> Option<&str> {
+    line.trim().strip_prefix('#').map(str::trim)
+}
+
+fn resolve_include(from: &Path, include: &str) -> Option<PathBuf> {
+    let include = include.trim().strip_prefix('"')?.strip_suffix('"')?;
+    if include.is_empty() {
+        return None;
+    }
+
+    let folder = from.parent().unwrap_or(Path::new(""));
+    Some(folder.join(include).normalize())
+}
+
+fn strip_comments(code: &str) -> String {
+    let mut output = String::with_capacity(code.len());
+    let mut depth = 0;
+    let mut rest = code;
+    while !rest.is_empty() {
+        if depth == 0 && rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+        } else if rest.starts_with("/*") {
+            depth += 1;
+            rest = &rest[2..];
+        } else if depth > 0 && rest.starts_with("*/") {
+            depth -= 1;
+            output.push(' ');
+            rest = &rest[2..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            if depth == 0 {
+                output.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    output
+}
+
+fn identifier(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// Skips any further attributes and checks that a function declaration follows.
+fn declares_fn(mut rest: &str) -> bool {
+    loop {
+        rest = rest.trim_start();
+        let Some(attribute) = rest.strip_prefix('@') else {
+            return identifier(rest) == "fn";
+        };
+
+        rest = attribute.trim_start();
+        rest = rest[identifier(rest).len()..].trim_start();
+        if rest.starts_with('(') {
+            let Some(end) = rest.find(')') else {
+                return false;
+            };
+            rest = &rest[end + 1..];
+        }
+    }
+}
+
+fn substitute(line: &str, defs: &ShaderDefs) -> String {
+    if defs.iter().all(|(_, value)| value.is_empty()) {
+        return line.to_string();
+    }
+
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !(c.is_ascii_alphabetic() || c == '_') {
+            output.push(c);
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some((i, c)) = chars.peek() {
+            if !(c.is_ascii_alphanumeric() || *c == '_') {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+
+        let word = &line[start..end];
+        match defs.get(word) {
+            Some(value) if !value.is_empty() => output.push_str(value),
+            _ => output.push_str(word),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str =
+        "@vertex\nfn vs_main() -> @builtin(position) vec4<f32> {\n    return vec4<f32>(0.0);\n}\n";
+
+    fn shader(source: &str, includes: &[(&str, &str)]) -> ShaderInfo {
+        let mut info = ShaderInfo::new("shaders/main.wgsl", source);
+        for (key, source) in includes {
+            info.add_include(
+                PathBuf::from(key),
+                Path::new("shaders").join(key),
+                source.to_string(),
+            );
+        }
+
+        info
+    }
+
+    fn lines(composed: &ComposedShader) -> Vec<&str> {
+        composed.code.lines().collect()
+    }
+
+    #[test]
+    fn ifdef_selects_branch() {
+        let info = shader("#ifdef A\na\n#else\nb\n#endif\nc", &[]);
+
+        let defined = info.compose(&ShaderDefs::new().with("A")).unwrap();
+        assert_eq!(lines(&defined), ["a", "c"]);
+
+        let undefined = info.compose(&ShaderDefs::new()).unwrap();
+        assert_eq!(lines(&undefined), ["b", "c"]);
+        assert_eq!(undefined.source_line(1), Some((0, 4)));
+    }
+
+    #[test]
+    fn ifndef_and_nested_conditions() {
+        let info = shader("#ifndef A\n#ifdef B\nb\n#endif\nnot_a\n#endif", &[]);
+
+        let composed = info.compose(&ShaderDefs::new().with("B")).unwrap();
+        assert_eq!(lines(&composed), ["b", "not_a"]);
+
+        let composed = info
+            .compose(&ShaderDefs::new().with("A").with("B"))
+            .unwrap();
+        assert!(composed.code.is_empty());
+    }
+
+    #[test]
+    fn define_substitutes_values() {
+        let info = shader("#define COUNT 4\nlet n = COUNT;", &[]);
+
+        let composed = info.compose(&ShaderDefs::new()).unwrap();
+        assert_eq!(lines(&composed), ["let n = 4;"]);
+    }
+
+    #[test]
+    fn unbalanced_conditions_fail() {
+        let error = shader("#ifdef A\na", &[])
+            .compose(&ShaderDefs::new())
+            .unwrap_err();
+        assert_eq!(error.line, Some(1));
+
+        let error = shader("a\n#endif", &[])
+            .compose(&ShaderDefs::new())
+            .unwrap_err();
+        assert_eq!(error.line, Some(2));
+
+        let error = shader("#ifdef A\n#else\n#else\n#endif", &[])
+            .compose(&ShaderDefs::new())
+            .unwrap_err();
+        assert_eq!(error.message, "Duplicate #else");
+    }
+
+    #[test]
+    fn includes_are_composed_once() {
+        let info = shader(
+            "#include \"a.wgsl\"\n#include \"b.wgsl\"\nmain",
+            &[("a.wgsl", "#include \"b.wgsl\"\na"), ("b.wgsl", "b")],
+        );
+
+        let composed = info.compose(&ShaderDefs::new()).unwrap();
+        assert_eq!(lines(&composed), ["b", "a", "main"]);
+        assert_eq!(composed.source_line(1), Some((2, 1)));
+        assert_eq!(composed.source_line(3), Some((0, 3)));
+    }
+
+    #[test]
+    fn include_cycle_fails() {
+        let info = shader(
+            "#include \"a.wgsl\"",
+            &[
+                ("a.wgsl", "#include \"b.wgsl\""),
+                ("b.wgsl", "\n#include \"a.wgsl\""),
+            ],
+        );
+
+        let error = info.compose(&ShaderDefs::new()).unwrap_err();
+        assert_eq!(error.path, Path::new("shaders/b.wgsl"));
+        assert_eq!(error.line, Some(2));
+        assert_eq!(
+            error.message,
+            "Include cycle: main.wgsl -> a.wgsl -> b.wgsl -> a.wgsl"
+        );
+    }
+
+    #[test]
+    fn missing_include_fails() {
+        let info = shader("a\n#include \"missing.wgsl\"", &[]);
+
+        let error = info.compose(&ShaderDefs::new()).unwrap_err();
+        assert_eq!(error.path, Path::new("shaders/main.wgsl"));
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.message, "Include 'missing.wgsl' not found");
+    }
+
+    #[test]
+    fn parse_errors_map_to_included_file() {
+        let info = shader(
+            &format!("#include \"lib.wgsl\"\n{}", ENTRY),
+            &[("lib.wgsl", "fn helper() -> f32 {\n    return 1.0\n}")],
+        );
+
+        let error = info.validate(&ShaderDefs::new()).unwrap_err();
+        assert_eq!(error.path, Path::new("shaders/lib.wgsl"));
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn validation_errors_map_to_included_file() {
+        let info = shader(
+            &format!("#include \"lib.wgsl\"\n{}", ENTRY),
+            &[("lib.wgsl", "\nfn helper() -> f32 {\n    return 1u;\n}")],
+        );
+
+        let error = info.validate(&ShaderDefs::new()).unwrap_err();
+        assert_eq!(error.path, Path::new("shaders/lib.wgsl"));
+        assert_eq!(error.line, Some(2));
+    }
+
+    #[test]
+    fn entry_points_ignore_comments() {
+        let has_entry_points = |source: &str| {
+            shader(source, &[])
+                .compose(&ShaderDefs::new())
+                .unwrap()
+                .has_entry_points()
+        };
+
+        assert!(has_entry_points(ENTRY));
+        assert!(has_entry_points(
+            "@compute @workgroup_size(8, 8)\nfn main() {}"
+        ));
+        assert!(!has_entry_points("// @vertex fn main() {}\nfn helper() {}"));
+        assert!(!has_entry_points("/* @fragment\nfn main() {} */"));
+        assert!(!has_entry_points("@vertex_output struct A {}"));
+        assert!(!has_entry_points("fn helper() {}"));
+    }
+}
@@ -22,217 +22,199 @@ fn forward_shader_template(layout: &ShaderLayout, max_lights: u32) -> String {
     let opacity_var = if layout.is_opaque() {
         "1.0"
     } else {
-        "opacity.x"
+        "opacity.x * input.color.a"
     };
 
     let texture_defs = layout.texture_binding_defs(1);
+    let sampler_defs = layout.sampler_binding_defs(1);
     let texture_vars = layout.texture_binding_vars();
     let material_vars = layout.material_attribute_vars("material");
 
     format!(
         r#"
+    struct VertexInput {{
+        @location(0) position: vec3<f32>,
+        @location(1) normal: vec3<f32>,
+        @location(2) tex_coords: vec2<f32>,
+    }}
+
+    struct InstanceInput {{
+        @location(3) model_0: vec4<f32>,
+        @location(4) model_1: vec4<f32>,
+        @location(5) model_2: vec4<f32>,
+        @location(6) model_3: vec4<f32>,
+        @location(7) color: vec4<f32>,
+    }}
+
+    struct VertexOutput {{
+        @builtin(position) clip_position: vec4<f32>,
+        @location(0) position: vec3<f32>,
+        @location(1) normal: vec3<f32>,
+        @location(2) tex_coords: vec2<f32>,
+        @location(3) color: vec4<f32>,
+        @location(4) eye: vec3<f32>,
+    }}
+
+    struct Camera {{
+        view: mat4x4<f32>,
+        projection: mat4x4<f32>,
+    }}
+
     struct MaterialUniform {{
-        color: vec4<f32>;
-        specular: vec4<f32>;
-        normal: vec4<f32>;
-        metallic: vec4<f32>;
-        roughness: vec4<f32>;
-        emissive: vec4<f32>;
-        opacity: vec4<f32>;
-    }};
+        color: vec4<f32>,
+        specular: vec4<f32>,
+        metallic: vec4<f32>,
+        roughness: vec4<f32>,
+        emissive: vec4<f32>,
+        opacity: vec4<f32>,
+    }}
 
     struct Light {{
-        position: vec3<f32>;
-        direction: vec3<f32>;
-        color: vec3<f32>;
-        intensity: f32;
-        range: f32;
-        kind: u32;
-        spot_angle: f32;
-    }};
+        position: vec3<f32>,
+        direction: vec3<f32>,
+        color: vec3<f32>,
+        _padding: f32,
+        intensity: f32,
+        range: f32,
+        spot_angle: f32,
+        kind: u32,
+    }}
 
     struct LightsBuffer {{
-        lights: array<Light, {max_lights}>;
-    }};
+        lights: array<Light, {max_lights}>,
+    }}
+
+    struct Surface {{
+        color: vec3<f32>,
+        specular: vec3<f32>,
+        metallic: f32,
+        roughness: f32,
+    }}
+
+    @group(0) @binding(0)
+    var<uniform> camera: Camera;
 
     @group(0) @binding(1)
     var<uniform> lights_buffer: LightsBuffer;
-    
+
     @group(1) @binding(0)
     var<uniform> material: MaterialUniform;
 
     {texture_defs}
-    
-    struct FragmentInput {{
-        @location(0) position: vec3<f32>,
-        @location(1) normal: vec3<f32>,
-        @location(2) tex_coords: vec2<f32>,
-    }};
+    {sampler_defs}
+
+    @vertex
+    fn vs_main(input: VertexInput, instance: InstanceInput) -> VertexOutput {{
+        let model = mat4x4<f32>(
+            instance.model_0,
+            instance.model_1,
+            instance.model_2,
+            instance.model_3,
+        );
+        let position = model * vec4<f32>(input.position, 1.0);
+
+        var out: VertexOutput;
+        out.clip_position = camera.projection * camera.view * position;
+        out.position = position.xyz;
+        out.normal = (model * vec4<f32>(input.normal, 0.0)).xyz;
+        out.tex_coords = input.tex_coords;
+        out.color = instance.color;
+
+        let rotation = mat3x3<f32>(camera.view[0].xyz, camera.view[1].xyz, camera.view[2].xyz);
+        out.eye = -(transpose(rotation) * camera.view[3].xyz);
+        return out;
+    }}
+
+    fn shade(light: Light, direction: vec3<f32>, attenuation: f32, normal: vec3<f32>, view_direction: vec3<f32>, surface: Surface) -> vec3<f32> {{
+        let radiance = light.color * light.intensity * attenuation;
+        let diffuse_factor = max(dot(normal, direction), 0.0);
+        let half_vector = normalize(direction + view_direction);
+        let shininess = mix(256.0, 2.0, clamp(surface.roughness, 0.0, 1.0));
+        let specular_factor = pow(max(dot(normal, half_vector), 0.0), shininess) * diffuse_factor;
+
+        let diffuse = surface.color * (1.0 - surface.metallic) * diffuse_factor;
+        let specular = mix(surface.specular, surface.color, surface.metallic) * specular_factor;
+        return (diffuse + specular) * radiance;
+    }}
 
-    @location(0) var<out> fragColor: vec4<f32>;
+    fn distance_attenuation(distance: f32) -> f32 {{
+        return 1.0 / (1.0 + 0.05 * distance + 0.007 * distance * distance);
+    }}
+
+    fn calculate_dir_light(position: vec3<f32>, normal: vec3<f32>, view_direction: vec3<f32>, surface: Surface, light: Light) -> vec3<f32> {{
+        let direction = -normalize(light.direction);
+        return shade(light, direction, 1.0, normal, view_direction, surface);
+    }}
 
-    fn calculate_light(position: vec3<f32>, normal: vec3<f32>, material: MaterialUniform, light: Light) -> vec3<f32> {{
+    fn calculate_point_light(position: vec3<f32>, normal: vec3<f32>, view_direction: vec3<f32>, surface: Surface, light: Light) -> vec3<f32> {{
+        let to_light = light.position - position;
+        let attenuation = distance_attenuation(length(to_light));
+        return shade(light, normalize(to_light), attenuation, normal, view_direction, surface);
+    }}
+
+    fn calculate_spot_light(position: vec3<f32>, normal: vec3<f32>, view_direction: vec3<f32>, surface: Surface, light: Light) -> vec3<f32> {{
+        let to_light = light.position - position;
+        let direction = normalize(to_light);
+        let outer = cos(light.spot_angle);
+        let inner = min(outer + 0.05, 1.0);
+        let cone = smoothstep(outer, inner, dot(-direction, normalize(light.direction)));
+        let attenuation = distance_attenuation(length(to_light)) * cone;
+        return shade(light, direction, attenuation, normal, view_direction, surface);
+    }}
+
+    fn calculate_light(position: vec3<f32>, normal: vec3<f32>, view_direction: vec3<f32>, surface: Surface, light: Light) -> vec3<f32> {{
         if light.kind == 0u {{
-            return calculate_point_light(position, normal, material, light);
+            return calculate_dir_light(position, normal, view_direction, surface, light);
         }} else if light.kind == 1u {{
-            return calculate_spot_light(position, normal, material, light);
+            return calculate_point_light(position, normal, view_direction, surface, light);
         }} else if light.kind == 2u {{
-            return calculate_dir_light(position, normal, material, light);
-        }} else {{
-            return vec3<f32>(0.0, 0.0, 0.0);
+            return calculate_spot_light(position, normal, view_direction, surface, light);
         }}
-    }}
-
-    fn calculate_lights(position: vec3<f32>, normal: vec3<f32>, material: MaterialUniform, lights: array<Light, {max_lights}>) -> vec3<f32> {{
-        let mut color = vec3<f32>(0.0, 0.0, 0.0);
 
-        for light in lights {{
-            color += calculate_light(light, normal, position);
-        }}
+        return vec3<f32>(0.0, 0.0, 0.0);
+    }}
 
-        color
-    }}
-
-    fn calculate_point_light(
-        fragCoord: vec3<f32>,
-        fragNormal: vec3<f32>,
-        material: MaterialUniform,
-        light: Light
-    ) -> vec3<f32> {{
-        var lightDirection = normalize(light.position - fragCoord);
-        var lightColor = light.color;
-        var attenuation = 1.0;
-    
-        // Point light
-        var lightToFragment = light.position - fragCoord;
-        var lightDistance = length(lightToFragment);
-        attenuation = 1.0 / (1.0 + 0.05 * lightDistance + 0.007 * lightDistance * lightDistance);
-    
-        // Calculate the diffuse reflection
-        var diffuseFactor = max(dot(fragNormal, lightDirection), 0.0);
-        var diffuse = material.diffuseColor * lightColor * diffuseFactor * attenuation;
-    
-        // Calculate the specular reflection (Cook-Torrance model with roughness and metallic)
-        var viewDirection = normalize(-fragCoord); // Assuming camera is at the origin
-        var halfVector = normalize(lightDirection + viewDirection);
-        var NdotH = max(dot(fragNormal, halfVector), 0.0);
-        var D = (2.0 * NdotH * NdotH - 1.0) / (material.roughness * material.roughness * NdotH * NdotH);
-        var G = (2.0 * NdotH) / (dot(fragNormal, viewDirection) + dot(fragNormal, lightDirection));
-        var F = mix(0.04, 1.0, material.metallic);
-        var specular = ((D * G * F) / (4.0 * dot(fragNormal, viewDirection) * dot(fragNormal, lightDirection))) * material.specularColor * lightColor * attenuation;
-    
-        // Calculate the final color
-        return diffuse + specular;
-    }}
-
-    fn calculate_dir_light(
-        fragCoord: vec3<f32>,
-        fragNormal: vec3<f32>,
-        material: MaterialUniform,
-        light: Light
-    ) -> vec3<f32> {{
-        var lightDirection = -normalize(light.direction);
-        var lightColor = light.color;
-    
-        // Directional light
-        var attenuation = 1.0; // No attenuation for directional lights
-    
-        // Calculate the diffuse reflection
-        var diffuseFactor = max(dot(fragNormal, lightDirection), 0.0);
-        var diffuse = material.diffuseColor * lightColor * diffuseFactor * attenuation;
-    
-        // Calculate the specular reflection (Cook-Torrance model with roughness and metallic)
-        var viewDirection = normalize(-fragCoord); // Assuming camera is at the origin
-        var halfVector = normalize(lightDirection + viewDirection);
-        var NdotH = max(dot(fragNormal, halfVector), 0.0);
-        var D = (2.0 * NdotH * NdotH - 1.0) / (material.roughness * material.roughness * NdotH * NdotH);
-        var G = (2.0 * NdotH) / (dot(fragNormal, viewDirection) + dot(fragNormal, lightDirection));
-        var F = mix(0.04, 1.0, material.metallic);
-        var specular = ((D * G * F) / (4.0 * dot(fragNormal, viewDirection) * dot(fragNormal, lightDirection))) * material.specularColor * lightColor * attenuation;
-    
-        // Calculate the final color
-        return diffuse + specular;
-    }}
-    
-
-    fn calculate_spot_light(
-        fragCoord: vec3<f32>,
-        fragNormal: vec3<f32>,
-        material: MaterialUniform,
-        light: Light
-    ) -> vec3<f32> {{
-        var lightDirection = normalize(light.position - fragCoord);
-        var lightColor = light.color;
-        var attenuation = 1.0;
-    
-        // Spot light
-        var lightToFragment = normalize(light.position - fragCoord);
-        var lightDistance = length(lightToFragment);
-        var spotAngleCos = dot(-lightToFragment, normalize(light.direction));
-    
-        // Check if the fragment is inside the spot cone
-        if (spotAngleCos > 0.85) {{
-            // Apply attenuation based on distance and spot angle
-            attenuation = 1.0 / (1.0 +  0.05 * lightDistance + 0.007 * lightDistance * lightDistance);
-        }} else if (spotAngleCos > 0.9) {{
-            // Apply penumbra attenuation if inside the inner cone
-            var smoothstepFactor = smoothstep(0.9, 0.85, spotAngleCos);
-            attenuation = 1.0 / (1.0 +  0.05 * lightDistance + 0.007 * lightDistance * lightDistance);
-            attenuation = attenuation * smoothstepFactor;
-        }} else {{
-            // Outside of the spot cone
-            attenuation = 0.0;
-        }}
-    
-        // Calculate the diffuse reflection
-        var diffuseFactor = max(dot(fragNormal, lightDirection), 0.0);
-        var diffuse = material.diffuseColor * lightColor * diffuseFactor * attenuation;
-    
-        // Calculate the specular reflection (Cook-Torrance model with roughness and metallic)
-        var viewDirection = normalize(-fragCoord); // Assuming camera is at the origin
-        var halfVector = normalize(lightDirection + viewDirection);
-        var NdotH = max(dot(fragNormal, halfVector), 0.0);
-        var D = (2.0 * NdotH * NdotH - 1.0) / (material.roughness * material.roughness * NdotH * NdotH);
-        var G = (2.0 * NdotH) / (dot(fragNormal, viewDirection) + dot(fragNormal, lightDirection));
-        var F = mix(0.04, 1.0, material.metallic);
-        var specular = ((D * G * F) / (4.0 * dot(fragNormal, viewDirection) * dot(fragNormal, lightDirection))) * material.specularColor * lightColor * attenuation;
-    
-        // Calculate the final color
-        return diffuse + specular;
-    }}    
-
-    fn main(input: FragmentInput) {{
-        // Normalize the normal vector
-        var normal : vec3<f32> = normalize(input.normal);
-
-        // Get color from texture or uniform
+    @fragment
+    fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {{
         {texture_vars}
         {material_vars}
 
-        var material_uniform: MaterialUniform = MaterialUniform(
-            color,
-            specular,
-            normal,
-            metallic,
-            roughness,
-            emissive,
-            opacity
-        );
+        let world_normal = normalize(input.normal);
+        let view_direction = normalize(input.eye - input.position);
 
-        // Calculate the lights
-        var finalColor: vec3<f32> = calculate_lights(input.position, normal, material_uniform, lights_buffer.lights);
+        var surface: Surface;
+        surface.color = color.rgb * input.color.rgb;
+        surface.specular = specular.rgb;
+        surface.metallic = metallic.x;
+        surface.roughness = roughness.x;
 
-        // Multiply the final color by the texture color
-        finalColor = finalColor * color;
+        var final_color = emissive.rgb;
+        for (var i = 0u; i < {max_lights}u; i = i + 1u) {{
+            final_color += calculate_light(input.position, world_normal, view_direction, surface, lights_buffer.lights[i]);
+        }}
 
-        // Set the output color
-        fragColor = vec4<f32>(finalColor, {opacity_var});
+        return vec4<f32>(final_color, {opacity_var});
     }}
     "#,
         texture_defs = texture_defs,
+        sampler_defs = sampler_defs,
         texture_vars = texture_vars,
         material_vars = material_vars,
         opacity_var = opacity_var,
         max_lights = max_lights,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::layout::tests::*, *};
+    use crate::graphics::material::ShaderModel;
+
+    #[test]
+    fn forward_template_is_valid_wgsl() {
+        for material in materials(ShaderModel::Lit) {
+            let layout = ShaderLayout::from_material(&material);
+            validate(&layout, &forward_shader_template(&layout, 4));
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::graphics::{
     material::{BlendMode, Material, ShaderInput, ShaderModel},
     texture::Texture,
-    Graphics,
+    Graphics, MaterialId,
 };
 
 pub struct ShaderLayout {
@@ -24,14 +24,16 @@ impl ShaderLayout {
     pub fn from_material(material: &Material) -> ShaderLayout {
         let mut layout = ShaderLayout::new(material.shader_model(), material.blend_mode());
         layout.add_input("color", Some(material.color()));
-        layout.add_input("opacity", material.opacity());
         if material.shader_model() == ShaderModel::Lit {
             layout.add_input("specular", material.specular());
-            layout.add_input("normal", material.normal());
+            if let Some(normal @ ShaderInput::Texture(_)) = material.normal() {
+                layout.add_input("normal", Some(normal));
+            }
             layout.add_input("metallic", material.metallic());
             layout.add_input("roughness", material.roughness());
             layout.add_input("emissive", material.emissive());
         }
+        layout.add_input("opacity", material.opacity());
 
         layout
     }
@@ -77,9 +79,10 @@ impl ShaderLayout {
     }
 
     pub fn sampler_binding_defs(&self, group: u32) -> String {
+        let count = self.texture_bindings.len() as u32;
         self.texture_bindings
             .iter()
-            .map(|binding| binding.get_sampler_def(group))
+            .map(|binding| binding.get_sampler_def(group, count))
             .collect::<Vec<String>>()
             .join("\n")
     }
@@ -148,38 +151,46 @@ impl TextureBinding {
         Self { binding, name }
     }
 
+    pub fn texture_name(&self) -> String {
+        format!("{}_tex", self.name)
+    }
+
+    pub fn sampler_name(&self) -> String {
+        format!("{}_sampler", self.name)
+    }
+
     pub fn get_binding_def(&self, group: u32) -> String {
         format!(
             r#"
             @group({group}) @binding({texture_binding})
-            var<uniform> {texture}_tex: texture_2d<f32>;
+            var {texture}: texture_2d<f32>;
         "#,
             group = group,
-            texture = self.name,
-            texture_binding = self.binding,
+            texture = self.texture_name(),
+            texture_binding = 1 + self.binding,
         )
     }
 
-    pub fn get_sampler_def(&self, binding: u32) -> String {
-        let sampler_name = format!("{}_sampler", self.name);
-
+    pub fn get_sampler_def(&self, group: u32, texture_count: u32) -> String {
         format!(
             r#"
-            @group(1) @binding({sampler_binding})
-            var<uniform> {sampler}: sampler;
+            @group({group}) @binding({sampler_binding})
+            var {sampler}: sampler;
         "#,
-            sampler_binding = binding,
-            sampler = sampler_name
+            group = group,
+            sampler_binding = 1 + texture_count + self.binding,
+            sampler = self.sampler_name(),
         )
     }
 
     pub fn get_binding_var(&self) -> String {
         format!(
             r#"
-            let {texture} = textureSample({texture}_tex, {sampler}_sampler, input.tex_coords);
+            let {name} = textureSample({texture}, {sampler}, input.tex_coords);
         "#,
-            texture = self.name,
-            sampler = format!("{}_sampler", self.name)
+            name = self.name,
+            texture = self.texture_name(),
+            sampler = self.sampler_name(),
         )
     }
 }
@@ -229,9 +240,7 @@ impl MaterialBindGroupLayout {
             getters.push(MaterialBindGroupLayout::get_emissive_texture);
         }
 
-        if material.blend_mode() == BlendMode::Translucent {
-            getters.push(MaterialBindGroupLayout::get_opacity_texture);
-        }
+        getters.push(MaterialBindGroupLayout::get_opacity_texture);
 
         MaterialBindGroupLayout {
             layout,
@@ -240,10 +249,16 @@ impl MaterialBindGroupLayout {
         }
     }
 
-    pub fn create_bind_group(&self, graphics: &Graphics, material: &Material) -> wgpu::BindGroup {
-        let buffer = match self.model {
-            ShaderModel::Lit => graphics.shader_resources().lit_material(),
-            ShaderModel::Unlit => graphics.shader_resources().unlit_material(),
+    pub fn create_bind_group(
+        &self,
+        graphics: &Graphics,
+        id: &MaterialId,
+        material: &Material,
+    ) -> wgpu::BindGroup {
+        let buffer = match (graphics.material_buffer(id), self.model) {
+            (Some(buffer), _) => buffer,
+            (None, ShaderModel::Lit) => graphics.shader_resources().lit_material(),
+            (None, ShaderModel::Unlit) => graphics.shader_resources().unlit_material(),
         };
 
         let mut entries = vec![wgpu::BindGroupEntry {
@@ -353,3 +368,104 @@ impl MaterialBindGroupLayout {
         }
     }
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::{
+        graphics::{color::Color, material::MaterialInfo},
+        shared::ResourceId,
+    };
+    use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+    pub fn materials(model: ShaderModel) -> Vec<Material> {
+        let texture = ResourceId::new;
+        let info = || MaterialInfo::new().shader_model(model);
+        vec![
+            info().build(),
+            info().color(ShaderInput::Texture(texture(1))).build(),
+            info()
+                .color(ShaderInput::Texture(texture(1)))
+                .specular(texture(2))
+                .normal(texture(3))
+                .metallic(texture(4))
+                .roughness(texture(5))
+                .emissive(texture(6))
+                .opacity(texture(7))
+                .build(),
+            info()
+                .blend_mode(BlendMode::Translucent)
+                .color(ShaderInput::Color(Color::white()))
+                .emissive(texture(6))
+                .opacity(texture(7))
+                .build(),
+        ]
+    }
+
+    pub fn validate(layout: &ShaderLayout, source: &str) {
+        let module = naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("{}\n{}", e.emit_to_string(source), source));
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .unwrap_or_else(|e| panic!("{:?}\n{}", e.as_inner(), source));
+
+        let mut bindings = module
+            .global_variables
+            .iter()
+            .filter_map(|(_, var)| var.binding.as_ref().map(|binding| (binding, var)))
+            .filter(|(binding, _)| binding.group == 1)
+            .map(|(binding, var)| (binding.binding, &module.types[var.ty].inner))
+            .collect::<Vec<_>>();
+        bindings.sort_by_key(|(binding, _)| *binding);
+
+        let entries = layout.bind_group_layout_entries();
+        assert_eq!(bindings.len(), entries.len(), "{}", source);
+        for ((binding, ty), entry) in bindings.into_iter().zip(entries) {
+            assert_eq!(binding, entry.binding, "{}", source);
+            let matches = match entry.ty {
+                wgpu::BindingType::Buffer { .. } => matches!(ty, naga::TypeInner::Struct { .. }),
+                wgpu::BindingType::Texture { .. } => matches!(ty, naga::TypeInner::Image { .. }),
+                wgpu::BindingType::Sampler(_) => matches!(ty, naga::TypeInner::Sampler { .. }),
+                _ => false,
+            };
+            assert!(matches, "binding {} is {:?}\n{}", binding, ty, source);
+        }
+    }
+
+    #[test]
+    fn texture_and_sampler_bindings_follow_the_material_uniform() {
+        let layout = ShaderLayout::from_material(&materials(ShaderModel::Lit)[2]);
+        let names = layout
+            .texture_bindings()
+            .iter()
+            .map(|binding| binding.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "color",
+                "specular",
+                "normal",
+                "metallic",
+                "roughness",
+                "emissive",
+                "opacity"
+            ]
+        );
+
+        let textures = layout.texture_binding_defs(1);
+        let samplers = layout.sampler_binding_defs(1);
+        assert!(
+            textures.contains("@group(1) @binding(1)\n            var color_tex: texture_2d<f32>;")
+        );
+        assert!(textures
+            .contains("@group(1) @binding(7)\n            var opacity_tex: texture_2d<f32>;"));
+        assert!(samplers.contains("@group(1) @binding(8)\n            var color_sampler: sampler;"));
+        assert!(
+            samplers.contains("@group(1) @binding(14)\n            var opacity_sampler: sampler;")
+        );
+        assert!(layout
+            .texture_binding_vars()
+            .contains("let color = textureSample(color_tex, color_sampler, input.tex_coords);"));
+    }
+}
@@ -15,8 +15,8 @@ impl UnlitShaderTemplate {
 }
 
 fn unlit_shader_template(layout: &ShaderLayout) -> String {
-    let texture_defs = layout.texture_binding_defs(2);
-    let sampler_defs = layout.sampler_binding_defs(2);
+    let texture_defs = layout.texture_binding_defs(1);
+    let sampler_defs = layout.sampler_binding_defs(1);
     let texture_vars = layout.texture_binding_vars();
     let material_vars = layout.material_attribute_vars("material");
 
@@ -28,9 +28,18 @@ fn unlit_shader_template(layout: &ShaderLayout) -> String {
             @location(2) tex_coords: vec2<f32>,
         }}
 
+        struct InstanceInput {{
+            @location(3) model_0: vec4<f32>,
+            @location(4) model_1: vec4<f32>,
+            @location(5) model_2: vec4<f32>,
+            @location(6) model_3: vec4<f32>,
+            @location(7) color: vec4<f32>,
+        }}
+
         struct VertexOutput {{
             @builtin(position) position: vec4<f32>,
             @location(0) tex_coords: vec2<f32>,
+            @location(1) color: vec4<f32>,
         }}
 
         struct Camera {{
@@ -38,35 +47,31 @@ fn unlit_shader_template(layout: &ShaderLayout) -> String {
             projection: mat4x4<f32>,
         }}
 
-        struct Object {{
-            model: mat4x4<f32>
-        }}
-
         @group(0) @binding(0)
         var<uniform> camera: Camera;
 
-        @group(1) @binding(0)
-        var<uniform> object: Object;
-
         @vertex
-        fn vs_main(input: VertexInput) -> VertexOutput {{
+        fn vs_main(input: VertexInput, instance: InstanceInput) -> VertexOutput {{
+            let model = mat4x4<f32>(
+                instance.model_0,
+                instance.model_1,
+                instance.model_2,
+                instance.model_3,
+            );
+
             var out: VertexOutput;
-            out.position = vec4<f32>(input.position.xyz, 1.0);
+            out.position = camera.projection * camera.view * model * vec4<f32>(input.position, 1.0);
             out.tex_coords = input.tex_coords;
+            out.color = instance.color;
             return out;
         }}
 
         struct Material {{
-            color: vec4<f32>;
-            opacity: vec4<f32>;
+            color: vec4<f32>,
+            opacity: vec4<f32>,
         }}
 
-        struct Standard {{
-            color: vec4<f32>;
-            opacity: f32;
-        }}
-
-        @group(2) @binding(0)
+        @group(1) @binding(0)
         var<uniform> material: Material;
 
         {texture_defs}
@@ -77,15 +82,27 @@ fn unlit_shader_template(layout: &ShaderLayout) -> String {
             {texture_vars}
             {material_vars}
 
-            let standard: Standard;
-            standard.color = color;
-            standard.opacity = opacity.x;
-
-            return vec4<f32>(standard.color.rgb, standard.opacity);
+            let final_color = color * input.color;
+            return vec4<f32>(final_color.rgb, opacity.x * input.color.a);
         }}
     "#,
         texture_defs = texture_defs,
+        sampler_defs = sampler_defs,
         texture_vars = texture_vars,
         material_vars = material_vars,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{super::layout::tests::*, *};
+    use crate::graphics::material::ShaderModel;
+
+    #[test]
+    fn unlit_template_is_valid_wgsl() {
+        for material in materials(ShaderModel::Unlit) {
+            let layout = ShaderLayout::from_material(&material);
+            validate(&layout, &unlit_shader_template(&layout));
+        }
+    }
+}
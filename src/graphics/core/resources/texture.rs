@@ -1,3 +1,5 @@
+use crate::asset::Asset;
+
 pub type Dimension = wgpu::TextureDimension;
 pub type Format = wgpu::TextureFormat;
 
@@ -8,6 +10,17 @@ pub enum FilterMode {
     Trilinear,
 }
 
+impl FilterMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "point" | "nearest" => Some(Self::Point),
+            "bilinear" | "linear" => Some(Self::Bilinear),
+            "trilinear" => Some(Self::Trilinear),
+            _ => None,
+        }
+    }
+}
+
 impl Into<wgpu::FilterMode> for FilterMode {
     fn into(self) -> wgpu::FilterMode {
         match self {
@@ -25,6 +38,17 @@ pub enum WrapMode {
     Mirror,
 }
 
+impl WrapMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "repeat" => Some(Self::Repeat),
+            "clamp" => Some(Self::Clamp),
+            "mirror" => Some(Self::Mirror),
+            _ => None,
+        }
+    }
+}
+
 impl Into<wgpu::AddressMode> for WrapMode {
     fn into(self) -> wgpu::AddressMode {
         match self {
@@ -51,6 +75,7 @@ pub trait Texture: 'static {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
 
+#[derive(Clone, Debug)]
 pub struct TextureInfo {
     pub width: u32,
     pub height: u32,
@@ -106,6 +131,38 @@ impl TextureInfo {
         }
     }
 
+    pub fn missing() -> Self {
+        let magenta = [255, 0, 255, 255];
+        let black = [0, 0, 0, 255];
+
+        Self {
+            width: 2,
+            height: 2,
+            depth: 1,
+            dimension: Dimension::D2,
+            format: Format::Rgba8UnormSrgb,
+            filter_mode: FilterMode::Point,
+            wrap_mode: WrapMode::Repeat,
+            mipmaps: false,
+            pixels: [magenta, black, black, magenta].concat(),
+        }
+    }
+
+    pub fn bytes_per_row(&self) -> u32 {
+        self.width * self.format.block_size(None).unwrap_or(4)
+    }
+
+    pub fn mip_level_count(&self) -> u32 {
+        let mipmapped = self.mipmaps
+            && self.dimension == Dimension::D2
+            && matches!(self.format, Format::Rgba8Unorm | Format::Rgba8UnormSrgb);
+
+        match mipmapped {
+            true => 32 - self.width.max(self.height).max(1).leading_zeros(),
+            false => 1,
+        }
+    }
+
     pub fn gray(format: Format) -> Self {
         let pixels = match format {
             Format::Rgba8UnormSrgb => vec![128, 128, 128, 255],
@@ -128,6 +185,8 @@ impl TextureInfo {
     }
 }
 
+impl Asset for TextureInfo {}
+
 pub struct Texture2d {
     width: u32,
     height: u32,
@@ -172,7 +231,7 @@ impl Texture2d {
             dimension: wgpu::TextureDimension::D2,
             format: info.format,
             label: None,
-            mip_level_count: if info.mipmaps { 1 } else { 0 },
+            mip_level_count: info.mip_level_count(),
             sample_count: 1,
             size: wgpu::Extent3d {
                 depth_or_array_layers: 1,
@@ -193,7 +252,7 @@ impl Texture2d {
             &info.pixels,
             wgpu::ImageDataLayout {
                 offset: 0,
-                bytes_per_row: Some(info.width * 4),
+                bytes_per_row: Some(info.bytes_per_row()),
                 rows_per_image: Some(info.height),
             },
             wgpu::Extent3d {
@@ -203,6 +262,36 @@ impl Texture2d {
             },
         );
 
+        let mut mip = (info.width, info.height, Vec::new());
+        for mip_level in 1..info.mip_level_count() {
+            let source = match mip_level {
+                1 => &info.pixels,
+                _ => &mip.2,
+            };
+            mip = downsample(mip.0, mip.1, source, info.format == Format::Rgba8UnormSrgb);
+
+            let (width, height, pixels) = &mip;
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &gpu_texture,
+                    mip_level,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                pixels,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(width * 4),
+                    rows_per_image: Some(*height),
+                },
+                wgpu::Extent3d {
+                    depth_or_array_layers: 1,
+                    height: *height,
+                    width: *width,
+                },
+            );
+        }
+
         let view = gpu_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -234,6 +323,59 @@ impl Texture2d {
     }
 }
 
+/// Halves an RGBA8 image with a 2x2 box filter, clamping at odd edges.
+/// sRGB colour channels are averaged in linear space; alpha is always linear.
+fn downsample(width: u32, height: u32, pixels: &[u8], srgb: bool) -> (u32, u32, Vec<u8>) {
+    let (next_width, next_height) = ((width / 2).max(1), (height / 2).max(1));
+    let mut next = Vec::with_capacity((next_width * next_height * 4) as usize);
+
+    for y in 0..next_height {
+        for x in 0..next_width {
+            let xs = [(x * 2).min(width - 1), (x * 2 + 1).min(width - 1)];
+            let ys = [(y * 2).min(height - 1), (y * 2 + 1).min(height - 1)];
+
+            for channel in 0..4 {
+                let linear = srgb && channel < 3;
+                let mut sum = 0.0;
+                for sy in ys {
+                    for sx in xs {
+                        let value = pixels[((sy * width + sx) * 4 + channel) as usize];
+                        sum += match linear {
+                            true => srgb_to_linear(value),
+                            false => value as f32 / 255.0,
+                        };
+                    }
+                }
+
+                let average = sum / 4.0;
+                next.push(match linear {
+                    true => linear_to_srgb(average),
+                    false => (average * 255.0).round() as u8,
+                });
+            }
+        }
+    }
+
+    (next_width, next_height, next)
+}
+
+pub(crate) fn srgb_to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+    match value <= 0.04045 {
+        true => value / 12.92,
+        false => ((value + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+pub(crate) fn linear_to_srgb(value: f32) -> u8 {
+    let value = match value <= 0.0031308 {
+        true => value * 12.92,
+        false => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+    };
+
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
 impl Texture for Texture2d {
     fn width(&self) -> u32 {
         self.width
@@ -287,3 +429,155 @@ impl Texture for Texture2d {
         self
     }
 }
+
+pub struct RenderTexture {
+    width: u32,
+    height: u32,
+    format: Format,
+    view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+}
+
+impl RenderTexture {
+    pub fn new(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        format: Format,
+        depth_format: Format,
+    ) -> Self {
+        let size = wgpu::Extent3d {
+            depth_or_array_layers: 1,
+            height,
+            width,
+        };
+
+        let color = device.create_texture(&wgpu::TextureDescriptor {
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            label: Some("Render Texture"),
+            mip_level_count: 1,
+            sample_count: 1,
+            size,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let depth = device.create_texture(&wgpu::TextureDescriptor {
+            dimension: wgpu::TextureDimension::D2,
+            format: depth_format,
+            label: Some("Render Texture Depth"),
+            mip_level_count: 1,
+            sample_count: 1,
+            size,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: None,
+            address_mode_u: WrapMode::Clamp.into(),
+            address_mode_v: WrapMode::Clamp.into(),
+            address_mode_w: WrapMode::Clamp.into(),
+            mag_filter: FilterMode::Bilinear.into(),
+            min_filter: FilterMode::Bilinear.into(),
+            mipmap_filter: FilterMode::Bilinear.into(),
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 100.0,
+            compare: None,
+            anisotropy_clamp: 1,
+            border_color: None,
+        });
+
+        Self {
+            width,
+            height,
+            format,
+            view: color.create_view(&wgpu::TextureViewDescriptor::default()),
+            depth_view: depth.create_view(&wgpu::TextureViewDescriptor::default()),
+            sampler,
+        }
+    }
+
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth_view
+    }
+}
+
+impl Texture for RenderTexture {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn depth(&self) -> u32 {
+        1
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::D2
+    }
+
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn filter_mode(&self) -> FilterMode {
+        FilterMode::Bilinear
+    }
+
+    fn wrap_mode(&self) -> WrapMode {
+        WrapMode::Clamp
+    }
+
+    fn mipmaps(&self) -> bool {
+        false
+    }
+
+    fn pixels(&self) -> &[u8] {
+        &[]
+    }
+
+    fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    fn sampler(&self) -> &wgpu::Sampler {
+        &self.sampler
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsample_averages_srgb_in_linear_space() {
+        let pixels = [[0, 0, 0, 0], [255, 255, 255, 255]].repeat(2).concat();
+
+        let (_, _, unorm) = downsample(2, 2, &pixels, false);
+        assert_eq!(unorm, vec![128, 128, 128, 128]);
+
+        let (_, _, srgb) = downsample(2, 2, &pixels, true);
+        assert_eq!(srgb, vec![188, 188, 188, 128]);
+    }
+
+    #[test]
+    fn srgb_round_trips() {
+        for value in 0..=255 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+    }
+}
@@ -1,21 +1,39 @@
 use super::{
     gpu::Gpu,
-    material::Material,
+    material::{Material, ShaderModel},
     mesh::SubMesh,
-    shader::{program::ShaderProgram, resources::ShaderResources},
+    shader::{
+        program::ShaderProgram,
+        resources::ShaderResources,
+        source::{ShaderDefs, ShaderInfo},
+        InstanceData, LitMaterialData, UnlitMaterialData,
+    },
     vertex::Vertex,
     BufferId, DrawMesh, MaterialId, MeshId, RenderScene, TextureId,
 };
 use crate::{
-    ecs::Resource,
+    asset::{AssetDatabase, AssetEventKind, ImportError},
+    diagnostics::{Diagnostics, CULLED, VISIBLE},
+    ecs::{
+        builtin::{Parent, Transform},
+        world::{query::Copied, Query},
+        Resource, World,
+    },
     graphics::{
+        camera::Camera,
+        color::Color,
         light::LightRef,
         material::MaterialInfo,
-        mesh::Mesh,
-        texture::{Texture, TextureInfo},
+        mesh::{Mesh, MeshInfo},
+        mesh_renderer::MeshRenderer,
+        texture::{RenderTexture, Texture, Texture2d, TextureInfo},
+        view::{RenderTarget, RenderView},
     },
 };
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 use wgpu::util::DeviceExt;
 
 pub struct Graphics {
@@ -25,14 +43,16 @@ pub struct Graphics {
     textures: HashMap<TextureId, Box<dyn Texture>>,
     meshes: HashMap<MeshId, Mesh>,
     materials: HashMap<MaterialId, Material>,
+    material_buffers: HashMap<MaterialId, wgpu::Buffer>,
     shader_programs: HashMap<Material, ShaderProgram>,
     shader_resources: ShaderResources,
     config: Config,
+    max_lights: usize,
 }
 
 impl Graphics {
-    pub fn new(gpu: Rc<Gpu>, config: Config) -> Self {
-        let shader_resources = ShaderResources::new(gpu.device(), 100);
+    pub fn new(gpu: Rc<Gpu>, config: Config, max_lights: usize) -> Self {
+        let shader_resources = ShaderResources::new(gpu.device(), max_lights);
 
         Self {
             gpu,
@@ -41,9 +61,11 @@ impl Graphics {
             textures: HashMap::new(),
             meshes: HashMap::new(),
             materials: HashMap::new(),
+            material_buffers: HashMap::new(),
             shader_programs: HashMap::new(),
             shader_resources,
             config,
+            max_lights,
         }
     }
 
@@ -55,6 +77,10 @@ impl Graphics {
         &self.config
     }
 
+    pub fn max_lights(&self) -> usize {
+        self.max_lights
+    }
+
     pub fn buffer(&self, id: &BufferId) -> Option<&wgpu::Buffer> {
         self.buffers.get(id)
     }
@@ -77,6 +103,10 @@ impl Graphics {
         self.materials.get(id)
     }
 
+    pub fn material_buffer(&self, id: &MaterialId) -> Option<&wgpu::Buffer> {
+        self.material_buffers.get(id)
+    }
+
     pub fn shader_program(&self, material: &Material) -> Option<&ShaderProgram> {
         self.shader_programs.get(material)
     }
@@ -85,6 +115,31 @@ impl Graphics {
         &self.shader_resources
     }
 
+    pub fn views(&self) -> &[RenderView] {
+        self.scene.views()
+    }
+
+    pub fn set_views(&mut self, mut views: Vec<RenderView>) {
+        views.sort_by_key(|view| view.order());
+        self.scene.set_views(views);
+    }
+
+    pub fn cull_views(&mut self, width: u32, height: u32) {
+        let textures = &self.textures;
+        self.scene.cull(|view| {
+            let (width, height) = match view.target() {
+                RenderTarget::Window => (width, height),
+                RenderTarget::Texture(id) => textures
+                    .get(id)
+                    .map(|texture| (texture.width(), texture.height()))
+                    .unwrap_or((width, height)),
+            };
+
+            let viewport = view.pixel_viewport(width, height);
+            viewport.width / viewport.height.max(1.0)
+        });
+    }
+
     pub(super) fn scene(&self) -> &RenderScene {
         &self.scene
     }
@@ -99,14 +154,112 @@ impl Graphics {
 
     pub fn add_texture<T: Texture>(&mut self, id: &TextureId, texture: T) {
         self.textures.insert(id.clone(), Box::new(texture));
+
+        let materials = self
+            .materials
+            .iter()
+            .filter(|(_, material)| material.textures().contains(id))
+            .map(|(material_id, _)| *material_id)
+            .collect::<Vec<_>>();
+
+        for material_id in materials {
+            let program = self
+                .materials
+                .get(&material_id)
+                .and_then(|material| self.shader_programs.get_mut(material));
+            if let Some(program) = program {
+                program.remove_material_bind_group(&material_id);
+            }
+
+            self.create_material_bind_group(&material_id);
+        }
+    }
+
+    pub fn add_render_texture(&mut self, id: &TextureId, width: u32, height: u32) {
+        let texture = RenderTexture::new(
+            self.gpu.device(),
+            width,
+            height,
+            self.config.color_format(),
+            self.config.depth_format(),
+        );
+        self.add_texture(id, texture);
+    }
+
+    pub fn remove_texture(&mut self, id: &TextureId) -> bool {
+        self.textures.remove(id).is_some()
     }
 
     pub fn add_mesh(&mut self, id: &MeshId, mesh: Mesh) {
         self.meshes.insert(id.clone(), mesh);
     }
 
+    pub fn remove_mesh(&mut self, id: &MeshId) -> bool {
+        self.meshes.remove(id).is_some()
+    }
+
     pub fn add_material(&mut self, id: &MaterialId, material: Material) {
-        self.materials.insert(id.clone(), material);
+        let data = material_data(&material);
+        match self.materials.get(id) {
+            Some(current) if *current == material => {
+                if current.textures() != material.textures() {
+                    if let Some(program) = self.shader_programs.get_mut(current) {
+                        program.remove_material_bind_group(id);
+                    }
+                }
+
+                match self.material_buffers.get(id) {
+                    Some(buffer) => self.gpu.queue().write_buffer(buffer, 0, &data),
+                    None => {
+                        let buffer = self.create_uniform_buffer(&data);
+                        self.material_buffers.insert(*id, buffer);
+                    }
+                }
+            }
+            current => {
+                if let Some(program) = current.and_then(|c| self.shader_programs.get_mut(c)) {
+                    program.remove_material_bind_group(id);
+                }
+
+                let buffer = self.create_uniform_buffer(&data);
+                self.material_buffers.insert(*id, buffer);
+            }
+        }
+
+        self.materials.insert(*id, material);
+        self.create_material_bind_group(id);
+    }
+
+    fn create_material_bind_group(&mut self, id: &MaterialId) {
+        let Some(material) = self.materials.get(id).cloned() else {
+            return;
+        };
+
+        if !material
+            .textures()
+            .iter()
+            .all(|t| self.textures.contains_key(t))
+        {
+            return;
+        }
+
+        if let Some(mut program) = self.shader_programs.remove(&material) {
+            program.add_material_bind_group(self, *id, &material);
+            self.shader_programs.insert(material, program);
+        }
+    }
+
+    pub fn remove_material(&mut self, id: &MaterialId) -> bool {
+        let Some(material) = self.materials.remove(id) else {
+            return false;
+        };
+
+        if let Some(program) = self.shader_programs.get_mut(&material) {
+            program.remove_material_bind_group(id);
+        }
+
+        self.material_buffers.remove(id);
+        true
     }
 
     pub fn create_vertex_buffer(&self, vertices: &Vec<Vertex>) -> wgpu::Buffer {
@@ -129,6 +282,16 @@ impl Graphics {
             })
     }
 
+    pub fn create_instance_buffer(&self, instances: &[InstanceData]) -> wgpu::Buffer {
+        self.gpu
+            .device()
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Instance Buffer"),
+                contents: bytemuck::cast_slice(instances),
+                usage: wgpu::BufferUsages::VERTEX,
+            })
+    }
+
     pub fn create_uniform_buffer(&self, buffer: &[u8]) -> wgpu::Buffer {
         self.gpu
             .device()
@@ -144,7 +307,7 @@ impl Graphics {
             dimension: info.dimension,
             format: info.format,
             label: None,
-            mip_level_count: if info.mipmaps { 1 } else { 0 },
+            mip_level_count: 1,
             sample_count: 1,
             size: wgpu::Extent3d {
                 depth_or_array_layers: info.depth,
@@ -165,7 +328,7 @@ impl Graphics {
             &info.pixels,
             wgpu::ImageDataLayout {
                 offset: 0,
-                bytes_per_row: Some(info.width * 4),
+                bytes_per_row: Some(info.bytes_per_row()),
                 rows_per_image: Some(info.height),
             },
             wgpu::Extent3d {
@@ -195,6 +358,23 @@ impl Graphics {
         })
     }
 
+    pub fn create_shader_module(
+        &self,
+        info: &ShaderInfo,
+        defs: &ShaderDefs,
+    ) -> Result<wgpu::ShaderModule, ImportError> {
+        let code = info.validate(defs)?;
+        let module = self
+            .gpu
+            .device()
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: info.path().to_str(),
+                source: wgpu::ShaderSource::Wgsl(code.into()),
+            });
+
+        Ok(module)
+    }
+
     pub fn create_material(&mut self, info: &MaterialInfo) -> Material {
         let material = info.build();
         if !self.shader_programs.contains_key(&material) {
@@ -215,12 +395,18 @@ impl Graphics {
         Mesh::new(self.gpu.device(), &vertices, &indices, &submeshes)
     }
 
-    pub fn draw_mesh(&mut self, id: &MeshId, materials: Vec<MaterialId>, transform: glam::Mat4) {
+    pub fn draw_mesh(
+        &mut self,
+        id: &MeshId,
+        materials: Vec<MaterialId>,
+        transform: glam::Mat4,
+        color: Option<Color>,
+    ) {
         if let Some(mesh) = self.mesh(id) {
             let bounds = mesh.bounds().transform(&transform);
+            let draw = DrawMesh::new(transform, id.clone(), materials, bounds).with_color(color);
 
-            self.scene
-                .add_mesh(DrawMesh::new(transform, id.clone(), materials, bounds));
+            self.scene.add_mesh(draw);
         }
     }
 
@@ -239,6 +425,149 @@ impl Resource for Graphics {
     }
 }
 
+pub fn extract_views(world: &World) {
+    let Some(mut graphics) = world.try_resource_mut::<Graphics>() else {
+        return;
+    };
+
+    let views = Query::<(Copied<Camera>, Copied<Transform>, Option<Copied<Parent>>)>::new(world)
+        .map(|(camera, transform, parent)| {
+            let (_, rotation, position) = transform
+                .matrix(world, parent)
+                .to_scale_rotation_translation();
+            let view = glam::Mat4::from_rotation_translation(rotation, position).inverse();
+
+            RenderView::from_camera(&camera, view)
+        })
+        .collect();
+
+    graphics.set_views(views);
+}
+
+pub fn extract_meshes(world: &World) {
+    let Some(mut graphics) = world.try_resource_mut::<Graphics>() else {
+        return;
+    };
+
+    graphics.scene.clear_meshes();
+    let query = Query::<(MeshRenderer, Copied<Transform>, Option<Copied<Parent>>)>::new(world);
+    for (renderer, transform, parent) in query {
+        let matrix = transform.matrix(world, parent);
+        let mut draws: Vec<(MeshId, Vec<MaterialId>)> = Vec::new();
+        for element in &renderer.elements {
+            match draws
+                .iter_mut()
+                .find(|(mesh, _)| *mesh == element.mesh_id())
+            {
+                Some((_, materials)) => materials.push(element.material_id()),
+                None => draws.push((element.mesh_id(), vec![element.material_id()])),
+            }
+        }
+
+        for (mesh, materials) in draws {
+            graphics.draw_mesh(&mesh, materials, matrix, renderer.color);
+        }
+    }
+}
+
+pub fn record_visibility(world: &World) {
+    let Some(graphics) = world.try_resource::<Graphics>() else {
+        return;
+    };
+
+    if let Some(mut diagnostics) = world.try_resource_mut::<Diagnostics>() {
+        diagnostics.record(VISIBLE, graphics.scene.visible_count() as f64);
+        diagnostics.record(CULLED, graphics.scene.culled_count() as f64);
+    }
+}
+
+pub fn upload_textures(world: &World) {
+    let Some(mut graphics) = world.try_resource_mut::<Graphics>() else {
+        return;
+    };
+
+    let db = world.resource::<AssetDatabase>();
+    let mut uploaded = HashSet::new();
+    for event in db.events::<TextureInfo>() {
+        let id = event.id();
+        match event.kind() {
+            AssetEventKind::Removed => {
+                graphics.remove_texture(&id);
+            }
+            _ => {
+                if let Some(info) = db.get::<TextureInfo>(&id) {
+                    if uploaded.insert(id) {
+                        let gpu = graphics.gpu.clone();
+                        let texture = Texture2d::new_info(gpu.device(), gpu.queue(), info);
+                        graphics.add_texture(&id, texture);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn upload_meshes(world: &World) {
+    let Some(mut graphics) = world.try_resource_mut::<Graphics>() else {
+        return;
+    };
+
+    let db = world.resource::<AssetDatabase>();
+    let mut uploaded = HashSet::new();
+    for event in db.events::<MeshInfo>() {
+        let id = event.id();
+        match event.kind() {
+            AssetEventKind::Removed => {
+                graphics.remove_mesh(&id);
+            }
+            _ => {
+                if let Some(info) = db.get::<MeshInfo>(&id) {
+                    if uploaded.insert(id) {
+                        let mesh =
+                            graphics.create_mesh(&info.vertices, &info.indices, &info.submeshes);
+                        graphics.add_mesh(&id, mesh);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn upload_materials(world: &World) {
+    let Some(mut graphics) = world.try_resource_mut::<Graphics>() else {
+        return;
+    };
+
+    let db = world.resource::<AssetDatabase>();
+    let mut uploaded = HashSet::new();
+    for event in db.events::<MaterialInfo>() {
+        let id = event.id();
+        match event.kind() {
+            AssetEventKind::Removed => {
+                graphics.remove_material(&id);
+            }
+            _ => {
+                if let Some(info) = db.get::<MaterialInfo>(&id) {
+                    if uploaded.insert(id) {
+                        let material = graphics.create_material(info);
+                        graphics.add_material(&id, material);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn material_data(material: &Material) -> Vec<u8> {
+    match material.shader_model() {
+        ShaderModel::Lit => bytemuck::bytes_of(&LitMaterialData::from_material(material)).to_vec(),
+        ShaderModel::Unlit => {
+            bytemuck::bytes_of(&UnlitMaterialData::from_material(material)).to_vec()
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct Config {
     color_format: wgpu::TextureFormat,
     depth_format: wgpu::TextureFormat,
@@ -1,9 +1,10 @@
 use super::{
     gpu::Gpu,
-    renderer::{Renderer, RendererBuilder},
+    renderer::{graph::RenderGraphBuilder, Renderer, RendererBuilder},
     surface::RenderSurface,
     Config, Graphics,
 };
+use crate::game::WindowConfig;
 use std::rc::Rc;
 use winit::event_loop::EventLoop;
 
@@ -14,8 +15,8 @@ pub struct GraphicsEngine {
 }
 
 impl GraphicsEngine {
-    pub(crate) async fn new(events: &EventLoop<()>) -> GraphicsEngine {
-        let surface = RenderSurface::new(events).await;
+    pub(crate) async fn new(events: &EventLoop<()>, window: &WindowConfig) -> GraphicsEngine {
+        let surface = RenderSurface::new(events, window).await;
         let gpu = Rc::new(Gpu::new(surface.adapter()).await);
         let renderer = RendererBuilder::new().build(&gpu, &surface);
 
@@ -42,6 +43,12 @@ impl GraphicsEngine {
         self.surface.window()
     }
 
+    pub fn set_render_graph(&mut self, graph: RenderGraphBuilder) {
+        self.renderer = RendererBuilder::new()
+            .with_graph(graph)
+            .build(&self.gpu, &self.surface);
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         self.surface.resize(self.gpu.device(), width, height);
         self.renderer.resize(width, height);
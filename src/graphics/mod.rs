@@ -1,5 +1,6 @@
 pub mod core;
 pub mod engine;
+pub mod plugin;
 pub mod renderer;
 pub mod surface;
 
@@ -0,0 +1,80 @@
+use super::{
+    camera::Camera,
+    extract_meshes, extract_views,
+    importers::{GltfImporter, MaterialImporter, ObjImporter, ShaderImporter, TextureImporter},
+    light::Light,
+    material::MaterialInfo,
+    mesh::MeshInfo,
+    mesh_renderer::MeshRenderer,
+    prefab::Prefab,
+    record_visibility,
+    shader::source::ShaderInfo,
+    texture::TextureInfo,
+    upload_materials, upload_meshes, upload_textures, Graphics,
+};
+use crate::{
+    ecs::builtin::{Children, Parent, Transform},
+    game::{GameBuilder, Plugin},
+    graphics::renderer::{
+        self,
+        graph::DEPTH,
+        node::RenderPassKind,
+        pass::{Attachment, RenderPassBuilder},
+        subpass::SubpassBuilder,
+        TextureSize,
+    },
+    schedule::ScenePhase,
+};
+
+pub struct GraphicsPlugin;
+
+impl Plugin for GraphicsPlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        game.add_component::<Transform>();
+        game.add_component::<Parent>();
+        game.add_component::<Children>();
+        game.add_component::<MeshRenderer>();
+        game.add_component::<Camera>();
+        game.add_component::<Light>();
+
+        game.add_importer::<TextureInfo, _>(TextureImporter);
+        game.add_importer::<MeshInfo, _>(ObjImporter);
+        game.add_importer::<Prefab, _>(GltfImporter);
+        game.add_importer::<ShaderInfo, _>(ShaderImporter);
+        game.add_importer::<MaterialInfo, _>(MaterialImporter);
+        game.add_placeholder(TextureInfo::missing);
+
+        if let (Some(gpu), Some(config)) = (game.gpu(), game.graphics_config()) {
+            let max_lights = game.game_config().renderer.max_lights;
+            let graphics = Graphics::new(gpu.clone(), config, max_lights);
+            game.add_resource(graphics);
+            game.add_system(ScenePhase::PreRender, extract_views);
+            game.add_system(ScenePhase::PreRender, extract_meshes);
+            game.add_system(ScenePhase::PreRender, upload_textures);
+            game.add_system(ScenePhase::PreRender, upload_meshes);
+            game.add_system(ScenePhase::PreRender, upload_materials);
+            game.add_system(ScenePhase::PostRender, record_visibility);
+
+            game.add_render_texture(
+                DEPTH,
+                renderer::TextureInfo {
+                    format: config.depth_format(),
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    size: TextureSize::Surface(1.0),
+                },
+            );
+
+            let mut forward = RenderPassBuilder::new(RenderPassKind::Forward)
+                .with_color(Attachment::Surface, None, wgpu::StoreOp::Store, true)
+                .with_depth(
+                    Attachment::Texture(DEPTH.to_string()),
+                    wgpu::StoreOp::Discard,
+                    wgpu::StoreOp::Discard,
+                    Some(1.0),
+                    None,
+                );
+            forward.add_subpass(SubpassBuilder::new());
+            game.add_render_node("forward", forward);
+        }
+    }
+}
@@ -0,0 +1,657 @@
+use super::{
+    node::{RenderGraphNode, RenderGraphNodeBuilder},
+    BufferInfo, TextureInfo,
+};
+use crate::graphics::{
+    texture::{RenderTexture, Texture},
+    view::RenderTarget,
+    Graphics,
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+};
+
+pub const SURFACE: &str = "surface";
+pub const DEPTH: &str = "depth";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+pub struct NodeResources {
+    accesses: Vec<(String, Access)>,
+}
+
+impl NodeResources {
+    pub fn new() -> Self {
+        Self {
+            accesses: Vec::new(),
+        }
+    }
+
+    pub fn read(&mut self, name: &str) {
+        self.access(name, Access::Read);
+    }
+
+    pub fn write(&mut self, name: &str) {
+        self.access(name, Access::Write);
+    }
+
+    pub fn modify(&mut self, name: &str) {
+        self.access(name, Access::ReadWrite);
+    }
+
+    pub fn accesses(&self) -> &[(String, Access)] {
+        &self.accesses
+    }
+
+    fn access(&mut self, name: &str, access: Access) {
+        match self.accesses.iter_mut().find(|(n, _)| n == name) {
+            Some((_, current)) if *current != access => *current = Access::ReadWrite,
+            Some(_) => {}
+            None => self.accesses.push((name.to_string(), access)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenderGraphError {
+    DuplicateNode(String),
+    DuplicateResource(String),
+    UnknownResource { node: String, resource: String },
+    MissingProducer { node: String, resource: String },
+    Cycle(Vec<String>),
+}
+
+impl Display for RenderGraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateNode(node) => write!(f, "Duplicate render node '{}'", node),
+            Self::DuplicateResource(resource) => {
+                write!(f, "Duplicate render resource '{}'", resource)
+            }
+            Self::UnknownResource { node, resource } => write!(
+                f,
+                "Render node '{}' uses unknown resource '{}'",
+                node, resource
+            ),
+            Self::MissingProducer { node, resource } => write!(
+                f,
+                "Render node '{}' reads '{}' but no node writes it",
+                node, resource
+            ),
+            Self::Cycle(nodes) => write!(f, "Render graph cycle between {}", nodes.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for RenderGraphError {}
+
+pub struct RenderGraphBuilder {
+    textures: Vec<(String, TextureInfo)>,
+    buffers: Vec<(String, BufferInfo)>,
+    nodes: Vec<(String, Box<dyn RenderGraphNodeBuilder>)>,
+}
+
+impl RenderGraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            textures: Vec::new(),
+            buffers: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn add_texture(&mut self, name: &str, info: TextureInfo) {
+        self.textures.push((name.to_string(), info));
+    }
+
+    pub fn add_buffer(&mut self, name: &str, info: BufferInfo) {
+        self.buffers.push((name.to_string(), info));
+    }
+
+    pub fn add_node<T: RenderGraphNodeBuilder>(&mut self, name: &str, node: T) {
+        self.nodes.push((name.to_string(), Box::new(node)));
+    }
+
+    pub fn add_dyn_node(&mut self, name: &str, node: Box<dyn RenderGraphNodeBuilder>) {
+        self.nodes.push((name.to_string(), node));
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn compile(&self) -> Result<RenderGraphPlan, RenderGraphError> {
+        let mut names = HashSet::new();
+        for (name, _) in &self.nodes {
+            if !names.insert(name.as_str()) {
+                return Err(RenderGraphError::DuplicateNode(name.clone()));
+            }
+        }
+
+        let mut resources = vec![SURFACE];
+        let declared = self.textures.iter().map(|(name, _)| name);
+        for name in declared.chain(self.buffers.iter().map(|(name, _)| name)) {
+            if resources.contains(&name.as_str()) {
+                return Err(RenderGraphError::DuplicateResource(name.clone()));
+            }
+
+            resources.push(name);
+        }
+
+        let accesses = self
+            .nodes
+            .iter()
+            .map(|(_, node)| {
+                let mut declared = NodeResources::new();
+                node.declare(&mut declared);
+                declared.accesses
+            })
+            .collect::<Vec<_>>();
+
+        for (index, declared) in accesses.iter().enumerate() {
+            if let Some((resource, _)) = declared
+                .iter()
+                .find(|(resource, _)| !resources.contains(&resource.as_str()))
+            {
+                return Err(RenderGraphError::UnknownResource {
+                    node: self.nodes[index].0.clone(),
+                    resource: resource.clone(),
+                });
+            }
+        }
+
+        let mut dependencies = vec![BTreeSet::new(); self.nodes.len()];
+        for resource in &resources {
+            let users = |kind: Access| {
+                accesses
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, declared)| {
+                        declared.iter().any(|(n, a)| n == resource && *a == kind)
+                    })
+                    .map(|(index, _)| index)
+            };
+
+            let mut writers = users(Access::Write)
+                .chain(users(Access::ReadWrite))
+                .collect::<Vec<_>>();
+            writers.sort();
+            let readers = users(Access::Read).collect::<Vec<_>>();
+
+            if *resource != SURFACE && users(Access::Write).next().is_none() {
+                if let Some(node) = readers.iter().chain(&writers).min() {
+                    return Err(RenderGraphError::MissingProducer {
+                        node: self.nodes[*node].0.clone(),
+                        resource: resource.to_string(),
+                    });
+                }
+            }
+
+            for pair in writers.windows(2) {
+                dependencies[pair[1]].insert(pair[0]);
+            }
+
+            // A reader sees the latest writer declared before it, or the first
+            // writer, and the writer after that one must wait for the read.
+            for reader in &readers {
+                let source = writers
+                    .iter()
+                    .rposition(|writer| writer < reader)
+                    .unwrap_or(0);
+                if let Some(writer) = writers.get(source) {
+                    dependencies[*reader].insert(*writer);
+                }
+
+                if let Some(next) = writers.get(source + 1) {
+                    dependencies[*next].insert(*reader);
+                }
+            }
+        }
+
+        let order = sort(&dependencies).map_err(|remaining| {
+            RenderGraphError::Cycle(
+                remaining
+                    .into_iter()
+                    .map(|index| self.nodes[index].0.clone())
+                    .collect(),
+            )
+        })?;
+
+        let lifetime = |name: &str| {
+            let mut uses = order
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| accesses[**node].iter().any(|(n, _)| n == name))
+                .map(|(position, _)| position);
+            let first = uses.next()?;
+            Some((first, uses.next_back().unwrap_or(first)))
+        };
+
+        let (textures, texture_slots) = alias(&self.textures, lifetime);
+        let (buffers, buffer_slots) = alias(&self.buffers, lifetime);
+
+        Ok(RenderGraphPlan {
+            names: self.nodes.iter().map(|(name, _)| name.clone()).collect(),
+            order,
+            textures,
+            texture_slots,
+            buffers,
+            buffer_slots,
+        })
+    }
+
+    pub(super) fn take_nodes(self) -> Vec<(String, Box<dyn RenderGraphNodeBuilder>)> {
+        self.nodes
+    }
+}
+
+pub struct RenderGraphPlan {
+    names: Vec<String>,
+    order: Vec<usize>,
+    textures: HashMap<String, usize>,
+    texture_slots: Vec<TextureInfo>,
+    buffers: HashMap<String, usize>,
+    buffer_slots: Vec<BufferInfo>,
+}
+
+impl RenderGraphPlan {
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn ordered_names(&self) -> impl Iterator<Item = &str> {
+        self.order.iter().map(|index| self.names[*index].as_str())
+    }
+
+    pub fn texture_slot(&self, name: &str) -> Option<usize> {
+        self.textures.get(name).copied()
+    }
+
+    pub fn texture_slots(&self) -> &[TextureInfo] {
+        &self.texture_slots
+    }
+
+    pub fn buffer_slot(&self, name: &str) -> Option<usize> {
+        self.buffers.get(name).copied()
+    }
+
+    pub fn buffer_slots(&self) -> &[BufferInfo] {
+        &self.buffer_slots
+    }
+}
+
+pub struct GraphContext<'a> {
+    graphics: &'a Graphics,
+    plan: &'a RenderGraphPlan,
+    surface: &'a wgpu::TextureView,
+    size: (u32, u32),
+    textures: &'a [wgpu::TextureView],
+    buffers: &'a [wgpu::Buffer],
+}
+
+impl<'a> GraphContext<'a> {
+    pub fn new(
+        graphics: &'a Graphics,
+        plan: &'a RenderGraphPlan,
+        surface: &'a wgpu::TextureView,
+        size: (u32, u32),
+        textures: &'a [wgpu::TextureView],
+        buffers: &'a [wgpu::Buffer],
+    ) -> Self {
+        Self {
+            graphics,
+            plan,
+            surface,
+            size,
+            textures,
+            buffers,
+        }
+    }
+
+    pub fn graphics(&self) -> &'a Graphics {
+        self.graphics
+    }
+
+    pub fn surface(&self) -> &'a wgpu::TextureView {
+        self.surface
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn target(&self, target: &RenderTarget) -> Option<(&'a wgpu::TextureView, u32, u32)> {
+        match target {
+            RenderTarget::Window => Some((self.surface, self.size.0, self.size.1)),
+            RenderTarget::Texture(id) => self
+                .graphics
+                .texture::<RenderTexture>(id)
+                .map(|texture| (texture.view(), texture.width(), texture.height())),
+        }
+    }
+
+    pub fn target_depth(&self, target: &RenderTarget) -> Option<&'a wgpu::TextureView> {
+        match target {
+            RenderTarget::Window => None,
+            RenderTarget::Texture(id) => self
+                .graphics
+                .texture::<RenderTexture>(id)
+                .map(|texture| texture.depth_view()),
+        }
+    }
+
+    pub fn texture(&self, name: &str) -> Option<&'a wgpu::TextureView> {
+        match name {
+            SURFACE => Some(self.surface),
+            _ => self
+                .plan
+                .texture_slot(name)
+                .and_then(|slot| self.textures.get(slot)),
+        }
+    }
+
+    pub fn buffer(&self, name: &str) -> Option<&'a wgpu::Buffer> {
+        self.plan
+            .buffer_slot(name)
+            .and_then(|slot| self.buffers.get(slot))
+    }
+}
+
+pub struct RenderGraph {
+    plan: RenderGraphPlan,
+    nodes: Vec<Box<dyn RenderGraphNode>>,
+}
+
+impl RenderGraph {
+    pub fn new(plan: RenderGraphPlan, nodes: Vec<Box<dyn RenderGraphNode>>) -> Self {
+        Self { plan, nodes }
+    }
+
+    pub fn plan(&self) -> &RenderGraphPlan {
+        &self.plan
+    }
+
+    pub fn execute(&self, ctx: &GraphContext, encoder: &mut wgpu::CommandEncoder) {
+        for node in &self.nodes {
+            node.execute(ctx, encoder);
+        }
+    }
+}
+
+fn sort(dependencies: &[BTreeSet<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut remaining = (0..dependencies.len()).collect::<BTreeSet<_>>();
+    let mut order = Vec::with_capacity(dependencies.len());
+    while let Some(next) = remaining
+        .iter()
+        .copied()
+        .find(|node| dependencies[*node].iter().all(|d| !remaining.contains(d)))
+    {
+        remaining.remove(&next);
+        order.push(next);
+    }
+
+    if remaining.is_empty() {
+        return Ok(order);
+    }
+
+    while let Some(sink) = remaining.iter().copied().find(|node| {
+        remaining
+            .iter()
+            .all(|other| !dependencies[*other].contains(node))
+    }) {
+        remaining.remove(&sink);
+    }
+
+    Err(remaining.into_iter().collect())
+}
+
+fn alias<T: Clone + PartialEq>(
+    resources: &[(String, T)],
+    lifetime: impl Fn(&str) -> Option<(usize, usize)>,
+) -> (HashMap<String, usize>, Vec<T>) {
+    let mut used = resources
+        .iter()
+        .filter_map(|(name, info)| lifetime(name).map(|(first, last)| (first, last, name, info)))
+        .collect::<Vec<_>>();
+    used.sort_by_key(|(first, last, _, _)| (*first, *last));
+
+    let mut slots: Vec<(T, usize)> = Vec::new();
+    let mut assigned = HashMap::new();
+    for (first, last, name, info) in used {
+        let slot = match slots
+            .iter()
+            .position(|(slot, end)| slot == info && *end < first)
+        {
+            Some(slot) => {
+                slots[slot].1 = last;
+                slot
+            }
+            None => {
+                slots.push((info.clone(), last));
+                slots.len() - 1
+            }
+        };
+
+        assigned.insert(name.clone(), slot);
+    }
+
+    (assigned, slots.into_iter().map(|(info, _)| info).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::{renderer::TextureSize, Gpu};
+
+    struct TestNode {
+        reads: Vec<&'static str>,
+        writes: Vec<&'static str>,
+        modifies: Vec<&'static str>,
+    }
+
+    impl TestNode {
+        fn new() -> Self {
+            Self {
+                reads: Vec::new(),
+                writes: Vec::new(),
+                modifies: Vec::new(),
+            }
+        }
+
+        fn reads(mut self, name: &'static str) -> Self {
+            self.reads.push(name);
+            self
+        }
+
+        fn writes(mut self, name: &'static str) -> Self {
+            self.writes.push(name);
+            self
+        }
+
+        fn modifies(mut self, name: &'static str) -> Self {
+            self.modifies.push(name);
+            self
+        }
+    }
+
+    impl RenderGraphNodeBuilder for TestNode {
+        fn declare(&self, resources: &mut NodeResources) {
+            self.reads.iter().for_each(|name| resources.read(name));
+            self.writes.iter().for_each(|name| resources.write(name));
+            self.modifies.iter().for_each(|name| resources.modify(name));
+        }
+
+        fn build(self: Box<Self>, _: &Gpu) -> Box<dyn RenderGraphNode> {
+            unreachable!()
+        }
+    }
+
+    fn texture(size: u32) -> TextureInfo {
+        TextureInfo {
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            size: TextureSize::Fixed(size, size),
+        }
+    }
+
+    fn graph(textures: &[&str], nodes: Vec<(&str, TestNode)>) -> RenderGraphBuilder {
+        let mut builder = RenderGraphBuilder::new();
+        for name in textures {
+            builder.add_texture(name, texture(64));
+        }
+
+        for (name, node) in nodes {
+            builder.add_node(name, node);
+        }
+
+        builder
+    }
+
+    fn order(builder: &RenderGraphBuilder) -> Vec<String> {
+        let plan = builder.compile().unwrap();
+        plan.ordered_names().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn readers_run_after_writers() {
+        let builder = graph(
+            &["shadow"],
+            vec![
+                ("forward", TestNode::new().reads("shadow").writes(SURFACE)),
+                ("shadow", TestNode::new().writes("shadow")),
+                ("ui", TestNode::new().modifies(SURFACE)),
+            ],
+        );
+
+        assert_eq!(order(&builder), ["shadow", "forward", "ui"]);
+    }
+
+    #[test]
+    fn readers_run_before_the_next_writer() {
+        let builder = graph(
+            &["color"],
+            vec![
+                ("clear", TestNode::new().writes("color")),
+                ("blur", TestNode::new().reads("color").writes(SURFACE)),
+                ("overlay", TestNode::new().writes("color")),
+                ("present", TestNode::new().reads("color").modifies(SURFACE)),
+            ],
+        );
+
+        let plan = builder.compile().unwrap();
+        let position = |name: &str| plan.ordered_names().position(|n| n == name).unwrap();
+        assert!(position("clear") < position("blur"));
+        assert!(position("blur") < position("overlay"));
+        assert!(position("overlay") < position("present"));
+    }
+
+    #[test]
+    fn missing_producer_fails() {
+        let builder = graph(
+            &["shadow"],
+            vec![("forward", TestNode::new().reads("shadow"))],
+        );
+
+        assert_eq!(
+            builder.compile().err(),
+            Some(RenderGraphError::MissingProducer {
+                node: "forward".to_string(),
+                resource: "shadow".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_resource_fails() {
+        let builder = graph(&[], vec![("forward", TestNode::new().reads("shadow"))]);
+
+        assert_eq!(
+            builder.compile().err(),
+            Some(RenderGraphError::UnknownResource {
+                node: "forward".to_string(),
+                resource: "shadow".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_node_fails() {
+        let builder = graph(
+            &[],
+            vec![
+                ("forward", TestNode::new().writes(SURFACE)),
+                ("forward", TestNode::new().writes(SURFACE)),
+            ],
+        );
+
+        assert_eq!(
+            builder.compile().err(),
+            Some(RenderGraphError::DuplicateNode("forward".to_string()))
+        );
+    }
+
+    #[test]
+    fn cycle_fails() {
+        let builder = graph(
+            &["a", "b"],
+            vec![
+                ("first", TestNode::new().reads("b").writes("a")),
+                ("second", TestNode::new().reads("a").writes("b")),
+                ("present", TestNode::new().reads("a").writes(SURFACE)),
+            ],
+        );
+
+        assert_eq!(
+            builder.compile().err(),
+            Some(RenderGraphError::Cycle(vec![
+                "first".to_string(),
+                "second".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn transient_textures_share_slots() {
+        let mut builder = graph(
+            &["a", "b"],
+            vec![
+                ("write_a", TestNode::new().writes("a")),
+                ("read_a", TestNode::new().reads("a").writes(SURFACE)),
+                ("write_b", TestNode::new().writes("b").modifies(SURFACE)),
+                ("read_b", TestNode::new().reads("b").modifies(SURFACE)),
+                ("write_c", TestNode::new().writes("c").modifies(SURFACE)),
+                ("read_c", TestNode::new().reads("c").modifies(SURFACE)),
+            ],
+        );
+        builder.add_texture("c", texture(32));
+
+        let plan = builder.compile().unwrap();
+        assert_eq!(plan.texture_slot("a"), plan.texture_slot("b"));
+        assert_ne!(plan.texture_slot("a"), plan.texture_slot("c"));
+        assert_eq!(plan.texture_slots().len(), 2);
+    }
+
+    #[test]
+    fn overlapping_textures_do_not_alias() {
+        let builder = graph(
+            &["a", "b"],
+            vec![
+                ("write", TestNode::new().writes("a").writes("b")),
+                (
+                    "read",
+                    TestNode::new().reads("a").reads("b").writes(SURFACE),
+                ),
+            ],
+        );
+
+        let plan = builder.compile().unwrap();
+        assert_ne!(plan.texture_slot("a"), plan.texture_slot("b"));
+        assert_eq!(plan.texture_slots().len(), 2);
+    }
+}
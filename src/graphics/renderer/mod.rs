@@ -1,41 +1,81 @@
-use self::{
-    node::RenderPassKind,
-    pass::{RenderPass, RenderPassBuilder},
-};
-use super::{gpu::Gpu, surface::RenderSurface, Graphics, TextureId};
-use std::{collections::HashMap, rc::Rc};
+use self::graph::{GraphContext, RenderGraph, RenderGraphBuilder};
+use super::{gpu::Gpu, surface::RenderSurface, Graphics};
+use std::rc::Rc;
 
+pub mod graph;
 pub mod node;
 pub mod pass;
 pub mod subpass;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextureSize {
+    Surface(f32),
+    Fixed(u32, u32),
+}
+
+impl TextureSize {
+    pub fn resolve(&self, width: u32, height: u32) -> (u32, u32) {
+        match *self {
+            Self::Surface(scale) => (
+                ((width as f32 * scale) as u32).max(1),
+                ((height as f32 * scale) as u32).max(1),
+            ),
+            Self::Fixed(width, height) => (width, height),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct TextureInfo {
     pub format: wgpu::TextureFormat,
     pub usage: wgpu::TextureUsages,
+    pub size: TextureSize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BufferInfo {
+    pub size: u64,
+    pub usage: wgpu::BufferUsages,
 }
 
 pub struct Renderer {
     gpu: Rc<Gpu>,
-    textures: HashMap<TextureId, wgpu::TextureView>,
-    texture_info: HashMap<TextureId, TextureInfo>,
-    passes: Vec<RenderPass>,
+    graph: RenderGraph,
+    textures: Vec<wgpu::TextureView>,
+    buffers: Vec<wgpu::Buffer>,
+    size: (u32, u32),
 }
 
 impl Renderer {
-    pub fn new(
-        gpu: Rc<Gpu>,
-        textures: HashMap<TextureId, wgpu::TextureView>,
-        texture_info: HashMap<TextureId, TextureInfo>,
-        passes: Vec<RenderPass>,
-    ) -> Self {
+    pub fn new(gpu: Rc<Gpu>, graph: RenderGraph, width: u32, height: u32) -> Self {
+        let textures = Self::create_textures(&gpu, &graph, width, height);
+        let buffers = graph
+            .plan()
+            .buffer_slots()
+            .iter()
+            .map(|info| {
+                gpu.device().create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Render Graph Buffer"),
+                    size: info.size,
+                    usage: info.usage,
+                    mapped_at_creation: false,
+                })
+            })
+            .collect();
+
         Self {
             gpu,
+            graph,
             textures,
-            passes,
-            texture_info,
+            buffers,
+            size: (width, height),
         }
     }
 
+    pub fn graph(&self) -> &RenderGraph {
+        &self.graph
+    }
+
     pub fn render(
         &self,
         graphics: &mut Graphics,
@@ -48,11 +88,23 @@ impl Renderer {
                     label: Some("Render Encoder"),
                 });
 
+        graphics.cull_views(self.size.0, self.size.1);
+
         let surface_texture = surface.surface().get_current_texture()?;
         let surface_view = surface_texture
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
+        let ctx = GraphContext::new(
+            graphics,
+            self.graph.plan(),
+            &surface_view,
+            self.size,
+            &self.textures,
+            &self.buffers,
+        );
+        self.graph.execute(&ctx, &mut encoder);
+
         self.gpu.queue().submit(std::iter::once(encoder.finish()));
 
         surface_texture.present();
@@ -61,60 +113,27 @@ impl Renderer {
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        for (id, info) in &self.texture_info {
-            let texture = self.gpu.device().create_texture(&wgpu::TextureDescriptor {
-                label: Some("Texture"),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: info.format,
-                usage: info.usage,
-                view_formats: &vec![],
-            });
-
-            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-            self.textures.insert(id.clone(), view);
-        }
+        self.size = (width, height);
+        self.textures = Self::create_textures(&self.gpu, &self.graph, width, height);
     }
-}
 
-pub struct RendererBuilder {
-    textures: HashMap<TextureId, TextureInfo>,
-}
-
-impl RendererBuilder {
-    pub fn new() -> Self {
-        Self {
-            textures: HashMap::new(),
-        }
-    }
-
-    pub fn add_texture(&mut self, id: TextureId, info: TextureInfo) {
-        self.textures.insert(id, info);
-    }
-
-    pub fn build(mut self, gpu: &Rc<Gpu>, surface: &RenderSurface) -> Renderer {
-        let passes = self.create_passes();
-
-        let passes = passes.into_iter().map(|p| p.build(gpu)).collect::<Vec<_>>();
-
-        let size = surface.window().inner_size();
-
-        let textures = self
-            .textures
+    fn create_textures(
+        gpu: &Gpu,
+        graph: &RenderGraph,
+        width: u32,
+        height: u32,
+    ) -> Vec<wgpu::TextureView> {
+        graph
+            .plan()
+            .texture_slots()
             .iter()
-            .map(|(id, info)| {
+            .map(|info| {
+                let (width, height) = info.size.resolve(width, height);
                 let texture = gpu.device().create_texture(&wgpu::TextureDescriptor {
-                    label: Some("Texture"),
+                    label: Some("Render Graph Texture"),
                     size: wgpu::Extent3d {
-                        width: size.width,
-                        height: size.height,
+                        width,
+                        height,
                         depth_or_array_layers: 1,
                     },
                     mip_level_count: 1,
@@ -122,21 +141,55 @@ impl RendererBuilder {
                     dimension: wgpu::TextureDimension::D2,
                     format: info.format,
                     usage: info.usage,
-                    view_formats: &vec![],
+                    view_formats: &[],
                 });
 
-                let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-                (id.clone(), view)
+                texture.create_view(&wgpu::TextureViewDescriptor::default())
             })
-            .collect::<HashMap<_, _>>();
+            .collect()
+    }
+}
 
-        Renderer::new(gpu.clone(), textures, self.textures, passes)
+pub struct RendererBuilder {
+    graph: RenderGraphBuilder,
+}
+
+impl RendererBuilder {
+    pub fn new() -> Self {
+        Self {
+            graph: RenderGraphBuilder::new(),
+        }
     }
 
-    fn create_passes(&mut self) -> Vec<RenderPassBuilder> {
-        let forward = RenderPassBuilder::new(RenderPassKind::Forward);
+    pub fn with_graph(mut self, graph: RenderGraphBuilder) -> Self {
+        self.graph = graph;
+        self
+    }
 
-        vec![forward]
+    pub fn build(self, gpu: &Rc<Gpu>, surface: &RenderSurface) -> Renderer {
+        let plan = match self.graph.compile() {
+            Ok(plan) => plan,
+            Err(error) => panic!("Invalid render graph: {}", error),
+        };
+
+        let mut nodes = self
+            .graph
+            .take_nodes()
+            .into_iter()
+            .map(|(_, node)| Some(node.build(gpu)))
+            .collect::<Vec<_>>();
+        let nodes = plan
+            .order()
+            .iter()
+            .filter_map(|index| nodes[*index].take())
+            .collect();
+
+        let size = surface.window().inner_size();
+        Renderer::new(
+            gpu.clone(),
+            RenderGraph::new(plan, nodes),
+            size.width,
+            size.height,
+        )
     }
 }
@@ -1,4 +1,7 @@
-use super::Gpu;
+use super::{
+    graph::{GraphContext, NodeResources},
+    Gpu,
+};
 use crate::graphics::RenderContext;
 use std::collections::HashMap;
 
@@ -16,6 +19,16 @@ pub trait RenderNodeBuilder: 'static {
     fn build(&self, device: &Gpu) -> Box<dyn RenderNode>;
 }
 
+pub trait RenderGraphNode {
+    fn execute(&self, ctx: &GraphContext, encoder: &mut wgpu::CommandEncoder);
+}
+
+pub trait RenderGraphNodeBuilder: 'static {
+    fn declare(&self, resources: &mut NodeResources);
+
+    fn build(self: Box<Self>, gpu: &Gpu) -> Box<dyn RenderGraphNode>;
+}
+
 pub struct RenderNodes {
     nodes: HashMap<RenderPassKind, Vec<(usize, Box<dyn RenderNodeBuilder>)>>,
 }
@@ -1,14 +1,25 @@
 use super::{
-    node::{RenderNodeBuilder, RenderPassKind},
+    graph::{GraphContext, NodeResources, SURFACE},
+    node::{RenderGraphNode, RenderGraphNodeBuilder, RenderNodeBuilder, RenderPassKind},
     subpass::{Subpass, SubpassBuilder},
 };
-use crate::graphics::{context::RenderContext, gpu::Gpu, view::RenderView, Graphics, TextureId};
-use std::collections::HashMap;
-use wgpu::TextureView;
+use crate::{
+    graphics::{context::RenderContext, gpu::Gpu, view::RenderView, DrawList},
+    shared::Rect,
+};
 
 pub enum Attachment {
     Surface,
-    Texture(TextureId),
+    Texture(String),
+}
+
+impl Attachment {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Surface => SURFACE,
+            Self::Texture(name) => name,
+        }
+    }
 }
 
 pub struct ColorAttachment {
@@ -52,20 +63,36 @@ impl RenderPass {
         self.id
     }
 
-    pub fn execute(
+    pub fn execute_view(
         &self,
-        graphics: &Graphics,
-        view: &RenderView,
-        target: &TextureView,
-        textures: &HashMap<TextureId, wgpu::TextureView>,
+        graph: &GraphContext,
+        index: usize,
+        target: &wgpu::TextureView,
+        viewport: Rect,
+        clear: bool,
         encoder: &mut wgpu::CommandEncoder,
     ) {
-        let ctx = RenderContext::new(graphics, view, &textures);
+        let ctx = RenderContext::new(graph, index, viewport);
+        let view = ctx.view();
+        let draws = DrawList::build(self.id, &ctx);
+        let buffers = draws.upload(&ctx);
 
-        let mut render_pass = self.begin_render_pass(view, target, textures, encoder);
-        for subpass in &self.subpasses {
-            //SORT DRAW COMMANDS HERE
+        let depth = graph.target_depth(view.target());
+        let mut render_pass = self.begin_render_pass(view, graph, target, depth, clear, encoder);
+        render_pass.set_viewport(
+            viewport.x,
+            viewport.y,
+            viewport.width,
+            viewport.height,
+            0.0,
+            1.0,
+        );
+
+        if let Some(buffers) = &buffers {
+            draws.execute(graph.graphics(), buffers, &mut render_pass);
+        }
 
+        for subpass in &self.subpasses {
             subpass.execute(&ctx, &mut render_pass);
         }
     }
@@ -73,10 +100,17 @@ impl RenderPass {
     fn begin_render_pass<'a>(
         &'a self,
         view: &RenderView,
-        target: &'a TextureView,
-        textures: &'a HashMap<TextureId, wgpu::TextureView>,
+        graph: &GraphContext<'a>,
+        target: &'a wgpu::TextureView,
+        target_depth: Option<&'a wgpu::TextureView>,
+        clear: bool,
         encoder: &'a mut wgpu::CommandEncoder,
     ) -> wgpu::RenderPass<'a> {
+        let attachment = |attachment: &Attachment| match attachment {
+            Attachment::Surface => target,
+            Attachment::Texture(name) => graph.texture(name).expect("Texture not found"),
+        };
+
         encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Render Pass"),
             color_attachments: &self
@@ -84,37 +118,21 @@ impl RenderPass {
                 .iter()
                 .map(|color| {
                     Some(wgpu::RenderPassColorAttachment {
-                        view: match color.attachment {
-                            Attachment::Surface => &target,
-                            Attachment::Texture(ref id) => {
-                                textures.get(id).expect("Texture not found")
-                            }
-                        },
+                        view: attachment(&color.attachment),
                         ops: wgpu::Operations {
                             store: color.store_op,
-                            load: match color.clear {
+                            load: match color.clear && clear {
                                 true => wgpu::LoadOp::Clear(view.clear_color().into()),
                                 false => wgpu::LoadOp::Load,
                             },
                         },
-                        resolve_target: match color.resolve_target {
-                            Some(ref attachment) => Some(match attachment {
-                                Attachment::Surface => &target,
-                                Attachment::Texture(ref id) => {
-                                    textures.get(id).expect("Texture not found")
-                                }
-                            }),
-                            None => None,
-                        },
+                        resolve_target: color.resolve_target.as_ref().map(attachment),
                     })
                 })
                 .collect::<Vec<_>>(),
             depth_stencil_attachment: match self.depth {
                 Some(ref depth) => Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: match depth.attachment {
-                        Attachment::Surface => &target,
-                        Attachment::Texture(ref id) => textures.get(id).expect("Texture not found"),
-                    },
+                    view: target_depth.unwrap_or_else(|| attachment(&depth.attachment)),
                     depth_ops: Some(wgpu::Operations {
                         load: wgpu::LoadOp::Clear(depth.clear_depth.unwrap_or(1.0)),
                         store: depth.depth_store_op,
@@ -131,10 +149,34 @@ impl RenderPass {
     }
 }
 
+impl RenderGraphNode for RenderPass {
+    fn execute(&self, ctx: &GraphContext, encoder: &mut wgpu::CommandEncoder) {
+        let mut cleared = Vec::new();
+        for (index, view) in ctx.graphics().views().iter().enumerate() {
+            let Some((target, width, height)) = ctx.target(view.target()) else {
+                continue;
+            };
+
+            let viewport = view.pixel_viewport(width, height);
+            if viewport.width < 1.0 || viewport.height < 1.0 {
+                continue;
+            }
+
+            let clear = !cleared.contains(view.target());
+            if clear {
+                cleared.push(*view.target());
+            }
+
+            self.execute_view(ctx, index, target, viewport, clear, encoder);
+        }
+    }
+}
+
 pub struct RenderPassBuilder {
     id: RenderPassKind,
     colors: Vec<ColorAttachment>,
     depth: Option<DepthAttachment>,
+    reads: Vec<String>,
     subpasses: Vec<SubpassBuilder>,
 }
 
@@ -144,6 +186,7 @@ impl RenderPassBuilder {
             id,
             colors: Vec::new(),
             depth: None,
+            reads: Vec::new(),
             subpasses: Vec::new(),
         }
     }
@@ -188,6 +231,11 @@ impl RenderPassBuilder {
         self
     }
 
+    pub fn with_read(mut self, name: &str) -> Self {
+        self.reads.push(name.to_string());
+        self
+    }
+
     pub fn add_subpass(&mut self, subpass: SubpassBuilder) {
         self.subpasses.push(subpass);
     }
@@ -212,3 +260,30 @@ impl RenderPassBuilder {
         )
     }
 }
+
+impl RenderGraphNodeBuilder for RenderPassBuilder {
+    fn declare(&self, resources: &mut NodeResources) {
+        for name in &self.reads {
+            resources.read(name);
+        }
+
+        for color in &self.colors {
+            match color.clear {
+                true => resources.write(color.attachment.name()),
+                false => resources.modify(color.attachment.name()),
+            }
+
+            if let Some(resolve_target) = &color.resolve_target {
+                resources.write(resolve_target.name());
+            }
+        }
+
+        if let Some(depth) = &self.depth {
+            resources.write(depth.attachment.name());
+        }
+    }
+
+    fn build(self: Box<Self>, gpu: &Gpu) -> Box<dyn RenderGraphNode> {
+        Box::new((*self).build(gpu))
+    }
+}
@@ -1,4 +1,9 @@
-use winit::{dpi::PhysicalSize, event_loop::EventLoop, window::Window, window::WindowBuilder};
+use crate::game::WindowConfig;
+use winit::{
+    dpi::PhysicalSize,
+    event_loop::EventLoop,
+    window::{Fullscreen, Window, WindowBuilder},
+};
 
 pub struct RenderSurface {
     surface: wgpu::Surface,
@@ -11,14 +16,18 @@ pub struct RenderSurface {
 }
 
 impl RenderSurface {
-    pub async fn new(events: &EventLoop<()>) -> Self {
+    pub async fn new(events: &EventLoop<()>, settings: &WindowConfig) -> Self {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             backends: wgpu::Backends::PRIMARY,
             ..Default::default()
         });
 
+        let fullscreen = settings.fullscreen.then_some(Fullscreen::Borderless(None));
+
         let window = WindowBuilder::new()
-            .with_title("Rust Game Engine")
+            .with_title(&settings.title)
+            .with_inner_size(PhysicalSize::new(settings.width, settings.height))
+            .with_fullscreen(fullscreen)
             .build(events)
             .unwrap();
 
@@ -44,12 +53,18 @@ impl RenderSurface {
             .find(|f| f.is_srgb())
             .unwrap_or(surface_caps.formats[0]);
 
+        let present_mode = if settings.vsync {
+            wgpu::PresentMode::AutoVsync
+        } else {
+            wgpu::PresentMode::AutoNoVsync
+        };
+
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: surface_caps.present_modes[0],
+            present_mode,
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
         };
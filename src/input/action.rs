@@ -0,0 +1,635 @@
+use super::{GamepadAxis, GamepadButton, Gamepads, Input, KeyCode, Mouse, MouseButton};
+use crate::ecs::{Resource, World};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputSource {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    pub fn keys(&self) -> [KeyCode; 2] {
+        match self {
+            Self::Shift => [KeyCode::LShift, KeyCode::RShift],
+            Self::Control => [KeyCode::LControl, KeyCode::RControl],
+            Self::Alt => [KeyCode::LAlt, KeyCode::RAlt],
+            Self::Super => [KeyCode::LWin, KeyCode::RWin],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AxisSource {
+    Gamepad(GamepadAxis),
+    Buttons {
+        negative: InputSource,
+        positive: InputSource,
+    },
+    MouseX,
+    MouseY,
+    Wheel,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ButtonBinding {
+    pub inputs: Vec<InputSource>,
+    pub modifiers: Vec<Modifier>,
+}
+
+impl ButtonBinding {
+    pub fn new(input: InputSource) -> Self {
+        Self {
+            inputs: vec![input],
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn chord(inputs: Vec<InputSource>) -> Self {
+        Self {
+            inputs,
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub source: AxisSource,
+    pub dead_zone: f32,
+    pub scale: f32,
+}
+
+impl AxisBinding {
+    pub fn new(source: AxisSource) -> Self {
+        Self {
+            source,
+            dead_zone: 0.0,
+            scale: 1.0,
+        }
+    }
+
+    pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Button(ButtonBinding),
+    Axis(AxisBinding),
+    DualAxis { x: AxisBinding, y: AxisBinding },
+}
+
+impl Binding {
+    pub fn key(key: KeyCode) -> Binding {
+        Binding::Button(ButtonBinding::new(InputSource::Key(key)))
+    }
+
+    pub fn mouse(button: MouseButton) -> Binding {
+        Binding::Button(ButtonBinding::new(InputSource::Mouse(button)))
+    }
+
+    pub fn gamepad(button: GamepadButton) -> Binding {
+        Binding::Button(ButtonBinding::new(InputSource::Gamepad(button)))
+    }
+
+    pub fn keys_2d(left: KeyCode, right: KeyCode, down: KeyCode, up: KeyCode) -> Binding {
+        Binding::DualAxis {
+            x: AxisBinding::new(AxisSource::Buttons {
+                negative: InputSource::Key(left),
+                positive: InputSource::Key(right),
+            }),
+            y: AxisBinding::new(AxisSource::Buttons {
+                negative: InputSource::Key(down),
+                positive: InputSource::Key(up),
+            }),
+        }
+    }
+
+    pub fn stick(x: GamepadAxis, y: GamepadAxis, dead_zone: f32) -> Binding {
+        Binding::DualAxis {
+            x: AxisBinding::new(AxisSource::Gamepad(x)).with_dead_zone(dead_zone),
+            y: AxisBinding::new(AxisSource::Gamepad(y)).with_dead_zone(dead_zone),
+        }
+    }
+}
+
+pub struct InputSnapshot<'a> {
+    pub keys: &'a Input<KeyCode>,
+    pub buttons: &'a Input<MouseButton>,
+    pub mouse: &'a Mouse,
+    pub gamepads: &'a Gamepads,
+}
+
+impl<'a> InputSnapshot<'a> {
+    pub fn pressed(&self, source: InputSource) -> bool {
+        match source {
+            InputSource::Key(key) => self.keys.pressed(key),
+            InputSource::Mouse(button) => self.buttons.pressed(button),
+            InputSource::Gamepad(button) => self
+                .gamepads
+                .iter()
+                .any(|(_, gamepad)| gamepad.buttons().pressed(button)),
+        }
+    }
+
+    pub fn just_pressed(&self) -> Option<InputSource> {
+        if let Some(key) = self.keys.get_just_pressed().next() {
+            return Some(InputSource::Key(*key));
+        }
+
+        if let Some(button) = self.buttons.get_just_pressed().next() {
+            return Some(InputSource::Mouse(*button));
+        }
+
+        self.gamepads.iter().find_map(|(_, gamepad)| {
+            gamepad
+                .buttons()
+                .get_just_pressed()
+                .next()
+                .map(|button| InputSource::Gamepad(*button))
+        })
+    }
+
+    pub fn modifier(&self, modifier: Modifier) -> bool {
+        self.keys.any_pressed(modifier.keys())
+    }
+
+    pub fn button(&self, binding: &ButtonBinding) -> bool {
+        !binding.inputs.is_empty()
+            && binding.inputs.iter().all(|input| self.pressed(*input))
+            && binding
+                .modifiers
+                .iter()
+                .all(|modifier| self.modifier(*modifier))
+    }
+
+    pub fn axis(&self, binding: &AxisBinding) -> f32 {
+        let value = match binding.source {
+            AxisSource::Gamepad(axis) => self
+                .gamepads
+                .iter()
+                .map(|(_, gamepad)| gamepad.axis(axis))
+                .fold(0.0, strongest),
+            AxisSource::Buttons { negative, positive } => {
+                let negative = if self.pressed(negative) { 1.0 } else { 0.0 };
+                let positive = if self.pressed(positive) { 1.0 } else { 0.0 };
+                positive - negative
+            }
+            AxisSource::MouseX => self.mouse.delta().x,
+            AxisSource::MouseY => self.mouse.delta().y,
+            AxisSource::Wheel => self.mouse.wheel().y,
+        };
+
+        if value.abs() <= binding.dead_zone {
+            0.0
+        } else {
+            value * binding.scale
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ActionMap {
+    actions: HashMap<String, Vec<Binding>>,
+    #[serde(skip)]
+    listening: Option<String>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, action: &str, binding: Binding) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        self.actions
+            .entry(action.to_string())
+            .or_default()
+            .push(binding);
+    }
+
+    pub fn rebind(&mut self, action: &str, index: usize, binding: Binding) {
+        match self.actions.get_mut(action) {
+            Some(bindings) if index < bindings.len() => bindings[index] = binding,
+            _ => self.bind(action, binding),
+        }
+    }
+
+    pub fn unbind(&mut self, action: &str) -> Option<Vec<Binding>> {
+        self.actions.remove(action)
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions
+            .get(action)
+            .map(|b| b.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = (&String, &Vec<Binding>)> {
+        self.actions.iter()
+    }
+
+    pub fn listen(&mut self, action: &str) {
+        self.listening = Some(action.to_string());
+    }
+
+    pub fn listening(&self) -> Option<&str> {
+        self.listening.as_deref()
+    }
+
+    pub fn capture(&mut self, input: &InputSnapshot) -> bool {
+        let Some(action) = self.listening.clone() else {
+            return false;
+        };
+
+        match input.just_pressed() {
+            Some(source) => {
+                let binding = Binding::Button(ButtonBinding::new(source));
+                let index = self
+                    .bindings(&action)
+                    .iter()
+                    .position(|b| matches!(b, Binding::Button(_)))
+                    .unwrap_or(usize::MAX);
+                self.rebind(&action, index, binding);
+                self.listening = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<ActionMap> {
+        let text = std::fs::read_to_string(path)?;
+        ron::from_str(&text)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        std::fs::write(path, text)
+    }
+}
+
+impl Resource for ActionMap {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActionData {
+    pub pressed: bool,
+    pub just_pressed: bool,
+    pub just_released: bool,
+    pub value: f32,
+    pub axis: glam::Vec2,
+}
+
+pub struct ActionState {
+    actions: HashMap<String, ActionData>,
+}
+
+impl ActionState {
+    pub fn new() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, action: &str) -> ActionData {
+        self.actions.get(action).copied().unwrap_or_default()
+    }
+
+    pub fn pressed(&self, action: &str) -> bool {
+        self.get(action).pressed
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.get(action).just_pressed
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.get(action).just_released
+    }
+
+    pub fn value(&self, action: &str) -> f32 {
+        self.get(action).value
+    }
+
+    pub fn axis(&self, action: &str) -> glam::Vec2 {
+        self.get(action).axis
+    }
+
+    pub fn update(&mut self, map: &ActionMap, input: &InputSnapshot) {
+        self.actions
+            .retain(|action, _| map.actions.contains_key(action));
+
+        for (action, bindings) in map.actions() {
+            let mut value = 0.0;
+            let mut axis = glam::Vec2::ZERO;
+            for binding in bindings {
+                match binding {
+                    Binding::Button(button) if input.button(button) => {
+                        value = strongest(value, 1.0)
+                    }
+                    Binding::Button(_) => {}
+                    Binding::Axis(binding) => value = strongest(value, input.axis(binding)),
+                    Binding::DualAxis { x, y } => {
+                        axis += glam::Vec2::new(input.axis(x), input.axis(y));
+                    }
+                }
+            }
+
+            let value = value.clamp(-1.0, 1.0);
+            let axis = axis.clamp_length_max(1.0);
+            let pressed = value != 0.0 || axis != glam::Vec2::ZERO;
+
+            let data = self.actions.entry(action.clone()).or_default();
+            data.just_pressed = pressed && !data.pressed;
+            data.just_released = !pressed && data.pressed;
+            data.pressed = pressed;
+            data.value = value;
+            data.axis = axis;
+        }
+    }
+}
+
+impl Resource for ActionState {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+fn strongest(value: f32, other: f32) -> f32 {
+    if other.abs() > value.abs() || (other.abs() == value.abs() && other > value) {
+        other
+    } else {
+        value
+    }
+}
+
+pub fn update_actions(world: &World) {
+    let keys = world.resource::<Input<KeyCode>>();
+    let buttons = world.resource::<Input<MouseButton>>();
+    let mouse = world.resource::<Mouse>();
+    let gamepads = world.resource::<Gamepads>();
+    let snapshot = InputSnapshot {
+        keys: &keys,
+        buttons: &buttons,
+        mouse: &mouse,
+        gamepads: &gamepads,
+    };
+
+    let mut map = world.resource_mut::<ActionMap>();
+    map.capture(&snapshot);
+
+    world.resource_mut::<ActionState>().update(&map, &snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::GamepadId;
+
+    struct Devices {
+        keys: Input<KeyCode>,
+        buttons: Input<MouseButton>,
+        mouse: Mouse,
+        gamepads: Gamepads,
+    }
+
+    impl Devices {
+        fn new() -> Self {
+            let mut gamepads = Gamepads::new();
+            gamepads.connect(GamepadId(0));
+
+            Self {
+                keys: Input::new(),
+                buttons: Input::new(),
+                mouse: Mouse::new(),
+                gamepads,
+            }
+        }
+
+        fn snapshot(&self) -> InputSnapshot<'_> {
+            InputSnapshot {
+                keys: &self.keys,
+                buttons: &self.buttons,
+                mouse: &self.mouse,
+                gamepads: &self.gamepads,
+            }
+        }
+
+        fn stick(&mut self, axis: GamepadAxis, value: f32) {
+            self.gamepads
+                .get_mut(GamepadId(0))
+                .unwrap()
+                .set_axis(axis, value);
+        }
+
+        fn update(&mut self, state: &mut ActionState, map: &ActionMap) {
+            state.update(map, &self.snapshot());
+            self.keys.clear();
+            self.buttons.clear();
+        }
+    }
+
+    fn left_right() -> Binding {
+        Binding::Axis(AxisBinding::new(AxisSource::Buttons {
+            negative: InputSource::Key(KeyCode::A),
+            positive: InputSource::Key(KeyCode::D),
+        }))
+    }
+
+    #[test]
+    fn button_and_axis_bindings_combine_in_any_order() {
+        let button = Binding::key(KeyCode::Space);
+        let forward = ActionMap::new()
+            .with("move", left_right())
+            .with("move", button.clone());
+        let backward = ActionMap::new()
+            .with("move", button)
+            .with("move", left_right());
+
+        let mut devices = Devices::new();
+        devices.keys.press(KeyCode::A);
+        devices.keys.press(KeyCode::Space);
+
+        for map in [forward, backward] {
+            let mut state = ActionState::new();
+            devices.update(&mut state, &map);
+            assert!(state.pressed("move"));
+            assert_eq!(state.value("move"), 1.0);
+        }
+
+        devices.keys.release(KeyCode::Space);
+        let map = ActionMap::new()
+            .with("move", Binding::key(KeyCode::Space))
+            .with("move", left_right());
+        let mut state = ActionState::new();
+        devices.update(&mut state, &map);
+        assert!(state.pressed("move"));
+        assert_eq!(state.value("move"), -1.0);
+    }
+
+    #[test]
+    fn strongest_axis_wins() {
+        let stick = Binding::Axis(AxisBinding::new(AxisSource::Gamepad(
+            GamepadAxis::LeftStickX,
+        )));
+        let map = ActionMap::new()
+            .with("steer", stick)
+            .with("steer", left_right());
+
+        let mut devices = Devices::new();
+        devices.stick(GamepadAxis::LeftStickX, 0.4);
+        devices.keys.press(KeyCode::A);
+
+        let mut state = ActionState::new();
+        devices.update(&mut state, &map);
+        assert_eq!(state.value("steer"), -1.0);
+
+        devices.keys.release(KeyCode::A);
+        devices.update(&mut state, &map);
+        assert_eq!(state.value("steer"), 0.4);
+    }
+
+    #[test]
+    fn dead_zone_and_scale_apply_per_binding() {
+        let stick = AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftStickY))
+            .with_dead_zone(0.2)
+            .with_scale(0.5);
+        let map = ActionMap::new().with("look", Binding::Axis(stick));
+
+        let mut devices = Devices::new();
+        let mut state = ActionState::new();
+
+        devices.stick(GamepadAxis::LeftStickY, 0.15);
+        devices.update(&mut state, &map);
+        assert!(!state.pressed("look"));
+        assert_eq!(state.value("look"), 0.0);
+
+        devices.stick(GamepadAxis::LeftStickY, -0.8);
+        devices.update(&mut state, &map);
+        assert!(state.just_pressed("look"));
+        assert_eq!(state.value("look"), -0.4);
+    }
+
+    #[test]
+    fn chords_need_every_input() {
+        let chord = ButtonBinding::chord(vec![
+            InputSource::Key(KeyCode::Q),
+            InputSource::Mouse(MouseButton::Left),
+        ]);
+        let map = ActionMap::new().with("special", Binding::Button(chord));
+
+        let mut devices = Devices::new();
+        let mut state = ActionState::new();
+
+        devices.keys.press(KeyCode::Q);
+        devices.update(&mut state, &map);
+        assert!(!state.pressed("special"));
+
+        devices.buttons.press(MouseButton::Left);
+        devices.update(&mut state, &map);
+        assert!(state.just_pressed("special"));
+
+        devices.keys.release(KeyCode::Q);
+        devices.update(&mut state, &map);
+        assert!(state.just_released("special"));
+    }
+
+    #[test]
+    fn modifiers_accept_either_side() {
+        let save =
+            ButtonBinding::new(InputSource::Key(KeyCode::S)).with_modifier(Modifier::Control);
+        let map = ActionMap::new().with("save", Binding::Button(save));
+
+        let mut devices = Devices::new();
+        let mut state = ActionState::new();
+
+        devices.keys.press(KeyCode::S);
+        devices.update(&mut state, &map);
+        assert!(!state.pressed("save"));
+
+        devices.keys.press(KeyCode::RControl);
+        devices.update(&mut state, &map);
+        assert!(state.just_pressed("save"));
+
+        devices.update(&mut state, &map);
+        assert!(state.pressed("save"));
+        assert!(!state.just_pressed("save"));
+    }
+
+    #[test]
+    fn dual_axes_are_clamped_to_unit_length() {
+        let map = ActionMap::new().with(
+            "walk",
+            Binding::keys_2d(KeyCode::A, KeyCode::D, KeyCode::S, KeyCode::W),
+        );
+
+        let mut devices = Devices::new();
+        devices.keys.press(KeyCode::D);
+        devices.keys.press(KeyCode::W);
+
+        let mut state = ActionState::new();
+        devices.update(&mut state, &map);
+        let axis = state.axis("walk");
+        assert!(state.pressed("walk"));
+        assert!((axis.length() - 1.0).abs() < 1e-6);
+        assert!((axis.x - axis.y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn capture_replaces_the_first_button_binding() {
+        let mut map = ActionMap::new()
+            .with("jump", left_right())
+            .with("jump", Binding::key(KeyCode::Space));
+        map.listen("jump");
+
+        let mut devices = Devices::new();
+        assert!(!map.capture(&devices.snapshot()));
+
+        devices.keys.press(KeyCode::J);
+        assert!(map.capture(&devices.snapshot()));
+        assert_eq!(map.listening(), None);
+        assert_eq!(map.bindings("jump")[1], Binding::key(KeyCode::J));
+    }
+}
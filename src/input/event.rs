@@ -0,0 +1,324 @@
+use super::{
+    GamepadAxis, GamepadButton, GamepadId, Gamepads, Input, KeyCode, Mouse, MouseButton, TextInput,
+};
+use crate::ecs::{Resource, World};
+use serde::{Deserialize, Serialize};
+use winit::event::{DeviceEvent, ElementState, MouseScrollDelta, WindowEvent};
+
+const LINE_HEIGHT: f32 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InputEvent {
+    KeyPressed(KeyCode),
+    KeyReleased(KeyCode),
+    MousePressed(MouseButton),
+    MouseReleased(MouseButton),
+    MouseMoved(glam::Vec2),
+    MouseMotion(glam::Vec2),
+    MouseWheel(glam::Vec2),
+    MouseEntered,
+    MouseLeft,
+    Text(char),
+    GamepadConnected(GamepadId),
+    GamepadDisconnected(GamepadId),
+    GamepadPressed(GamepadId, GamepadButton),
+    GamepadReleased(GamepadId, GamepadButton),
+    GamepadAxis(GamepadId, GamepadAxis, f32),
+    FocusLost,
+}
+
+impl InputEvent {
+    pub fn from_window_event(event: &WindowEvent) -> Option<InputEvent> {
+        match event {
+            WindowEvent::KeyboardInput { input, .. } => {
+                let key = input.virtual_keycode?;
+                match input.state {
+                    ElementState::Pressed => Some(InputEvent::KeyPressed(key)),
+                    ElementState::Released => Some(InputEvent::KeyReleased(key)),
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => match state {
+                ElementState::Pressed => Some(InputEvent::MousePressed(*button)),
+                ElementState::Released => Some(InputEvent::MouseReleased(*button)),
+            },
+            WindowEvent::CursorMoved { position, .. } => Some(InputEvent::MouseMoved(
+                glam::Vec2::new(position.x as f32, position.y as f32),
+            )),
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => glam::Vec2::new(*x, *y),
+                    MouseScrollDelta::PixelDelta(position) => glam::Vec2::new(
+                        position.x as f32 / LINE_HEIGHT,
+                        position.y as f32 / LINE_HEIGHT,
+                    ),
+                };
+                Some(InputEvent::MouseWheel(delta))
+            }
+            WindowEvent::CursorEntered { .. } => Some(InputEvent::MouseEntered),
+            WindowEvent::CursorLeft { .. } => Some(InputEvent::MouseLeft),
+            WindowEvent::ReceivedCharacter(c) if !c.is_control() => Some(InputEvent::Text(*c)),
+            WindowEvent::Focused(false) => Some(InputEvent::FocusLost),
+            _ => None,
+        }
+    }
+
+    pub fn from_device_event(event: &DeviceEvent) -> Option<InputEvent> {
+        match event {
+            DeviceEvent::MouseMotion { delta } => Some(InputEvent::MouseMotion(glam::Vec2::new(
+                delta.0 as f32,
+                delta.1 as f32,
+            ))),
+            _ => None,
+        }
+    }
+}
+
+pub struct InputEvents {
+    pending: Vec<InputEvent>,
+    frame: Vec<InputEvent>,
+}
+
+impl InputEvents {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            frame: Vec::new(),
+        }
+    }
+
+    pub fn send(&mut self, event: InputEvent) {
+        self.pending.push(event);
+    }
+
+    pub fn pending(&self) -> &[InputEvent] {
+        &self.pending
+    }
+
+    pub fn frame(&self) -> &[InputEvent] {
+        &self.frame
+    }
+
+    pub fn replace(&mut self, events: Vec<InputEvent>) {
+        self.pending = events;
+    }
+
+    pub(super) fn advance(&mut self) -> &[InputEvent] {
+        self.frame = std::mem::take(&mut self.pending);
+        &self.frame
+    }
+}
+
+impl Resource for InputEvents {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn update_input(world: &World) {
+    let mut events = world.resource_mut::<InputEvents>();
+    let mut keys = world.resource_mut::<Input<KeyCode>>();
+    let mut buttons = world.resource_mut::<Input<MouseButton>>();
+    let mut mouse = world.resource_mut::<Mouse>();
+    let mut text = world.resource_mut::<TextInput>();
+    let mut gamepads = world.resource_mut::<Gamepads>();
+
+    keys.clear();
+    buttons.clear();
+    mouse.clear();
+    text.clear();
+    gamepads.clear();
+
+    for event in events.advance() {
+        match *event {
+            InputEvent::KeyPressed(key) => keys.press(key),
+            InputEvent::KeyReleased(key) => keys.release(key),
+            InputEvent::MousePressed(button) => buttons.press(button),
+            InputEvent::MouseReleased(button) => buttons.release(button),
+            InputEvent::MouseMoved(position) => mouse.set_position(position),
+            InputEvent::MouseMotion(delta) => mouse.add_delta(delta),
+            InputEvent::MouseWheel(delta) => mouse.add_wheel(delta),
+            InputEvent::MouseEntered => mouse.set_inside(true),
+            InputEvent::MouseLeft => mouse.set_inside(false),
+            InputEvent::Text(c) => text.push(c),
+            InputEvent::GamepadConnected(id) => gamepads.connect(id),
+            InputEvent::GamepadDisconnected(id) => gamepads.disconnect(id),
+            InputEvent::GamepadPressed(id, button) => {
+                if let Some(gamepad) = gamepads.get_mut(id) {
+                    gamepad.buttons_mut().press(button);
+                }
+            }
+            InputEvent::GamepadReleased(id, button) => {
+                if let Some(gamepad) = gamepads.get_mut(id) {
+                    gamepad.buttons_mut().release(button);
+                }
+            }
+            InputEvent::GamepadAxis(id, axis, value) => {
+                if let Some(gamepad) = gamepads.get_mut(id) {
+                    gamepad.set_axis(axis, value);
+                }
+            }
+            InputEvent::FocusLost => {
+                keys.release_all();
+                buttons.release_all();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{ComponentManager, ResourceManager};
+    use std::collections::HashMap;
+
+    fn world() -> World {
+        let mut resources = ResourceManager::new();
+        resources.register(InputEvents::new());
+        resources.register(Input::<KeyCode>::new());
+        resources.register(Input::<MouseButton>::new());
+        resources.register(Mouse::new());
+        resources.register(TextInput::new());
+        resources.register(Gamepads::new());
+
+        World::new(ComponentManager::new(HashMap::new()), resources)
+    }
+
+    fn step(world: &World, events: &[InputEvent]) {
+        {
+            let mut input = world.resource_mut::<InputEvents>();
+            for event in events {
+                input.send(*event);
+            }
+        }
+
+        update_input(world);
+    }
+
+    #[test]
+    fn keys_are_just_pressed_for_one_step() {
+        let world = world();
+
+        step(&world, &[InputEvent::KeyPressed(KeyCode::Space)]);
+        {
+            let keys = world.resource::<Input<KeyCode>>();
+            assert!(keys.pressed(KeyCode::Space));
+            assert!(keys.just_pressed(KeyCode::Space));
+            assert!(!keys.just_released(KeyCode::Space));
+        }
+
+        step(&world, &[]);
+        {
+            let keys = world.resource::<Input<KeyCode>>();
+            assert!(keys.pressed(KeyCode::Space));
+            assert!(!keys.just_pressed(KeyCode::Space));
+        }
+
+        step(&world, &[InputEvent::KeyReleased(KeyCode::Space)]);
+        {
+            let keys = world.resource::<Input<KeyCode>>();
+            assert!(!keys.pressed(KeyCode::Space));
+            assert!(keys.just_released(KeyCode::Space));
+        }
+
+        step(&world, &[]);
+        assert!(!world
+            .resource::<Input<KeyCode>>()
+            .just_released(KeyCode::Space));
+    }
+
+    #[test]
+    fn press_and_release_in_one_step_reports_both_edges() {
+        let world = world();
+
+        step(
+            &world,
+            &[
+                InputEvent::MousePressed(MouseButton::Left),
+                InputEvent::MouseReleased(MouseButton::Left),
+            ],
+        );
+
+        let buttons = world.resource::<Input<MouseButton>>();
+        assert!(!buttons.pressed(MouseButton::Left));
+        assert!(buttons.just_pressed(MouseButton::Left));
+        assert!(buttons.just_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn focus_loss_releases_everything_held() {
+        let world = world();
+
+        step(
+            &world,
+            &[
+                InputEvent::KeyPressed(KeyCode::W),
+                InputEvent::MousePressed(MouseButton::Right),
+            ],
+        );
+        step(&world, &[InputEvent::FocusLost]);
+
+        let keys = world.resource::<Input<KeyCode>>();
+        let buttons = world.resource::<Input<MouseButton>>();
+        assert!(!keys.pressed(KeyCode::W));
+        assert!(keys.just_released(KeyCode::W));
+        assert!(!buttons.pressed(MouseButton::Right));
+        assert!(buttons.just_released(MouseButton::Right));
+    }
+
+    #[test]
+    fn mouse_delta_and_wheel_reset_each_step() {
+        let world = world();
+
+        step(
+            &world,
+            &[
+                InputEvent::MouseMoved(glam::Vec2::new(10.0, 20.0)),
+                InputEvent::MouseMotion(glam::Vec2::new(1.0, 2.0)),
+                InputEvent::MouseMotion(glam::Vec2::new(3.0, -1.0)),
+                InputEvent::MouseWheel(glam::Vec2::new(0.0, 1.0)),
+                InputEvent::MouseWheel(glam::Vec2::new(0.0, 2.0)),
+                InputEvent::Text('a'),
+            ],
+        );
+        {
+            let mouse = world.resource::<Mouse>();
+            assert_eq!(mouse.position(), glam::Vec2::new(10.0, 20.0));
+            assert_eq!(mouse.delta(), glam::Vec2::new(4.0, 1.0));
+            assert_eq!(mouse.wheel(), glam::Vec2::new(0.0, 3.0));
+            assert_eq!(world.resource::<TextInput>().text(), "a");
+        }
+
+        step(&world, &[]);
+        let mouse = world.resource::<Mouse>();
+        assert_eq!(mouse.position(), glam::Vec2::new(10.0, 20.0));
+        assert_eq!(mouse.delta(), glam::Vec2::ZERO);
+        assert_eq!(mouse.wheel(), glam::Vec2::ZERO);
+        assert_eq!(world.resource::<TextInput>().text(), "");
+    }
+
+    #[test]
+    fn gamepad_events_apply_to_connected_pads() {
+        let world = world();
+        let pad = GamepadId(0);
+
+        step(
+            &world,
+            &[
+                InputEvent::GamepadPressed(pad, GamepadButton::South),
+                InputEvent::GamepadConnected(pad),
+                InputEvent::GamepadPressed(pad, GamepadButton::South),
+                InputEvent::GamepadAxis(pad, GamepadAxis::LeftStickX, 0.5),
+            ],
+        );
+
+        let gamepads = world.resource::<Gamepads>();
+        let gamepad = gamepads.get(pad).unwrap();
+        assert!(gamepad.buttons().just_pressed(GamepadButton::South));
+        assert_eq!(gamepad.axis(GamepadAxis::LeftStickX), 0.5);
+        assert!(gamepads.get(GamepadId(1)).is_none());
+    }
+}
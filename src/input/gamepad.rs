@@ -0,0 +1,133 @@
+use super::Input;
+use crate::ecs::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GamepadId(pub u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+pub struct Gamepad {
+    buttons: Input<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl Gamepad {
+    pub fn new() -> Self {
+        Self {
+            buttons: Input::new(),
+            axes: HashMap::new(),
+        }
+    }
+
+    pub fn buttons(&self) -> &Input<GamepadButton> {
+        &self.buttons
+    }
+
+    pub fn buttons_mut(&mut self) -> &mut Input<GamepadButton> {
+        &mut self.buttons
+    }
+
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    pub fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes.insert(axis, value.clamp(-1.0, 1.0));
+    }
+
+    pub fn left_stick(&self) -> glam::Vec2 {
+        glam::Vec2::new(
+            self.axis(GamepadAxis::LeftStickX),
+            self.axis(GamepadAxis::LeftStickY),
+        )
+    }
+
+    pub fn right_stick(&self) -> glam::Vec2 {
+        glam::Vec2::new(
+            self.axis(GamepadAxis::RightStickX),
+            self.axis(GamepadAxis::RightStickY),
+        )
+    }
+}
+
+pub struct Gamepads {
+    gamepads: HashMap<GamepadId, Gamepad>,
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self {
+            gamepads: HashMap::new(),
+        }
+    }
+
+    pub fn connect(&mut self, id: GamepadId) {
+        self.gamepads.entry(id).or_insert_with(Gamepad::new);
+    }
+
+    pub fn disconnect(&mut self, id: GamepadId) {
+        self.gamepads.remove(&id);
+    }
+
+    pub fn get(&self, id: GamepadId) -> Option<&Gamepad> {
+        self.gamepads.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: GamepadId) -> Option<&mut Gamepad> {
+        self.gamepads.get_mut(&id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &GamepadId> {
+        self.gamepads.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&GamepadId, &Gamepad)> {
+        self.gamepads.iter()
+    }
+
+    pub fn clear(&mut self) {
+        for gamepad in self.gamepads.values_mut() {
+            gamepad.buttons.clear();
+        }
+    }
+}
+
+impl Resource for Gamepads {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,146 @@
+use crate::ecs::Resource;
+use std::{collections::HashSet, hash::Hash};
+
+pub mod action;
+pub mod event;
+pub mod gamepad;
+pub mod mouse;
+pub mod plugin;
+pub mod replay;
+
+pub use action::*;
+pub use event::*;
+pub use gamepad::*;
+pub use mouse::*;
+pub use plugin::*;
+pub use replay::*;
+
+pub type KeyCode = winit::event::VirtualKeyCode;
+pub type MouseButton = winit::event::MouseButton;
+
+pub struct Input<T: Copy + Eq + Hash + 'static> {
+    pressed: HashSet<T>,
+    just_pressed: HashSet<T>,
+    just_released: HashSet<T>,
+}
+
+impl<T: Copy + Eq + Hash + 'static> Input<T> {
+    pub fn new() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+        }
+    }
+
+    pub fn press(&mut self, input: T) {
+        if self.pressed.insert(input) {
+            self.just_pressed.insert(input);
+        }
+    }
+
+    pub fn release(&mut self, input: T) {
+        if self.pressed.remove(&input) {
+            self.just_released.insert(input);
+        }
+    }
+
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+    }
+
+    pub fn pressed(&self, input: T) -> bool {
+        self.pressed.contains(&input)
+    }
+
+    pub fn just_pressed(&self, input: T) -> bool {
+        self.just_pressed.contains(&input)
+    }
+
+    pub fn just_released(&self, input: T) -> bool {
+        self.just_released.contains(&input)
+    }
+
+    pub fn any_pressed(&self, inputs: impl IntoIterator<Item = T>) -> bool {
+        inputs.into_iter().any(|input| self.pressed(input))
+    }
+
+    pub fn all_pressed(&self, inputs: impl IntoIterator<Item = T>) -> bool {
+        inputs.into_iter().all(|input| self.pressed(input))
+    }
+
+    pub fn any_just_pressed(&self, inputs: impl IntoIterator<Item = T>) -> bool {
+        inputs.into_iter().any(|input| self.just_pressed(input))
+    }
+
+    pub fn get_pressed(&self) -> impl Iterator<Item = &T> {
+        self.pressed.iter()
+    }
+
+    pub fn get_just_pressed(&self) -> impl Iterator<Item = &T> {
+        self.just_pressed.iter()
+    }
+
+    pub fn get_just_released(&self) -> impl Iterator<Item = &T> {
+        self.just_released.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    pub fn reset(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+impl<T: Copy + Eq + Hash + 'static> Resource for Input<T> {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub struct TextInput {
+    text: String,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.text.chars()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+}
+
+impl Resource for TextInput {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
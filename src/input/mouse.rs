@@ -0,0 +1,66 @@
+use crate::ecs::Resource;
+
+pub struct Mouse {
+    position: glam::Vec2,
+    delta: glam::Vec2,
+    wheel: glam::Vec2,
+    inside: bool,
+}
+
+impl Mouse {
+    pub fn new() -> Self {
+        Self {
+            position: glam::Vec2::ZERO,
+            delta: glam::Vec2::ZERO,
+            wheel: glam::Vec2::ZERO,
+            inside: false,
+        }
+    }
+
+    pub fn position(&self) -> glam::Vec2 {
+        self.position
+    }
+
+    pub fn delta(&self) -> glam::Vec2 {
+        self.delta
+    }
+
+    pub fn wheel(&self) -> glam::Vec2 {
+        self.wheel
+    }
+
+    pub fn inside(&self) -> bool {
+        self.inside
+    }
+
+    pub fn set_position(&mut self, position: glam::Vec2) {
+        self.position = position;
+    }
+
+    pub fn set_inside(&mut self, inside: bool) {
+        self.inside = inside;
+    }
+
+    pub fn add_delta(&mut self, delta: glam::Vec2) {
+        self.delta += delta;
+    }
+
+    pub fn add_wheel(&mut self, wheel: glam::Vec2) {
+        self.wheel += wheel;
+    }
+
+    pub fn clear(&mut self) {
+        self.delta = glam::Vec2::ZERO;
+        self.wheel = glam::Vec2::ZERO;
+    }
+}
+
+impl Resource for Mouse {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,100 @@
+use super::{
+    record_input, replay_input, save_recording, update_actions, update_input, ActionMap,
+    ActionState, Gamepads, Input, InputEvents, InputRecorder, InputRecording, KeyCode, Mouse,
+    MouseButton, TextInput,
+};
+use crate::{
+    game::{GameBuilder, Plugin},
+    schedule::ScenePhase,
+};
+use std::path::PathBuf;
+
+#[derive(Default)]
+pub struct InputPlugin {
+    bindings: Option<PathBuf>,
+    record: Option<PathBuf>,
+    replay: Option<(PathBuf, bool)>,
+}
+
+impl InputPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bindings(mut self, path: impl Into<PathBuf>) -> Self {
+        self.bindings = Some(path.into());
+        self
+    }
+
+    pub fn with_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    pub fn with_replay(mut self, path: impl Into<PathBuf>, exit_on_finish: bool) -> Self {
+        self.replay = Some((path.into(), exit_on_finish));
+        self
+    }
+
+    fn recorder(&self, game: &GameBuilder) -> Option<InputRecorder> {
+        let fixed_delta = game.timer().borrow().fixed_delta();
+
+        if let Some((path, exit)) = &self.replay {
+            match InputRecording::load(path) {
+                Ok(recording) => {
+                    if recording.fixed_delta != fixed_delta {
+                        eprintln!(
+                            "Replay {} was recorded with a different fixed delta",
+                            path.display()
+                        );
+                    }
+                    return Some(InputRecorder::replay(recording, *exit));
+                }
+                Err(e) => eprintln!("Failed to load replay {}: {}", path.display(), e),
+            }
+        }
+
+        self.record
+            .as_ref()
+            .map(|path| InputRecorder::record(path.clone(), fixed_delta))
+    }
+}
+
+impl Plugin for InputPlugin {
+    fn build(&self, game: &mut GameBuilder) {
+        game.add_resource(InputEvents::new());
+        game.add_resource(Input::<KeyCode>::new());
+        game.add_resource(Input::<MouseButton>::new());
+        game.add_resource(Mouse::new());
+        game.add_resource(TextInput::new());
+        game.add_resource(Gamepads::new());
+        game.add_resource(ActionState::new());
+
+        let bindings = self.bindings.as_ref().filter(|path| path.exists());
+        if let Some(path) = bindings {
+            match ActionMap::load(path) {
+                Ok(map) => game.add_resource(map),
+                Err(e) => eprintln!("Failed to load bindings {}: {}", path.display(), e),
+            }
+        }
+
+        if !game.resources().contains::<ActionMap>() {
+            game.add_resource(ActionMap::new());
+        }
+
+        let recorder = self.recorder(game);
+        let recording = recorder.is_some();
+        if let Some(recorder) = recorder {
+            game.add_resource(recorder);
+            game.add_system(ScenePhase::Update, replay_input);
+        }
+
+        game.add_system(ScenePhase::Update, update_input);
+        game.add_system(ScenePhase::Update, update_actions);
+
+        if recording {
+            game.add_system(ScenePhase::Update, record_input);
+            game.add_system(ScenePhase::End, save_recording);
+        }
+    }
+}
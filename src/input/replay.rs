@@ -0,0 +1,157 @@
+use super::{InputEvent, InputEvents};
+use crate::{
+    ecs::{Resource, World},
+    game::GameTime,
+    scene::SceneManager,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub step: u64,
+    pub events: Vec<InputEvent>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub fixed_delta: Duration,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn new(fixed_delta: Duration) -> Self {
+        Self {
+            fixed_delta,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<InputRecording> {
+        let text = std::fs::read_to_string(path)?;
+        ron::from_str(&text)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let text = ron::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        std::fs::write(path, text)
+    }
+}
+
+enum RecorderMode {
+    Record { path: PathBuf },
+    Replay { cursor: usize, exit: bool },
+}
+
+pub struct InputRecorder {
+    mode: RecorderMode,
+    recording: InputRecording,
+}
+
+impl InputRecorder {
+    pub fn record(path: impl Into<PathBuf>, fixed_delta: Duration) -> Self {
+        Self {
+            mode: RecorderMode::Record { path: path.into() },
+            recording: InputRecording::new(fixed_delta),
+        }
+    }
+
+    pub fn replay(recording: InputRecording, exit_on_finish: bool) -> Self {
+        Self {
+            mode: RecorderMode::Replay {
+                cursor: 0,
+                exit: exit_on_finish,
+            },
+            recording,
+        }
+    }
+
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    pub fn recording_mode(&self) -> bool {
+        matches!(self.mode, RecorderMode::Record { .. })
+    }
+
+    pub fn replaying(&self) -> bool {
+        match self.mode {
+            RecorderMode::Replay { cursor, .. } => cursor < self.recording.frames.len(),
+            RecorderMode::Record { .. } => false,
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        match &self.mode {
+            RecorderMode::Record { path } => self.recording.save(path),
+            RecorderMode::Replay { .. } => Ok(()),
+        }
+    }
+
+    fn next_frame(&mut self) -> Option<RecordedFrame> {
+        match &mut self.mode {
+            RecorderMode::Replay { cursor, .. } => {
+                let frame = self.recording.frames.get(*cursor).cloned();
+                *cursor += 1;
+                frame
+            }
+            RecorderMode::Record { .. } => None,
+        }
+    }
+}
+
+impl Resource for InputRecorder {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+pub fn replay_input(world: &World) {
+    let mut recorder = world.resource_mut::<InputRecorder>();
+    if !recorder.replaying() {
+        if let RecorderMode::Replay { exit: true, .. } = recorder.mode {
+            world.resource_mut::<SceneManager>().quit();
+        }
+        return;
+    }
+
+    if let Some(frame) = recorder.next_frame() {
+        let step = world.resource::<GameTime>().steps();
+        if frame.step != step {
+            eprintln!(
+                "Replay desync: recorded step {} but simulation is at step {}",
+                frame.step, step
+            );
+        }
+
+        world.resource_mut::<InputEvents>().replace(frame.events);
+    }
+}
+
+pub fn record_input(world: &World) {
+    let mut recorder = world.resource_mut::<InputRecorder>();
+    if recorder.recording_mode() {
+        let time = world.resource::<GameTime>();
+        let events = world.resource::<InputEvents>();
+        recorder.recording.frames.push(RecordedFrame {
+            step: time.steps(),
+            events: events.frame().to_vec(),
+        });
+    }
+}
+
+pub fn save_recording(world: &World) {
+    let recorder = world.resource::<InputRecorder>();
+    if let Err(e) = recorder.save() {
+        eprintln!("Failed to save input recording: {}", e);
+    }
+}
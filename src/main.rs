@@ -3,9 +3,12 @@ use ecs::{world::Write, EntityId, World};
 use crate::{ecs::world::events::CreateEntity, game::Game};
 
 pub mod asset;
+pub mod console;
+pub mod diagnostics;
 pub mod ecs;
 pub mod game;
 pub mod graphics;
+pub mod input;
 pub mod scene;
 pub mod schedule;
 pub mod shared;
@@ -14,10 +17,6 @@ pub mod spatial;
 pub struct MainScene;
 
 impl scene::Scene for MainScene {
-    fn name(&self) -> &str {
-        todo!()
-    }
-
     fn scheduler(&self) -> schedule::Scheduler {
         let schedule = schedule::Schedule::new().add_system(test_system).flush();
 
@@ -7,7 +7,11 @@ use std::{any::TypeId, collections::HashMap};
 pub type SceneId = ResourceType;
 
 pub trait Scene: 'static {
-    fn name(&self) -> &str;
+    fn name(&self) -> &str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     fn scheduler(&self) -> Scheduler;
     fn observers(&self) -> Option<Observers> {
         None
@@ -48,6 +52,20 @@ impl SceneManager {
             .map(|id| self.scenes.get(&id).expect("Scene doesn't exist"))
     }
 
+    pub fn find(&self, name: &str) -> Option<SceneId> {
+        self.scenes
+            .iter()
+            .find(|(_, scene)| scene.name() == name)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.scenes
+            .values()
+            .map(|scene| scene.name().to_string())
+            .collect()
+    }
+
     pub fn set_next(&mut self, id: SceneId) {
         self.next = Some(id);
     }
@@ -65,7 +83,7 @@ impl SceneManager {
             return None;
         }
 
-        if let Some(next) = self.next() {
+        if let Some(next) = self.next.take() {
             self.current = next;
             Some(self.current_scene().scheduler())
         } else {
@@ -1,9 +1,13 @@
-use crate::ecs::{observer::EventManager, System, World};
-use std::collections::HashMap;
+use crate::{
+    diagnostics::{profiling, Diagnostics},
+    ecs::{observer::EventManager, System, World},
+};
+use std::{collections::HashMap, time::Instant};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ScenePhase {
     Start,
+    PreUpdate,
     Update,
     PostUpdate,
     PreRender,
@@ -13,6 +17,10 @@ pub enum ScenePhase {
 
 fn flush(world: &World) {
     let mut events = world.resource_mut::<EventManager>().take();
+    if let Some(mut diagnostics) = world.try_resource_mut::<Diagnostics>() {
+        diagnostics.add_events(events.count());
+    }
+
     events.flush(world);
     world.resource_mut::<EventManager>().give(events);
 }
@@ -39,8 +47,22 @@ impl Schedule {
     }
 
     pub fn run(&self, world: &World) {
+        if !profiling(world) {
+            for system in &self.systems {
+                system.run(&world);
+            }
+
+            return;
+        }
+
         for system in &self.systems {
-            system.run(&world);
+            let start = Instant::now();
+            system.run(world);
+            let duration = start.elapsed();
+
+            if let Some(mut diagnostics) = world.try_resource_mut::<Diagnostics>() {
+                diagnostics.record_span("system", system.name(), start, duration);
+            }
         }
     }
 }
@@ -82,6 +104,17 @@ impl Scheduler {
         }
     }
 
+    pub fn add_system<T: System>(&mut self, phase: ScenePhase, system: T) {
+        let schedules = self.schedules.entry(phase).or_insert_with(Vec::new);
+        if schedules.is_empty() {
+            schedules.push(Schedule::new());
+        }
+
+        if let Some(schedule) = schedules.last_mut() {
+            schedule.systems.push(Box::new(system));
+        }
+    }
+
     pub fn run(&mut self, phase: ScenePhase, world: &World) {
         if let Some(schedules) = self.schedules.get_mut(&phase) {
             for schedule in schedules {
@@ -1,10 +1,14 @@
-use std::{ffi::OsStr, path::Path};
+use std::{
+    ffi::OsStr,
+    path::{Component, Path, PathBuf},
+};
 
 pub trait PathExt {
     fn trim_prefix(&self, prefix: &str) -> String;
     fn visit_dirs<T>(&self, cb: &mut dyn FnMut(&Path) -> T) -> std::io::Result<()>;
     fn extension_str(&self) -> &str;
     fn standardize(&self) -> String;
+    fn normalize(&self) -> PathBuf;
 }
 
 impl PathExt for Path {
@@ -45,4 +49,22 @@ impl PathExt for Path {
             .to_str()
             .unwrap_or("")
     }
+
+    fn normalize(&self) -> PathBuf {
+        let mut components = Vec::new();
+        for component in self.components() {
+            match component {
+                Component::CurDir if !components.is_empty() => {}
+                Component::ParentDir => match components.last() {
+                    Some(Component::Normal(_)) => {
+                        components.pop();
+                    }
+                    _ => components.push(component),
+                },
+                _ => components.push(component),
+            }
+        }
+
+        components.iter().collect()
+    }
 }
@@ -64,76 +64,43 @@ impl<T: Object3D> TreeNode<T> {
         result
     }
 
+    pub fn query_with<F: Fn(&Bounds) -> bool>(&self, test: &F) -> Vec<&T> {
+        let mut result = Vec::new();
+
+        if test(&self.bounds) {
+            for object in self.objects.iter() {
+                if test(object.bounds()) {
+                    result.push(object);
+                }
+            }
+
+            if let Some(children) = &self.children {
+                for child in children.iter() {
+                    result.append(&mut child.query_with(test));
+                }
+            }
+        }
+
+        result
+    }
+
     fn split(&mut self) {
+        let min = self.bounds.min;
         let half_size = self.bounds.size() / 2.0;
-        let children = [
-            Box::new(TreeNode::new(
-                Bounds::new(self.bounds.min, self.bounds.min + half_size),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(half_size.x, 0.0, 0.0),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, 0.0),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(0.0, half_size.y, 0.0),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, half_size.z),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, 0.0),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, half_size.z),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(0.0, 0.0, half_size.z),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, half_size.z),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(half_size.x, 0.0, half_size.z),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, half_size.z),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
-            Box::new(TreeNode::new(
-                Bounds::new(
-                    self.bounds.min + glam::Vec3::new(0.0, half_size.y, half_size.z),
-                    self.bounds.min + glam::Vec3::new(half_size.x, half_size.y, half_size.z),
-                ),
-                self.depth + 1,
-                self.max_depth,
-                self.max_objects,
-            )),
+        let children = [0, 1, 2, 3, 4, 5, 6, 7].map(|octant| {
+            let offset = glam::Vec3::new(
+                (octant & 1) as f32 * half_size.x,
+                ((octant >> 1) & 1) as f32 * half_size.y,
+                ((octant >> 2) & 1) as f32 * half_size.z,
+            );
+
             Box::new(TreeNode::new(
-                Bounds::new(self.bounds.min + half_size, self.bounds.max),
+                Bounds::new(min + offset, min + offset + half_size),
                 self.depth + 1,
                 self.max_depth,
                 self.max_objects,
-            )),
-        ];
+            ))
+        });
 
         self.children = Some(children);
 
@@ -175,6 +142,10 @@ impl<T: Object3D> OctTree<T> {
         self.root.query(bounds)
     }
 
+    pub fn query_with<F: Fn(&Bounds) -> bool>(&self, test: F) -> Vec<&T> {
+        self.root.query_with(&test)
+    }
+
     pub fn remove(&mut self, object: &T) {
         self.root.remove(object);
     }